@@ -3,7 +3,10 @@ use std::sync::Arc;
 use tickify_api::{
     config,
     database::{connection::create_pool, AppState},
-    models::user::{CreateUserPayload, Role, Status, User},
+    models::{
+        auth::revocation::RevocationCache,
+        user::{CreateUserPayload, Role, Status, User},
+    },
     validations::uniqueness::is_user_unique,
 };
 use tracing::{error, info};
@@ -35,7 +38,21 @@ async fn main() {
         }
     };
 
-    let state = Arc::new(AppState { db: pool.clone() });
+    let jwt_keys = match config::Config::jwt_keys() {
+        Ok(keys) => keys,
+        Err(e) => {
+            error!("❌ Error loading the JWT keys: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    let state = Arc::new(AppState {
+        db: pool.clone(),
+        storage: config::Config::storage(),
+        revocations: RevocationCache::default(),
+        jwt_keys,
+        oidc: None,
+    });
 
     let user = CreateUserPayload {
         username: args.username,
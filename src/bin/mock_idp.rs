@@ -0,0 +1,335 @@
+//! A mock OpenID Connect provider, to try and test the single sign-on locally.
+//!
+//! Anyone signs in without a password, as the user of the `login_hint` sent by `/auth/oidc/login`
+//! or the default one, and the ID tokens are signed with the JWT signing key of the API.
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Redirect, Response},
+    routing::{get, post},
+    Form, Json, Router,
+};
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use chrono::Utc;
+use clap::Parser;
+use jsonwebtoken::{encode, jwk::JwkSet, Algorithm, EncodingKey, Header};
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    env,
+    sync::{Arc, Mutex},
+};
+use tickify_api::config::Config;
+use tracing::{error, info};
+use uuid::Uuid;
+
+#[derive(clap::Parser, Debug)]
+#[command(version, about)]
+pub struct Args {
+    /// Address to listen on, the issuer being `http://<host>`.
+    #[arg(long, default_value = "127.0.0.1:9000")]
+    host: String,
+
+    /// Email of the user signing in, unless the login sends a `login_hint`.
+    #[arg(short, long, default_value = "jane.doe@example.com")]
+    email: String,
+
+    /// Groups of the user signing in, sent in the `groups` claim.
+    #[arg(short, long, value_delimiter = ',')]
+    groups: Vec<String>,
+}
+
+/// A code issued to the client, waiting to be exchanged for an ID token.
+struct Authorization {
+    redirect_uri: String,
+    code_challenge: String,
+    nonce: Option<String>,
+    email: String,
+}
+
+struct MockIdp {
+    issuer: String,
+    client_id: String,
+    client_secret: Option<String>,
+    kid: String,
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    jwks: JwkSet,
+    email: String,
+    groups: Vec<String>,
+    codes: Mutex<HashMap<String, Authorization>>,
+}
+
+#[derive(Deserialize)]
+struct AuthorizeQuery {
+    response_type: String,
+    client_id: String,
+    redirect_uri: String,
+    state: String,
+    nonce: Option<String>,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
+    login_hint: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenForm {
+    grant_type: String,
+    code: String,
+    redirect_uri: String,
+    code_verifier: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+}
+
+fn oauth_error(status: StatusCode, error: &str, description: &str) -> Response {
+    (
+        status,
+        Json(json!({ "error": error, "error_description": description })),
+    )
+        .into_response()
+}
+
+async fn discovery(State(idp): State<Arc<MockIdp>>) -> impl IntoResponse {
+    Json(json!({
+        "issuer": idp.issuer,
+        "authorization_endpoint": format!("{}/authorize", idp.issuer),
+        "token_endpoint": format!("{}/token", idp.issuer),
+        "jwks_uri": format!("{}/jwks", idp.issuer),
+        "response_types_supported": ["code"],
+        "subject_types_supported": ["public"],
+        "id_token_signing_alg_values_supported": [format!("{:?}", idp.algorithm)],
+        "code_challenge_methods_supported": ["S256"],
+        "token_endpoint_auth_methods_supported": ["client_secret_basic", "client_secret_post"],
+    }))
+}
+
+async fn jwks(State(idp): State<Arc<MockIdp>>) -> impl IntoResponse {
+    Json(idp.jwks.clone())
+}
+
+/// Signs the user in at once, redirecting back to the client with a code.
+async fn authorize(
+    State(idp): State<Arc<MockIdp>>,
+    Query(query): Query<AuthorizeQuery>,
+) -> Response {
+    if query.client_id != idp.client_id {
+        return oauth_error(
+            StatusCode::BAD_REQUEST,
+            "unauthorized_client",
+            "unknown client",
+        );
+    }
+
+    let Ok(mut redirect_uri) = Url::parse(&query.redirect_uri) else {
+        return oauth_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            "invalid redirect_uri",
+        );
+    };
+
+    let code_challenge = match (query.code_challenge, query.code_challenge_method.as_deref()) {
+        (Some(code_challenge), Some("S256")) if query.response_type == "code" => code_challenge,
+        _ => {
+            redirect_uri
+                .query_pairs_mut()
+                .append_pair("error", "invalid_request")
+                .append_pair(
+                    "error_description",
+                    "the code response type with an S256 PKCE challenge is required",
+                )
+                .append_pair("state", &query.state);
+            return Redirect::to(redirect_uri.as_str()).into_response();
+        }
+    };
+
+    let email = query.login_hint.unwrap_or_else(|| idp.email.clone());
+    let code = Uuid::new_v4().simple().to_string();
+    info!("Signing in {email}");
+
+    idp.codes.lock().unwrap().insert(
+        code.clone(),
+        Authorization {
+            redirect_uri: query.redirect_uri,
+            code_challenge,
+            nonce: query.nonce,
+            email,
+        },
+    );
+
+    redirect_uri
+        .query_pairs_mut()
+        .append_pair("code", &code)
+        .append_pair("state", &query.state);
+    Redirect::to(redirect_uri.as_str()).into_response()
+}
+
+/// Exchanges a code for an ID token, checking the client credentials and the PKCE code verifier.
+async fn token(
+    State(idp): State<Arc<MockIdp>>,
+    headers: HeaderMap,
+    Form(form): Form<TokenForm>,
+) -> Response {
+    let basic = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok()?.strip_prefix("Basic "))
+        .and_then(|credentials| String::from_utf8(STANDARD.decode(credentials).ok()?).ok())
+        .and_then(|credentials| {
+            let (id, secret) = credentials.split_once(':')?;
+            Some((id.to_string(), secret.to_string()))
+        });
+    let (client_id, client_secret) = match basic {
+        Some((id, secret)) => (Some(id), Some(secret)),
+        None => (form.client_id, form.client_secret),
+    };
+
+    if client_id.as_deref() != Some(idp.client_id.as_str())
+        || (idp.client_secret.is_some() && client_secret != idp.client_secret)
+    {
+        return oauth_error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            "invalid client credentials",
+        );
+    }
+
+    if form.grant_type != "authorization_code" {
+        return oauth_error(
+            StatusCode::BAD_REQUEST,
+            "unsupported_grant_type",
+            "unsupported grant type",
+        );
+    }
+
+    let Some(authorization) = idp.codes.lock().unwrap().remove(&form.code) else {
+        return oauth_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_grant",
+            "unknown or used code",
+        );
+    };
+
+    if authorization.redirect_uri != form.redirect_uri {
+        return oauth_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_grant",
+            "redirect_uri mismatch",
+        );
+    }
+
+    let code_challenge = form
+        .code_verifier
+        .map(|verifier| URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes())));
+    if code_challenge.as_deref() != Some(authorization.code_challenge.as_str()) {
+        return oauth_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_grant",
+            "PKCE verification failed",
+        );
+    }
+
+    let email = authorization.email;
+    let local_part = email.split('@').next().unwrap_or_default();
+    let mut names = local_part.split('.').map(|name| {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+    });
+    let now = Utc::now().timestamp();
+
+    let claims = json!({
+        "iss": idp.issuer,
+        "sub": format!("{:x}", Sha256::digest(email.as_bytes()))[..32],
+        "aud": idp.client_id,
+        "iat": now,
+        "exp": now + 300,
+        "nonce": authorization.nonce,
+        "email": email,
+        "email_verified": true,
+        "preferred_username": local_part,
+        "given_name": names.next().flatten(),
+        "family_name": names.next().flatten(),
+        "groups": idp.groups,
+    });
+
+    let mut header = Header::new(idp.algorithm);
+    header.kid = Some(idp.kid.clone());
+
+    match encode(&header, &claims, &idp.encoding_key) {
+        Ok(id_token) => Json(json!({
+            "access_token": Uuid::new_v4().simple().to_string(),
+            "token_type": "Bearer",
+            "expires_in": 300,
+            "id_token": id_token,
+        }))
+        .into_response(),
+        Err(e) => oauth_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "server_error",
+            &e.to_string(),
+        ),
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    Config::init().expect("Config error");
+    let args = Args::parse();
+
+    let keys = match Config::jwt_keys() {
+        Ok(keys) if !keys.jwks.keys.is_empty() => keys,
+        Ok(_) => {
+            error!("❌ The mock identity provider signs with the JWT signing key: set JWT_SIGNING_KEY_FILE");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            error!("❌ Error loading the JWT keys: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    let idp = Arc::new(MockIdp {
+        issuer: format!("http://{}", args.host),
+        client_id: env::var("OIDC_CLIENT_ID").unwrap_or_else(|_| "tickify".to_string()),
+        client_secret: env::var("OIDC_CLIENT_SECRET")
+            .ok()
+            .filter(|secret| !secret.is_empty()),
+        kid: keys.kid,
+        algorithm: keys.algorithm,
+        encoding_key: keys.encoding_key,
+        jwks: keys.jwks,
+        email: args.email,
+        groups: args.groups,
+        codes: Mutex::new(HashMap::new()),
+    });
+
+    let app = Router::new()
+        .route("/.well-known/openid-configuration", get(discovery))
+        .route("/jwks", get(jwks))
+        .route("/authorize", get(authorize))
+        .route("/token", post(token))
+        .with_state(idp.clone());
+
+    let listener = match tokio::net::TcpListener::bind(&args.host).await {
+        Ok(listener) => {
+            info!("✅ Mock identity provider started at: {}", idp.issuer);
+            listener
+        }
+        Err(e) => {
+            error!("❌ Error starting the mock identity provider: {e}");
+            std::process::exit(1)
+        }
+    };
+
+    axum::serve(listener, app)
+        .await
+        .expect("Error starting the mock identity provider");
+}
@@ -0,0 +1,27 @@
+use super::Config;
+use chrono::{Duration, TimeDelta};
+use std::env;
+
+impl Config {
+    /// How long a refresh token can be exchanged for a new access token.
+    pub fn refresh_token_lifetime() -> TimeDelta {
+        let seconds: i64 = env::var("REFRESH_TOKEN_EXPIRATION_TIME").map_or(2592000, |value| {
+            value
+                .parse()
+                .expect("Invalid REFRESH_TOKEN_EXPIRATION_TIME value")
+        });
+
+        Duration::seconds(seconds)
+    }
+
+    /// How often the revocation cache is reloaded, picking up the revocations made by other instances.
+    pub fn revocation_sync_interval() -> std::time::Duration {
+        let seconds = env::var("REVOCATION_SYNC_INTERVAL").map_or(30, |value| {
+            value
+                .parse()
+                .expect("Invalid REVOCATION_SYNC_INTERVAL value")
+        });
+
+        std::time::Duration::from_secs(seconds)
+    }
+}
@@ -0,0 +1,48 @@
+use super::Config;
+use crate::{errors::config_error::ConfigError, utils::keys::JwtKeys};
+use std::{env, fs};
+
+impl Config {
+    /// Loads the keys signing and verifying the JWTs.
+    ///
+    /// `JWT_SIGNING_KEY_FILE` selects RS256 or EdDSA, depending on its PEM key, verified with the public keys of
+    /// `JWT_VERIFICATION_KEYS`; otherwise HS256 is used with `JWT_SECRET`.
+    pub fn jwt_keys() -> Result<JwtKeys, ConfigError> {
+        let kid = env::var("JWT_SIGNING_KEY_ID").unwrap_or_else(|_| "default".to_string());
+
+        match env::var("JWT_SIGNING_KEY_FILE") {
+            Ok(path) if !path.is_empty() => {
+                let private_key = read_key(&kid, &path)?;
+
+                let public_keys = env::var("JWT_VERIFICATION_KEYS")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|pair| !pair.is_empty())
+                    .map(|pair| match pair.split_once('=') {
+                        Some((kid, path)) => Ok((kid.to_string(), read_key(kid, path)?)),
+                        None => Err(ConfigError::InvalidJwtKey {
+                            kid: pair.to_string(),
+                            reason: String::from("expected a `kid=path` pair"),
+                        }),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                JwtKeys::from_pem(kid, &private_key, &public_keys)
+            }
+            _ => match env::var("JWT_SECRET") {
+                Ok(secret) if !secret.is_empty() => {
+                    Ok(JwtKeys::from_secret(kid, secret.as_bytes()))
+                }
+                _ => Err(ConfigError::MissingJwtKey),
+            },
+        }
+    }
+}
+
+fn read_key(kid: &str, path: &str) -> Result<Vec<u8>, ConfigError> {
+    fs::read(path).map_err(|e| ConfigError::InvalidJwtKey {
+        kid: kid.to_string(),
+        reason: format!("cannot read {path}: {e}"),
+    })
+}
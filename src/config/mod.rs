@@ -1,8 +1,19 @@
 use crate::errors::config_error::ConfigError;
 
+mod auth;
 mod cors;
 mod environment;
+mod jwt;
 mod logger;
+mod numbering;
+mod oidc;
+mod sla;
+mod storage;
+mod trash;
+
+pub use oidc::OidcSettings;
+pub use sla::SlaTargets;
+pub use storage::AttachmentLimits;
 
 pub struct Config {}
 
@@ -0,0 +1,9 @@
+use super::Config;
+use std::env;
+
+impl Config {
+    /// The prefix of the human-readable ticket numbers, like `TCK-` in `TCK-000123`.
+    pub fn ticket_number_prefix() -> String {
+        env::var("TICKET_NUMBER_PREFIX").unwrap_or_else(|_| String::from("TCK-"))
+    }
+}
@@ -0,0 +1,58 @@
+use super::Config;
+use crate::models::user::Role;
+use std::env;
+
+/// The OpenID Connect provider users sign in with, and how its claims map to roles.
+pub struct OidcSettings {
+    /// The issuer of the provider, matching the `iss` claim of its ID tokens.
+    pub issuer: String,
+    pub client_id: String,
+    /// Sent to the token endpoint with HTTP Basic authentication, when the client is confidential.
+    pub client_secret: Option<String>,
+    pub redirect_uri: String,
+    pub scopes: String,
+    /// Path of the claim holding the groups or roles of the user, with nested claims separated by dots.
+    pub role_claim: String,
+    /// Claim values granting a role.
+    pub role_mapping: Vec<(String, Role)>,
+}
+
+impl Config {
+    /// Loads the OpenID Connect settings, single sign-on being disabled without `OIDC_ISSUER`.
+    pub fn oidc() -> Option<OidcSettings> {
+        let issuer = env::var("OIDC_ISSUER")
+            .ok()
+            .filter(|issuer| !issuer.is_empty())?;
+
+        let client_id = env::var("OIDC_CLIENT_ID").expect("Missing OIDC_CLIENT_ID value");
+        let client_secret = env::var("OIDC_CLIENT_SECRET")
+            .ok()
+            .filter(|secret| !secret.is_empty());
+        let redirect_uri = env::var("OIDC_REDIRECT_URI").expect("Missing OIDC_REDIRECT_URI value");
+        let scopes = env::var("OIDC_SCOPES").unwrap_or_else(|_| "openid email profile".to_string());
+        let role_claim = env::var("OIDC_ROLE_CLAIM").unwrap_or_else(|_| "groups".to_string());
+
+        let role_mapping = env::var("OIDC_ROLE_MAPPING")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.split_once('=') {
+                Some((value, "admin")) => (value.to_string(), Role::Admin),
+                Some((value, "moderator")) => (value.to_string(), Role::Moderator),
+                Some((value, "user")) => (value.to_string(), Role::User),
+                _ => panic!("Invalid OIDC_ROLE_MAPPING value: {pair}"),
+            })
+            .collect();
+
+        Some(OidcSettings {
+            issuer,
+            client_id,
+            client_secret,
+            redirect_uri,
+            scopes,
+            role_claim,
+            role_mapping,
+        })
+    }
+}
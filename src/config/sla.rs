@@ -0,0 +1,37 @@
+use super::Config;
+use crate::models::ticket::TicketPriority;
+use chrono::{Duration, TimeDelta};
+use std::env;
+
+/// Response and resolution targets of a ticket priority.
+pub struct SlaTargets {
+    pub response: TimeDelta,
+    pub resolution: TimeDelta,
+}
+
+/// Reads a target in seconds from the environment, falling back to the default one.
+fn target(name: &str, default: i64) -> TimeDelta {
+    let seconds = env::var(name).map_or(default, |value| {
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("Invalid {name} value"))
+    });
+
+    Duration::seconds(seconds)
+}
+
+impl Config {
+    pub fn sla_targets(priority: &TicketPriority) -> SlaTargets {
+        let (name, response, resolution) = match priority {
+            TicketPriority::Low => ("LOW", 24 * 3600, 7 * 24 * 3600),
+            TicketPriority::Normal => ("NORMAL", 8 * 3600, 3 * 24 * 3600),
+            TicketPriority::High => ("HIGH", 2 * 3600, 24 * 3600),
+            TicketPriority::Urgent => ("URGENT", 30 * 60, 4 * 3600),
+        };
+
+        SlaTargets {
+            response: target(&format!("SLA_{name}_RESPONSE_TIME"), response),
+            resolution: target(&format!("SLA_{name}_RESOLUTION_TIME"), resolution),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use super::Config;
+use crate::storage::{local::LocalStorage, Storage};
+use std::{env, sync::Arc};
+
+/// Size and type limits of the uploaded attachments.
+pub struct AttachmentLimits {
+    pub max_size: usize,
+    pub allowed_types: Vec<String>,
+}
+
+impl Config {
+    pub fn storage() -> Arc<dyn Storage> {
+        match env::var("STORAGE_BACKEND").as_deref().unwrap_or("local") {
+            "local" => Arc::new(LocalStorage::new(
+                env::var("STORAGE_PATH").unwrap_or_else(|_| "uploads".to_string()),
+            )),
+            backend => panic!("Invalid STORAGE_BACKEND value: {backend}"),
+        }
+    }
+
+    pub fn attachment_limits() -> AttachmentLimits {
+        let max_size = env::var("ATTACHMENT_MAX_SIZE").map_or(10 * 1024 * 1024, |value| {
+            value.parse().expect("Invalid ATTACHMENT_MAX_SIZE value")
+        });
+
+        let allowed_types = env::var("ATTACHMENT_ALLOWED_TYPES")
+            .unwrap_or_else(|_| "image/png,image/jpeg,text/plain,application/pdf".to_string())
+            .split(',')
+            .map(|mime| mime.trim().to_lowercase())
+            .filter(|mime| !mime.is_empty())
+            .collect();
+
+        AttachmentLimits {
+            max_size,
+            allowed_types,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use super::Config;
+use chrono::{Duration, TimeDelta};
+use std::env;
+
+impl Config {
+    /// How long deleted tickets stay in the trash before being purged, `None` keeping them forever.
+    pub fn trash_retention() -> Option<TimeDelta> {
+        let days: i64 = env::var("TRASH_RETENTION_DAYS").map_or(30, |value| {
+            value.parse().expect("Invalid TRASH_RETENTION_DAYS value")
+        });
+
+        (days > 0).then(|| Duration::days(days))
+    }
+
+    /// How often the trash is checked for tickets past the retention period.
+    pub fn trash_purge_interval() -> std::time::Duration {
+        let seconds = env::var("TRASH_PURGE_INTERVAL").map_or(3600, |value| {
+            value.parse().expect("Invalid TRASH_PURGE_INTERVAL value")
+        });
+
+        std::time::Duration::from_secs(seconds)
+    }
+}
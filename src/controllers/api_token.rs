@@ -0,0 +1,150 @@
+use crate::database::AppState;
+use crate::errors::api_error::ApiError;
+use crate::models::auth::access::AccessControl;
+use crate::models::auth::api_token::{
+    ApiToken, ApiTokenPublic, CreateApiTokenPayload, CreatedApiToken,
+};
+use crate::models::DeletePayload;
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+use validator::Validate;
+
+/// Retrieves the API tokens of the current user.
+///
+/// This endpoint lists the tokens with their scopes, expiration and last use.
+/// The tokens themselves are never returned again after their creation.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/tokens",
+    tags = ["API tokens"],
+    summary = "List the API tokens of the current user.",
+    description = "Fetches the API tokens of the current user, from the newest to the oldest. If there are no tokens, returns an empty array.",
+    security(
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "API tokens retrieved successfully.", body = Vec<ApiTokenPublic>),
+        (status = 401, description = "Not logged in."),
+        (status = 403, description = "Logged in with an API token."),
+        (status = 500, description = "An error occurred while retrieving the API tokens.")
+    )
+)]
+pub async fn find_api_tokens(
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = access.user().id;
+    debug!("Received request to retrieve API tokens of user with id: {user_id}");
+
+    match ApiToken::find_by_user(&state, user_id).await {
+        Ok(api_tokens) => {
+            info!("API tokens of user {user_id} listed successfully.");
+            Ok(Json(api_tokens))
+        }
+        Err(e) => {
+            error!("Error retrieving API tokens of user {user_id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Creates an API token for the current user.
+///
+/// This endpoint creates a named token acting as the current user within its scopes, until it expires,
+/// for scripts and integrations. The token is only returned in this response.
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/me/tokens",
+    tags = ["API tokens"],
+    summary = "Create an API token.",
+    description = "This endpoint creates an API token for the current user. Send it in the `X-Api-Key` header, or as a bearer token in `Authorization`. It is only shown once.",
+    request_body = CreateApiTokenPayload,
+    security(
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 201, description = "API token created successfully.", body = CreatedApiToken),
+        (status = 400, description = "Invalid input, including an empty name, no scopes or an expiration out of range."),
+        (status = 401, description = "Not logged in."),
+        (status = 403, description = "Logged in with an API token."),
+        (status = 500, description = "An error occurred while creating the API token.")
+    )
+)]
+pub async fn create_api_token(
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<CreateApiTokenPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = access.user().id;
+    debug!(
+        "Received request to create API token {} for user with id: {user_id}",
+        payload.name
+    );
+
+    // Validations
+    payload.validate()?;
+
+    let (api_token, token) = ApiToken::new(user_id, &payload);
+
+    match ApiToken::create(&state, &api_token).await {
+        Ok(_) => {
+            info!("API token created! ID: {}", api_token.id);
+            Ok((
+                StatusCode::CREATED,
+                Json(CreatedApiToken {
+                    token,
+                    api_token: ApiTokenPublic::from(&api_token),
+                }),
+            ))
+        }
+        Err(e) => {
+            error!("Error creating API token for user {user_id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Revokes an API token of the current user.
+///
+/// This endpoint deletes the token, which can no longer be used.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/me/tokens",
+    tags = ["API tokens"],
+    summary = "Revoke an API token.",
+    description = "This endpoint revokes an API token of the current user by its ID.",
+    request_body = DeletePayload,
+    security(
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 204, description = "API token revoked successfully."),
+        (status = 401, description = "Not logged in."),
+        (status = 403, description = "Logged in with an API token."),
+        (status = 404, description = "No API token of the current user found with the specified ID."),
+        (status = 500, description = "An error occurred while revoking the API token.")
+    )
+)]
+pub async fn delete_api_token(
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<DeletePayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_id = access.user().id;
+    debug!(
+        "Received request to revoke API token with id: {}",
+        payload.id
+    );
+
+    match ApiToken::delete(&state, user_id, &payload).await {
+        Ok(_) => {
+            info!("API token revoked! ID: {}", payload.id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            error!("Error revoking API token {}: {e}", payload.id);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use crate::config::Config;
+use crate::database::AppState;
+use crate::errors::api_error::ApiError;
+use crate::models::attachment::{Attachment, AttachmentPublic, UploadAttachmentForm};
+use crate::models::auth::access::AccessControl;
+use crate::models::user::Role;
+use crate::validations::access::{require_ticket_access, require_ticket_write_access};
+use axum::{
+    body::Bytes,
+    extract::{Multipart, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+/// Retrieves the attachment with the given ID, ensuring it belongs to the ticket.
+async fn find_ticket_attachment(
+    state: &AppState,
+    ticket_id: Uuid,
+    attachment_id: Uuid,
+) -> Result<Attachment, ApiError> {
+    match Attachment::find_by_id(state, attachment_id).await? {
+        Some(attachment) if attachment.ticket_id == ticket_id => Ok(attachment),
+        _ => {
+            error!("No attachment found with id {attachment_id} on ticket {ticket_id}");
+            Err(ApiError::NotFound)
+        }
+    }
+}
+
+/// Retrieves all attachments of a ticket.
+///
+/// This endpoint lists the metadata of the files attached to the ticket, ordered by upload date.
+/// If the ticket has no attachments, returns an empty array.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/{id}/attachments",
+    tags = ["Attachments"],
+    summary = "List all attachments of a ticket.",
+    description = "Fetches the metadata of all files attached to the ticket. If there are no attachments, returns an empty array.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Attachments retrieved successfully.", body = Vec<AttachmentPublic>),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 500, description = "An error occurred while retrieving the attachments.")
+    )
+)]
+pub async fn find_attachments(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve attachments of ticket with id: {id}");
+
+    require_ticket_access(&state, &access, id).await?;
+
+    match Attachment::find_by_ticket(&state, id).await {
+        Ok(attachments) => {
+            info!("Attachments of ticket {id} listed successfully.");
+            Ok(Json(attachments))
+        }
+        Err(e) => {
+            error!("Error retrieving attachments of ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Uploads a new attachment to a ticket.
+///
+/// This endpoint receives a multipart form with the file in the `file` field.
+/// The size and type of the file are checked against the configured limits
+/// before it is stored.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/{id}/attachments",
+    tags = ["Attachments"],
+    summary = "Upload a new attachment to a ticket.",
+    description = "This endpoint stores the file sent in the `file` field of a multipart form and attaches it to the ticket.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    request_body(content = UploadAttachmentForm, content_type = "multipart/form-data"),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 201, description = "Attachment uploaded successfully.", body = Uuid),
+        (status = 400, description = "No file was provided, the filename is empty or too long, or the form is invalid."),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 413, description = "The file exceeds the maximum size."),
+        (status = 415, description = "The file type is not allowed."),
+        (status = 500, description = "An error occurred while storing the attachment.")
+    )
+)]
+pub async fn upload_attachment(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to upload attachment to ticket with id: {id}");
+
+    require_ticket_write_access(&state, &access, id).await?;
+
+    let limits = Config::attachment_limits();
+
+    while let Some(mut field) = multipart.next_field().await? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let filename = field.file_name().unwrap_or("attachment").trim().to_string();
+
+        // The name is checked before the body is read, as it is stored in a VARCHAR(255).
+        if filename.is_empty() || filename.chars().count() > 255 {
+            error!("Attachment with an invalid filename rejected on ticket {id}");
+            return Err(ApiError::InvalidAttachmentName);
+        }
+
+        let content_type = field
+            .content_type()
+            .unwrap_or("application/octet-stream")
+            .to_lowercase();
+
+        if !limits.allowed_types.contains(&content_type) {
+            error!("Attachment of type {content_type} rejected on ticket {id}");
+            return Err(ApiError::UnsupportedMediaType(content_type));
+        }
+
+        // The file is read in chunks, so oversized uploads are rejected early.
+        let mut data = Vec::new();
+        while let Some(chunk) = field.chunk().await? {
+            if data.len() + chunk.len() > limits.max_size {
+                error!("Attachment {filename} exceeds the maximum size on ticket {id}");
+                return Err(ApiError::AttachmentTooLarge {
+                    max_size: limits.max_size,
+                });
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        let attachment = Attachment::new(
+            id,
+            access.user().id,
+            &filename,
+            &content_type,
+            data.len() as i64,
+        );
+
+        return match Attachment::create(&state, &attachment, &data).await {
+            Ok(_) => {
+                info!("Attachment uploaded! ID: {}", &attachment.id);
+                Ok((StatusCode::CREATED, Json(attachment.id)))
+            }
+            Err(e) => {
+                error!("Error uploading attachment to ticket {id}: {e}");
+                Err(e)
+            }
+        };
+    }
+
+    error!("No file provided to upload on ticket {id}");
+    Err(ApiError::AttachmentRequired)
+}
+
+/// Downloads an attachment of a ticket.
+///
+/// This endpoint returns the contents of the file, with the same access checks as the ticket.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/{id}/attachments/{attachment_id}",
+    tags = ["Attachments"],
+    summary = "Download an attachment of a ticket.",
+    description = "This endpoint returns the contents of the attached file, with its original name and type.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4),
+        ("attachment_id", description = "The unique identifier of the attachment.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Attachment downloaded successfully.", content_type = "application/octet-stream"),
+        (status = 404, description = "Ticket or attachment ID not found."),
+        (status = 500, description = "An error occurred while reading the attachment.")
+    )
+)]
+pub async fn download_attachment(
+    Path((id, attachment_id)): Path<(Uuid, Uuid)>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to download attachment {attachment_id} of ticket {id}");
+
+    require_ticket_access(&state, &access, id).await?;
+
+    let attachment = find_ticket_attachment(&state, id, attachment_id).await?;
+
+    let data = match attachment.load(&state).await {
+        Ok(data) => data,
+        Err(e) => {
+            error!("Error reading attachment with ID {attachment_id}: {e}");
+            return Err(e);
+        }
+    };
+
+    let mut headers = HeaderMap::new();
+
+    headers.insert(
+        header::CONTENT_TYPE,
+        attachment
+            .content_type
+            .parse()
+            .unwrap_or(header::HeaderValue::from_static("application/octet-stream")),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        format!(
+            "attachment; filename=\"{}\"",
+            attachment.filename.replace(['"', '\\', '\r', '\n'], "_")
+        )
+        .parse()
+        .unwrap_or(header::HeaderValue::from_static("attachment")),
+    );
+
+    info!("Attachment {attachment_id} downloaded.");
+    Ok((headers, Bytes::from(data)))
+}
+
+/// Deletes an attachment of a ticket.
+///
+/// This endpoint removes the file and its metadata.
+/// Only the uploader of the attachment, moderators and admins can delete it.
+/// If the attachment is successfully deleted, a 204 status code is returned.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tickets/{id}/attachments/{attachment_id}",
+    tags = ["Attachments"],
+    summary = "Delete an attachment of a ticket.",
+    description = "This endpoint deletes the attached file and its metadata.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4),
+        ("attachment_id", description = "The unique identifier of the attachment.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 204, description = "Attachment deleted successfully"),
+        (status = 404, description = "Ticket or attachment ID not found"),
+        (status = 500, description = "An error occurred while deleting the attachment")
+    )
+)]
+pub async fn delete_attachment(
+    Path((id, attachment_id)): Path<(Uuid, Uuid)>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to delete attachment {attachment_id} of ticket {id}");
+
+    require_ticket_access(&state, &access, id).await?;
+
+    let attachment = find_ticket_attachment(&state, id, attachment_id).await?;
+    access.require_owner_or_any_role(attachment.uploaded_by, &[Role::Admin, Role::Moderator])?;
+
+    match Attachment::delete(&state, &attachment).await {
+        Ok(_) => {
+            info!("Attachment deleted! ID: {attachment_id}");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            error!("Error deleting attachment with ID {attachment_id}: {e}");
+            Err(e)
+        }
+    }
+}
@@ -1,31 +1,43 @@
 use crate::{
     database::AppState,
-    errors::api_error::ApiError,
+    errors::{api_error::ApiError, auth_error::AuthError},
     models::{
-        auth::{token::VerifyTokenPayload, LoginPayload},
+        auth::{
+            refresh_token::RefreshToken,
+            revocation::RevokedToken,
+            token::{Claims, RefreshTokenPayload, TokenPair, VerifyTokenPayload},
+            LoginPayload,
+        },
         user::{CreateUserPayload, RegisterPayload, Role, Status, User},
     },
     utils::{
         hashing::verify_password,
-        jwt::{generate_jwt, validate_jwt},
+        jwt::{decode_jwt, generate_jwt},
     },
     validations::uniqueness::is_user_unique,
 };
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::DateTime;
 use std::sync::Arc;
 use tracing::{debug, error, info};
+use uuid::Uuid;
 use validator::Validate;
 
-/// Returns a JWT if the credentials passed are valid.
+/// Returns a JWT and a refresh token if the credentials passed are valid.
 #[utoipa::path(
     post,
     path = "/api/v1/auth/login",
     tags = ["Auth"],
     summary = "Returns a JTW.",
-    description = "If the credentials are correct, a JWT is returned.",
+    description = "If the credentials are correct, a short-lived JWT is returned along with a refresh token exchanging it for a new one on `/auth/refresh`.",
     request_body = LoginPayload,
     responses(
-        (status = 200, description = "Logged in successfully."),
+        (status = 200, description = "Logged in successfully.", body = TokenPair),
         (status = 401, description = "Incorrect password, unauthorized."),
         (status = 404, description = "User not found."),
     )
@@ -79,11 +91,121 @@ pub async fn login(
     };
     let user_role = user_role.to_string();
 
-    let token = generate_jwt(&payload.username, &user_role)?;
+    let user_id: Option<Uuid> = sqlx::query_scalar(r#"SELECT id FROM users WHERE username = $1;"#)
+        .bind(&payload.username)
+        .fetch_optional(&state.db)
+        .await?;
+
+    let user_id = match user_id {
+        Some(id) => id,
+        None => return Err(ApiError::NotFound),
+    };
+
+    // Every login starts a session, identified by the family of its refresh tokens.
+    let session_id = Uuid::now_v7();
+    let access_token = generate_jwt(&state.jwt_keys, &payload.username, &user_role, session_id)?;
+    let refresh_token = RefreshToken::issue(&state, user_id, session_id).await?;
 
     info!("Login successful for user: {}", payload.username);
 
-    Ok((StatusCode::OK, Json(token)))
+    Ok((
+        StatusCode::OK,
+        Json(TokenPair {
+            access_token,
+            refresh_token,
+        }),
+    ))
+}
+
+/// Exchanges a refresh token for a new JWT and refresh token.
+///
+/// Refresh tokens are single-use: presenting one again revokes every refresh token issued since the same login.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/refresh",
+    tags = ["Auth"],
+    summary = "Refresh a JWT.",
+    description = "Exchanges a refresh token for a new JWT and a new refresh token, the presented one being no longer valid. If an already used refresh token is presented, every refresh token issued since the same login is revoked.",
+    request_body = RefreshTokenPayload,
+    responses(
+        (status = 200, description = "Tokens refreshed successfully.", body = TokenPair),
+        (status = 401, description = "Invalid, expired, revoked or reused refresh token, or inactive user."),
+    )
+)]
+pub async fn refresh(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<RefreshTokenPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to refresh a token");
+
+    match RefreshToken::rotate(&state, &payload.refresh_token).await {
+        Ok((user, next, refresh_token)) => {
+            let access_token = generate_jwt(
+                &state.jwt_keys,
+                &user.username,
+                &user.role.to_string(),
+                next.family_id,
+            )?;
+            info!("Token refreshed for user: {}", user.username);
+
+            Ok((
+                StatusCode::OK,
+                Json(TokenPair {
+                    access_token,
+                    refresh_token,
+                }),
+            ))
+        }
+        Err(e) => {
+            error!("Error refreshing token: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Logs out, revoking the JWT used along with the refresh tokens of its session.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/logout",
+    tags = ["Auth"],
+    summary = "Log out.",
+    description = "Revokes the JWT of the request and the refresh tokens of its session, which can no longer be used.",
+    security(
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 204, description = "Logged out successfully."),
+        (status = 401, description = "Missing, invalid or already revoked JWT."),
+    )
+)]
+pub async fn logout(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<User>,
+    Extension(claims): Extension<Claims>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to log out user: {}", user.username);
+
+    let (jti, session_id, expires_at) = match (
+        Uuid::parse_str(&claims.jti),
+        Uuid::parse_str(&claims.sid),
+        DateTime::from_timestamp(claims.exp as i64, 0),
+    ) {
+        (Ok(jti), Ok(session_id), Some(expires_at)) => (jti, session_id, expires_at.naive_utc()),
+        _ => return Err(ApiError::from(AuthError::InvalidToken)),
+    };
+
+    let token = RevokedToken::new(jti, user.id, expires_at);
+
+    match RevokedToken::revoke(&state, &token, session_id).await {
+        Ok(_) => {
+            info!("Logout successful for user: {}", user.username);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            error!("Error logging out user {}: {e}", user.username);
+            Err(e)
+        }
+    }
 }
 
 /// Register a new user.
@@ -140,17 +262,60 @@ pub async fn register(
     path = "/api/v1/auth/verify",
     tags = ["Auth"],
     summary = "Verify JWT.",
-    description = "Verifies if a JWT is still valid.",
+    description = "Verifies if a JWT is still valid and was not revoked.",
     request_body = VerifyTokenPayload,
     responses(
         (status = 200, description = "Token is valid!"),
+        (status = 401, description = "The token was revoked."),
     )
 )]
 pub async fn verify(
+    State(state): State<Arc<AppState>>,
     Json(payload): Json<VerifyTokenPayload>,
 ) -> Result<impl IntoResponse, ApiError> {
-    validate_jwt(&payload.token)?;
+    let token_data = decode_jwt(&state.jwt_keys, payload.token)?;
+
+    let user_id: Option<Uuid> = sqlx::query_scalar(r#"SELECT id FROM users WHERE username = $1;"#)
+        .bind(&token_data.claims.sub)
+        .fetch_optional(&state.db)
+        .await?;
+
+    let is_revoked = match (user_id, Uuid::parse_str(&token_data.claims.jti)) {
+        (Some(user_id), Ok(jti)) => {
+            state
+                .revocations
+                .is_revoked(jti, user_id, token_data.claims.iat)
+        }
+        _ => true,
+    };
+
+    if is_revoked {
+        return Err(ApiError::from(AuthError::RevokedToken));
+    }
+
     info!("Successful verified token");
 
     Ok((StatusCode::OK, Json("Token is valid!")))
 }
+
+/// Returns the public keys verifying the JWTs.
+///
+/// Other services verify our tokens with the key matching their `kid` header.
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    tags = ["Auth"],
+    summary = "Get the JSON Web Key Set.",
+    description = "Returns the public keys verifying the JWTs, including those being rotated in or out. The set is empty when tokens are signed with a shared secret.",
+    responses(
+        (status = 200, description = "Key set retrieved successfully."),
+    )
+)]
+pub async fn jwks(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    debug!("Received request to retrieve the JWKS");
+
+    (
+        [(header::CACHE_CONTROL, "public, max-age=300")],
+        Json(state.jwt_keys.jwks.clone()),
+    )
+}
@@ -0,0 +1,248 @@
+use crate::database::AppState;
+use crate::errors::api_error::ApiError;
+use crate::models::auth::access::AccessControl;
+use crate::models::category::{Category, CreateCategoryPayload, UpdateCategoryPayload};
+use crate::models::user::Role;
+use crate::models::DeletePayload;
+use crate::validations::{existence::category_exists, uniqueness::is_category_unique};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Retrieves a list of all categories.
+///
+/// This endpoint fetches all ticket categories, ordered by name.
+/// If there are no categories, returns an empty array.
+#[utoipa::path(
+    get,
+    path = "/api/v1/categories",
+    tags = ["Categories"],
+    summary = "List all categories.",
+    description = "Fetches all ticket categories. If there are no categories, returns an empty array.",
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Categories retrieved successfully.", body = Vec<Category>),
+        (status = 500, description = "An error occurred while retrieving the categories.")
+    )
+)]
+pub async fn find_all_categories(
+    State(state): State<Arc<AppState>>,
+    _access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve all categories.");
+
+    match Category::find_all(&state).await {
+        Ok(categories) => {
+            info!("Categories listed successfully.");
+            Ok(Json(categories))
+        }
+        Err(e) => {
+            error!("Error retrieving all categories: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Retrieves a specific category by its ID.
+#[utoipa::path(
+    get,
+    path = "/api/v1/categories/{id}",
+    tags = ["Categories"],
+    summary = "Get a specific category by ID.",
+    description = "This endpoint retrieves a category using its ID. Returns the category if found, or a 404 status if not found.",
+    params(
+        ("id", description = "The unique identifier of the category to retrieve.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Category retrieved successfully.", body = Category),
+        (status = 404, description = "No category found with the specified ID."),
+        (status = 500, description = "An error occurred while retrieving the category.")
+    )
+)]
+pub async fn find_category_by_id(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    _access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve category with id: {id}");
+
+    match Category::find_by_id(&state, id).await {
+        Ok(Some(category)) => {
+            info!("Category found: {id}");
+            Ok(Json(category))
+        }
+        Ok(None) => {
+            error!("No category found with id: {id}");
+            Err(ApiError::NotFound)
+        }
+        Err(e) => {
+            error!("Error retrieving category with id {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Create a new category.
+///
+/// This endpoint creates a new ticket category. Only admins can manage categories.
+#[utoipa::path(
+    post,
+    path = "/api/v1/categories",
+    tags = ["Categories"],
+    summary = "Create a new category.",
+    description = "This endpoint creates a new ticket category with the provided details.",
+    request_body = CreateCategoryPayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 201, description = "Category created successfully.", body = Uuid),
+        (status = 400, description = "Invalid input, including empty name or name too short/long."),
+        (status = 409, description = "Conflict: Category with the same name already exists."),
+        (status = 500, description = "An error occurred while creating the category.")
+    )
+)]
+pub async fn create_category(
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<CreateCategoryPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!(
+        "Received request to create category with name: {}",
+        payload.name
+    );
+
+    access.require_role(Role::Admin)?;
+
+    // Validations
+    payload.validate()?;
+    is_category_unique(&state, &payload.name, None).await?;
+
+    match Category::create(&state, &payload).await {
+        Ok(new_category) => {
+            info!("Category created! ID: {}", &new_category.id);
+            Ok((StatusCode::CREATED, Json(new_category.id)))
+        }
+        Err(e) => {
+            error!("Error creating category with name {}: {e}", payload.name);
+            Err(e)
+        }
+    }
+}
+
+/// Updates an existing category.
+///
+/// This endpoint updates the name or description of a category. Only admins can manage categories.
+#[utoipa::path(
+    put,
+    path = "/api/v1/categories",
+    tags = ["Categories"],
+    summary = "Update an existing category.",
+    description = "This endpoint updates the details of an existing ticket category.",
+    request_body = UpdateCategoryPayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Category updated successfully.", body = Uuid),
+        (status = 400, description = "Invalid input, including empty name or name too short/long."),
+        (status = 404, description = "Category ID not found."),
+        (status = 409, description = "Conflict: Category with the same name already exists."),
+        (status = 500, description = "An error occurred while updating the category.")
+    )
+)]
+pub async fn update_category(
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<UpdateCategoryPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!(
+        "Received request to update category with ID: {}",
+        payload.id
+    );
+
+    access.require_role(Role::Admin)?;
+
+    // Validations
+    payload.validate()?;
+    category_exists(&state, payload.id).await?;
+    if let Some(name) = &payload.name {
+        is_category_unique(&state, name, Some(payload.id)).await?;
+    }
+
+    match Category::update(&state, &payload).await {
+        Ok(category_id) => {
+            info!("Category updated! ID: {category_id}");
+            Ok(Json(category_id))
+        }
+        Err(e) => {
+            error!("Error updating category with ID {}: {e}", payload.id);
+            Err(e)
+        }
+    }
+}
+
+/// Deletes an existing category.
+///
+/// This endpoint deletes a category by its ID. Tickets of the category are left without one.
+/// Only admins can manage categories.
+/// If the category is successfully deleted, a 204 status code is returned.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/categories",
+    tags = ["Categories"],
+    summary = "Delete an existing category.",
+    description = "This endpoint deletes a specific ticket category using its ID.",
+    request_body = DeletePayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 204, description = "Category deleted successfully"),
+        (status = 404, description = "Category ID not found"),
+        (status = 500, description = "An error occurred while deleting the category")
+    )
+)]
+pub async fn delete_category(
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<DeletePayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!(
+        "Received request to delete category with ID: {}",
+        payload.id
+    );
+
+    access.require_role(Role::Admin)?;
+
+    // Validations
+    category_exists(&state, payload.id).await?;
+
+    match Category::delete(&state, &payload).await {
+        Ok(_) => {
+            info!("Category deleted! ID: {}", &payload.id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            error!("Error deleting category with ID {}: {e}", payload.id);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use crate::database::AppState;
+use crate::errors::api_error::ApiError;
+use crate::models::auth::access::AccessControl;
+use crate::models::comment::{Comment, CommentPublic, CreateCommentPayload, UpdateCommentPayload};
+use crate::models::ticket::Ticket;
+use crate::models::user::Role;
+use crate::models::DeletePayload;
+use crate::validations::access::{require_ticket_access, require_ticket_write_access};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Retrieves the comment with the given ID, ensuring it belongs to the ticket
+/// and is visible to the current user.
+async fn find_ticket_comment(
+    state: &AppState,
+    access: &AccessControl,
+    ticket_id: Uuid,
+    comment_id: Uuid,
+) -> Result<Comment, ApiError> {
+    let include_internal = access.has_any_role(&[Role::Admin, Role::Moderator]);
+
+    match Comment::find_by_id(state, comment_id).await? {
+        Some(comment)
+            if comment.ticket_id == ticket_id && (include_internal || !comment.internal) =>
+        {
+            Ok(comment)
+        }
+        _ => {
+            error!("No comment found with id {comment_id} on ticket {ticket_id}");
+            Err(ApiError::NotFound)
+        }
+    }
+}
+
+/// Retrieves all comments of a ticket.
+///
+/// This endpoint fetches the whole conversation of a ticket, ordered by creation date.
+/// Internal notes are only returned to moderators and admins.
+/// Replies carry the `parent_id` of the comment they answer.
+/// If the ticket has no comments, returns an empty array.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/{id}/comments",
+    tags = ["Comments"],
+    summary = "List all comments of a ticket.",
+    description = "Fetches all comments of the ticket, from the oldest to the newest. If there are no comments, returns an empty array.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Comments retrieved successfully.", body = Vec<CommentPublic>),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 500, description = "An error occurred while retrieving the comments.")
+    )
+)]
+pub async fn find_comments(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve comments of ticket with id: {id}");
+
+    require_ticket_access(&state, &access, id).await?;
+
+    let include_internal = access.has_any_role(&[Role::Admin, Role::Moderator]);
+
+    match Comment::find_by_ticket(&state, id, include_internal).await {
+        Ok(comments) => {
+            info!("Comments of ticket {id} listed successfully.");
+            Ok(Json(comments))
+        }
+        Err(e) => {
+            error!("Error retrieving comments of ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Create a new comment on a ticket.
+///
+/// This endpoint adds a new comment to the conversation of the ticket.
+/// The author of the comment is taken from the JWT.
+/// Only moderators and admins can create internal notes.
+/// A comment can reply to another comment of the same ticket through `parent_id`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/{id}/comments",
+    tags = ["Comments"],
+    summary = "Create a new comment on a ticket.",
+    description = "This endpoint adds a new comment, authored by the current user, to the ticket. Set `parent_id` to reply to another comment of the same ticket.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    request_body = CreateCommentPayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 201, description = "Comment created successfully.", body = Uuid),
+        (status = 400, description = "Invalid input, including empty content or content too long."),
+        (status = 404, description = "No ticket or parent comment found with the specified ID."),
+        (status = 500, description = "An error occurred while creating the comment.")
+    )
+)]
+pub async fn create_comment(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(mut payload): Json<CreateCommentPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to create comment on ticket with id: {id}");
+
+    require_ticket_write_access(&state, &access, id).await?;
+
+    // A reply must answer a visible comment of the same ticket, and the
+    // replies to internal notes are internal notes too.
+    if let Some(parent_id) = payload.parent_id {
+        let parent = find_ticket_comment(&state, &access, id, parent_id).await?;
+
+        if parent.internal {
+            payload.internal = Some(true);
+        }
+    }
+
+    if payload.internal.unwrap_or_default() {
+        access.require_any_role(&[Role::Admin, Role::Moderator])?;
+    }
+
+    // Validations
+    payload.validate()?;
+
+    match Comment::create(&state, id, access.user().id, &payload).await {
+        Ok(new_comment) => {
+            // The first public reply of an agent is the response of the ticket SLA.
+            if !new_comment.internal && access.has_any_role(&[Role::Admin, Role::Moderator]) {
+                Ticket::mark_responded(&state, id).await?;
+            }
+
+            info!("Comment created! ID: {}", &new_comment.id);
+            Ok((StatusCode::CREATED, Json(new_comment.id)))
+        }
+        Err(e) => {
+            error!("Error creating comment on ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Updates an existing comment.
+///
+/// This endpoint updates the content of an existing comment.
+/// Only the author of the comment, moderators and admins can edit it.
+#[utoipa::path(
+    put,
+    path = "/api/v1/tickets/{id}/comments",
+    tags = ["Comments"],
+    summary = "Update an existing comment.",
+    description = "This endpoint updates the content of an existing comment of the ticket.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    request_body = UpdateCommentPayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Comment updated successfully.", body = Uuid),
+        (status = 400, description = "Invalid input, including empty content or content too long."),
+        (status = 404, description = "Ticket or comment ID not found."),
+        (status = 500, description = "An error occurred while updating the comment.")
+    )
+)]
+pub async fn update_comment(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<UpdateCommentPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to update comment with ID: {}", payload.id);
+
+    require_ticket_access(&state, &access, id).await?;
+
+    let comment = find_ticket_comment(&state, &access, id, payload.id).await?;
+    access.require_owner_or_any_role(comment.author, &[Role::Admin, Role::Moderator])?;
+
+    // Validations
+    payload.validate()?;
+
+    match Comment::update(&state, &payload).await {
+        Ok(comment_id) => {
+            info!("Comment updated! ID: {comment_id}");
+            Ok(Json(comment_id))
+        }
+        Err(e) => {
+            error!("Error updating comment with ID {}: {e}", payload.id);
+            Err(e)
+        }
+    }
+}
+
+/// Deletes an existing comment.
+///
+/// This endpoint deletes a specific comment of the ticket by its ID.
+/// The replies to the comment are deleted along with it.
+/// Only the author of the comment, moderators and admins can delete it.
+/// If the comment is successfully deleted, a 204 status code is returned.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tickets/{id}/comments",
+    tags = ["Comments"],
+    summary = "Delete an existing comment.",
+    description = "This endpoint deletes a specific comment of the ticket using its ID.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    request_body = DeletePayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 204, description = "Comment deleted successfully"),
+        (status = 404, description = "Ticket or comment ID not found"),
+        (status = 500, description = "An error occurred while deleting the comment")
+    )
+)]
+pub async fn delete_comment(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<DeletePayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to delete comment with ID: {}", payload.id);
+
+    require_ticket_access(&state, &access, id).await?;
+
+    let comment = find_ticket_comment(&state, &access, id, payload.id).await?;
+    access.require_owner_or_any_role(comment.author, &[Role::Admin, Role::Moderator])?;
+
+    match Comment::delete(&state, &payload).await {
+        Ok(_) => {
+            info!("Comment deleted! ID: {}", &payload.id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            error!("Error deleting comment with ID {}: {e}", payload.id);
+            Err(e)
+        }
+    }
+}
@@ -1,16 +1,16 @@
 use crate::{
     database::AppState,
-    errors::api_error::ApiError,
+    errors::{api_error::ApiError, export_error::ExportError},
     export::{csv::create_tickets_csv, pdf::create_ticket_pdf},
     models::{
-        ticket::{Ticket, TicketView},
+        ticket::{Ticket, TicketView, TicketVisibility},
         user::{Status, User},
     },
 };
 use axum::{
     body::Bytes,
     extract::{Path, State},
-    http::{header, HeaderMap},
+    http::{header, HeaderMap, HeaderValue},
     response::IntoResponse,
     Extension,
 };
@@ -43,7 +43,9 @@ pub async fn ticket_to_pdf(
         return Err(ApiError::Unauthorized);
     }
 
-    let ticket = match Ticket::find_by_id(&state, id).await {
+    let visibility = TicketVisibility::of(&current_user);
+
+    let ticket = match Ticket::find_by_id(&state, id, &visibility).await {
         Ok(Some(ticket)) => Ok(ticket),
         Ok(None) => {
             error!("No ticket found with id: {id}");
@@ -55,46 +57,8 @@ pub async fn ticket_to_pdf(
         }
     }?;
 
-    // Formats all fields for PDF generation.
-    // For each field not found, returns `null`.
-
-    let id = ticket.id.to_string();
-    let formatted_status = ticket.status.to_string();
-    let formatted_solution = if let Some(solution) = ticket.solution {
-        solution
-    } else {
-        "null".to_string()
-    };
-
-    let time_fmt = "%Y-%m-%d %H:%M:%S";
-
-    let formatted_created_at = ticket.created_at.format(time_fmt).to_string();
-    let formatted_updated_at = ticket.updated_at.format(time_fmt).to_string();
-
-    let formatted_closed_at = if let Some(closed_at) = ticket.closed_at {
-        closed_at.format(time_fmt).to_string()
-    } else {
-        "null".to_string()
-    };
-
-    let formatted_closed_by = if let Some(closed_by) = ticket.closed_by {
-        closed_by.username.to_string()
-    } else {
-        "null".to_string()
-    };
-
-    let formatted_ticket = TicketView {
-        id,
-        title: ticket.title,
-        description: ticket.description,
-        requester: ticket.requester.username,
-        status: formatted_status,
-        closed_by: formatted_closed_by,
-        solution: formatted_solution,
-        created_at: formatted_created_at,
-        updated_at: formatted_updated_at,
-        closed_at: formatted_closed_at,
-    };
+    let formatted_ticket = TicketView::from(ticket);
+    let disposition = format!("attachment; filename=\"{}.pdf\"", formatted_ticket.number);
 
     let pdf = create_ticket_pdf(formatted_ticket).await;
 
@@ -106,7 +70,7 @@ pub async fn ticket_to_pdf(
     );
     headers.insert(
         header::CONTENT_DISPOSITION,
-        "attachment; filename=\"Ticket.pdf\"".parse().unwrap(),
+        HeaderValue::from_str(&disposition).map_err(ExportError::from)?,
     );
 
     let body = Bytes::from(pdf?);
@@ -139,7 +103,9 @@ pub async fn ticket_to_csv(
         return Err(ApiError::Unauthorized);
     }
 
-    let ticket = match Ticket::find_by_id(&state, id).await {
+    let visibility = TicketVisibility::of(&current_user);
+
+    let ticket = match Ticket::find_by_id(&state, id, &visibility).await {
         Ok(Some(ticket)) => Ok(ticket),
         Ok(None) => {
             error!("No ticket found with id: {id}");
@@ -151,48 +117,8 @@ pub async fn ticket_to_csv(
         }
     }?;
 
-    // Formats all fields for PDF generation.
-    // For each field not found, returns `null`.
-
-    let id = ticket.id.to_string();
-
-    let formatted_status = ticket.status.to_string();
-
-    let formatted_solution = if let Some(solution) = ticket.solution {
-        solution
-    } else {
-        "null".to_string()
-    };
-
-    let time_fmt = "%Y-%m-%d %H:%M:%S";
-
-    let formatted_created_at = ticket.created_at.format(time_fmt).to_string();
-    let formatted_updated_at = ticket.updated_at.format(time_fmt).to_string();
-
-    let formatted_closed_at = if let Some(closed_at) = ticket.closed_at {
-        closed_at.format(time_fmt).to_string()
-    } else {
-        "null".to_string()
-    };
-
-    let formatted_closed_by = if let Some(closed_by) = ticket.closed_by {
-        closed_by.username.to_string()
-    } else {
-        "null".to_string()
-    };
-
-    let formatted_ticket = TicketView {
-        id,
-        title: ticket.title,
-        description: ticket.description,
-        requester: ticket.requester.username,
-        status: formatted_status,
-        closed_by: formatted_closed_by,
-        solution: formatted_solution,
-        created_at: formatted_created_at,
-        updated_at: formatted_updated_at,
-        closed_at: formatted_closed_at,
-    };
+    let formatted_ticket = TicketView::from(ticket);
+    let disposition = format!("attachment; filename=\"{}.csv\"", formatted_ticket.number);
 
     let mut tickets = Vec::new();
     tickets.push(formatted_ticket);
@@ -207,7 +133,7 @@ pub async fn ticket_to_csv(
     );
     headers.insert(
         header::CONTENT_DISPOSITION,
-        "attachment; filename=\"Ticket.csv\"".parse().unwrap(),
+        HeaderValue::from_str(&disposition).map_err(ExportError::from)?,
     );
 
     let body = Bytes::from(csv);
@@ -220,7 +146,7 @@ pub async fn ticket_to_csv(
     path = "/api/v1/export/csv/tickets",
     tags = ["Tickets"],
     summary = "Generates a CSV of all tickets.",
-    description = "Generates a CSV with all tickets in the system.",
+    description = "Generates a CSV with all tickets visible to the current user.",
     security(
         (),
         ("jwt_token" = ["jwt_token"])
@@ -236,7 +162,9 @@ pub async fn tickets_to_csv(
         return Err(ApiError::Unauthorized);
     }
 
-    let tickets = match Ticket::find_all(&state).await {
+    let visibility = TicketVisibility::of(&current_user);
+
+    let tickets = match Ticket::find_all(&state, &visibility).await {
         Ok(tickets) => tickets,
         Err(e) => {
             error!("Error fetching all tickets for CSV export: {e}");
@@ -245,41 +173,7 @@ pub async fn tickets_to_csv(
     };
 
     // Formatar todos os tickets
-    let mut ticket_views = Vec::new();
-
-    for ticket in tickets {
-        let id = ticket.id.to_string();
-
-        let formatted_status = ticket.status.to_string();
-        let formatted_solution = ticket.solution.unwrap_or_else(|| "null".to_string());
-
-        let time_fmt = "%Y-%m-%d %H:%M:%S";
-
-        let formatted_created_at = ticket.created_at.format(time_fmt).to_string();
-        let formatted_updated_at = ticket.updated_at.format(time_fmt).to_string();
-        let formatted_closed_at = ticket.closed_at.map_or("null".to_string(), |closed_at| {
-            closed_at.format(time_fmt).to_string()
-        });
-
-        let closed_by_username = if let Some(closed_by) = ticket.closed_by {
-            closed_by.username.to_string()
-        } else {
-            "null".to_string()
-        };
-
-        ticket_views.push(TicketView {
-            id,
-            title: ticket.title,
-            description: ticket.description,
-            requester: ticket.requester.username,
-            status: formatted_status,
-            closed_by: closed_by_username,
-            solution: formatted_solution,
-            created_at: formatted_created_at,
-            updated_at: formatted_updated_at,
-            closed_at: formatted_closed_at,
-        });
-    }
+    let ticket_views: Vec<TicketView> = tickets.into_iter().map(TicketView::from).collect();
 
     // Gerar o CSV a partir de todos os tickets
     let csv = create_tickets_csv(ticket_views).await.unwrap();
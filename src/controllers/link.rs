@@ -0,0 +1,176 @@
+use crate::database::AppState;
+use crate::errors::api_error::ApiError;
+use crate::models::auth::access::AccessControl;
+use crate::models::link::{CreateTicketLinkPayload, TicketLink, TicketLinkPublic};
+use crate::models::ticket::TicketVisibility;
+use crate::validations::{
+    access::{require_ticket_access, require_ticket_write_access},
+    links::validate_link,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+/// Retrieves the links of a ticket.
+///
+/// This endpoint lists the tickets linked to the ticket, in both directions,
+/// with the relation of the ticket to each of them. Only the linked tickets
+/// visible to the current user are listed.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/{id}/links",
+    tags = ["Links"],
+    summary = "List the links of a ticket.",
+    description = "Fetches the tickets linked to the ticket, with their relation to it.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Links retrieved successfully.", body = Vec<TicketLinkPublic>),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 500, description = "An error occurred while retrieving the links.")
+    )
+)]
+pub async fn find_links(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve links of ticket with id: {id}");
+
+    require_ticket_access(&state, &access, id).await?;
+
+    let viewer = TicketVisibility::of(access.user()).viewer();
+
+    match TicketLink::find_by_ticket(&state, id, viewer).await {
+        Ok(links) => {
+            info!("Links of ticket {id} listed successfully.");
+            Ok(Json(links))
+        }
+        Err(e) => {
+            error!("Error retrieving links of ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Links a ticket to another ticket.
+///
+/// The kind of the link is read from the ticket to the target: the ticket is the parent of,
+/// blocks, relates to or is a duplicate of the target. A child has a single parent, and
+/// parent and blocking links cannot form cycles. A parent cannot be closed while it has open children.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/{id}/links",
+    tags = ["Links"],
+    summary = "Link a ticket to another ticket.",
+    description = "This endpoint creates a typed link from the ticket to the target ticket and returns its ID.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    request_body = CreateTicketLinkPayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 201, description = "Link created successfully.", body = Uuid),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 422, description = "The tickets cannot be linked, e.g. the link would form a cycle."),
+        (status = 500, description = "An error occurred while creating the link.")
+    )
+)]
+pub async fn create_link(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<CreateTicketLinkPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!(
+        "Received request to link ticket {id} to ticket {} as {}",
+        payload.target, payload.kind
+    );
+
+    require_ticket_write_access(&state, &access, id).await?;
+    require_ticket_access(&state, &access, payload.target).await?;
+
+    // Validations
+    let link = TicketLink::new(id, payload.target, payload.kind, access.user().id);
+    validate_link(&state, &link).await?;
+
+    match TicketLink::create(&state, &link, access.user()).await {
+        Ok(_) => {
+            info!("Link created! ID: {}", link.id);
+            Ok((StatusCode::CREATED, Json(link.id)))
+        }
+        Err(e) => {
+            error!(
+                "Error linking ticket {id} to ticket {}: {e}",
+                payload.target
+            );
+            Err(e)
+        }
+    }
+}
+
+/// Removes a link between tickets.
+///
+/// The link can be removed from either of its tickets.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tickets/{id}/links/{link_id}",
+    tags = ["Links"],
+    summary = "Remove a link between tickets.",
+    description = "This endpoint removes the link from the ticket to another ticket.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4),
+        ("link_id", description = "The unique identifier of the link.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 204, description = "Link removed successfully."),
+        (status = 404, description = "No link of the ticket found with the specified ID."),
+        (status = 500, description = "An error occurred while removing the link.")
+    )
+)]
+pub async fn delete_link(
+    Path((id, link_id)): Path<(Uuid, Uuid)>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to delete link {link_id} of ticket with id: {id}");
+
+    require_ticket_write_access(&state, &access, id).await?;
+
+    let link = match TicketLink::find_by_id(&state, link_id).await? {
+        Some(link) if link.source_id == id || link.target_id == id => link,
+        _ => {
+            error!("No link found with id {link_id} on ticket {id}");
+            return Err(ApiError::NotFound);
+        }
+    };
+
+    match TicketLink::delete(&state, &link, access.user()).await {
+        Ok(_) => {
+            info!("Link deleted! ID: {link_id}");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            error!("Error deleting link with ID {link_id}: {e}");
+            Err(e)
+        }
+    }
+}
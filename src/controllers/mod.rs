@@ -1,6 +1,17 @@
+pub mod api_token;
+pub mod attachment;
 pub mod auth;
+pub mod category;
+pub mod comment;
 pub mod export;
+pub mod link;
 pub mod migrations;
+pub mod oidc;
+pub mod rating;
+pub mod report;
 pub mod status;
+pub mod tag;
 pub mod ticket;
 pub mod user;
+pub mod watcher;
+pub mod worklog;
@@ -0,0 +1,155 @@
+use crate::{
+    database::AppState,
+    errors::{api_error::ApiError, oidc_error::OidcError},
+    models::{
+        auth::{
+            oidc::{OidcCallbackQuery, OidcLoginQuery, OidcLoginState, UserIdentity},
+            refresh_token::RefreshToken,
+            token::TokenPair,
+        },
+        user::Status,
+    },
+    utils::jwt::generate_jwt,
+};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect},
+    Json,
+};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+/// Starts a single sign-on, redirecting to the OpenID Connect provider.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oidc/login",
+    tags = ["Auth"],
+    summary = "Log in with single sign-on.",
+    description = "Redirects to the OpenID Connect provider to sign in, which redirects back to `/auth/oidc/callback`. The login must be completed within 10 minutes.",
+    params(OidcLoginQuery),
+    responses(
+        (status = 303, description = "Redirected to the identity provider."),
+        (status = 404, description = "Single sign-on is not configured."),
+        (status = 502, description = "The identity provider could not be reached."),
+    )
+)]
+pub async fn login(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<OidcLoginQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to log in with single sign-on");
+
+    let Some(client) = &state.oidc else {
+        return Err(ApiError::from(OidcError::NotConfigured));
+    };
+
+    let login_state = OidcLoginState::generate();
+    OidcLoginState::create(&state, &login_state).await?;
+
+    match client
+        .authorization_url(&login_state, query.login_hint.as_deref())
+        .await
+    {
+        Ok(url) => Ok(Redirect::to(url.as_str())),
+        Err(e) => {
+            error!("Error starting single sign-on: {e}");
+            Err(ApiError::from(e))
+        }
+    }
+}
+
+/// Completes a single sign-on, returning a JWT and a refresh token.
+///
+/// Users are matched by their identity at the provider, then by verified email on first login,
+/// and provisioned when none matches.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oidc/callback",
+    tags = ["Auth"],
+    summary = "Complete a single sign-on.",
+    description = "Called by the OpenID Connect provider once the user signed in. The authorization code is exchanged for an ID token, whose user is linked to the user with the same verified email, or provisioned, on first login. The role mapped from the claims of the ID token is given to the user.",
+    params(OidcCallbackQuery),
+    responses(
+        (status = 200, description = "Logged in successfully.", body = TokenPair),
+        (status = 401, description = "Login denied, expired or already completed, invalid ID token, or inactive user."),
+        (status = 404, description = "Single sign-on is not configured."),
+        (status = 502, description = "The identity provider could not be reached."),
+    )
+)]
+pub async fn callback(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<OidcCallbackQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received single sign-on callback");
+
+    let Some(client) = &state.oidc else {
+        return Err(ApiError::from(OidcError::NotConfigured));
+    };
+
+    // The login is over either way: its state cannot be used again.
+    let login_state = match &query.state {
+        Some(login_state) => OidcLoginState::take(&state, login_state).await?,
+        None => None,
+    };
+
+    if let Some(error) = query.error {
+        error!("Single sign-on denied by the identity provider: {error}");
+        return Err(ApiError::from(OidcError::Denied(
+            query.error_description.unwrap_or(error),
+        )));
+    }
+
+    let (Some(code), Some(login_state)) = (query.code, login_state) else {
+        return Err(ApiError::from(OidcError::InvalidState));
+    };
+
+    let claims = match client
+        .exchange_code(&code, &login_state.code_verifier)
+        .await
+    {
+        Ok(id_token) => client.verify_id_token(&id_token, &login_state.nonce).await,
+        Err(e) => Err(e),
+    };
+
+    let claims = match claims {
+        Ok(claims) => claims,
+        Err(e) => {
+            error!("Error completing single sign-on: {e}");
+            return Err(ApiError::from(e));
+        }
+    };
+
+    let user = match UserIdentity::sign_in(&state, &claims, client.role(&claims)).await {
+        Ok(user) => user,
+        Err(e) => {
+            error!("Error signing in identity {}: {e}", claims.sub);
+            return Err(e);
+        }
+    };
+
+    if user.status != Status::Active {
+        return Err(ApiError::Unauthorized);
+    }
+
+    // Every login starts a session, identified by the family of its refresh tokens.
+    let session_id = Uuid::now_v7();
+    let access_token = generate_jwt(
+        &state.jwt_keys,
+        &user.username,
+        &user.role.to_string(),
+        session_id,
+    )?;
+    let refresh_token = RefreshToken::issue(&state, user.id, session_id).await?;
+
+    info!("Single sign-on successful for user: {}", user.username);
+
+    Ok((
+        StatusCode::OK,
+        Json(TokenPair {
+            access_token,
+            refresh_token,
+        }),
+    ))
+}
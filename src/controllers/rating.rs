@@ -0,0 +1,81 @@
+use crate::database::AppState;
+use crate::errors::api_error::ApiError;
+use crate::models::auth::access::AccessControl;
+use crate::models::rating::{CreateRatingPayload, Rating};
+use crate::models::ticket::TicketStatus;
+use crate::validations::access::require_ticket_access;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Rates the resolution of a closed ticket.
+///
+/// This endpoint records the satisfaction of the requester with the resolution of the ticket,
+/// from 1 to 5 with an optional comment. The rating is credited to the assignee of the ticket,
+/// or to whoever closed it. Only the requester can rate a ticket, once it is closed, and only once.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/{id}/rating",
+    tags = ["Tickets"],
+    summary = "Rate the resolution of a closed ticket.",
+    description = "This endpoint records the satisfaction rating of the requester with the resolution of the ticket.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    request_body = CreateRatingPayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 201, description = "Rating created successfully."),
+        (status = 400, description = "Invalid input, including a score out of range or a comment too long."),
+        (status = 401, description = "Only the requester can rate the ticket."),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 409, description = "The ticket was already rated."),
+        (status = 422, description = "The ticket is not closed."),
+        (status = 500, description = "An error occurred while rating the ticket.")
+    )
+)]
+pub async fn rate_ticket(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<CreateRatingPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to rate ticket with id: {id}");
+
+    let ticket = require_ticket_access(&state, &access, id).await?;
+    access.require_owner_or_any_role(ticket.requester.id, &[])?;
+
+    if ticket.status != TicketStatus::Closed {
+        error!(
+            "Ticket {id} cannot be rated while {}",
+            ticket.status.to_string()
+        );
+        return Err(ApiError::TicketNotClosed);
+    }
+
+    // Validations
+    payload.validate()?;
+
+    let rating = Rating::new(id, access.user().id, &payload);
+
+    match Rating::create(&state, &rating).await {
+        Ok(_) => {
+            info!("Ticket {id} rated with {}.", rating.score);
+            Ok(StatusCode::CREATED)
+        }
+        Err(e) => {
+            error!("Error rating ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use crate::database::AppState;
+use crate::errors::api_error::ApiError;
+use crate::models::auth::access::AccessControl;
+use crate::models::rating::{Rating, RatingReportEntry};
+use crate::models::report::ReportQuery;
+use crate::models::user::Role;
+use crate::models::worklog::{Worklog, WorklogReportEntry};
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+
+/// Reports the time logged per agent and period.
+///
+/// This endpoint sums the worklogs of each agent in each day, week or month,
+/// optionally within a range of work days or for a single agent.
+/// Only admins can see the reports.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/worklogs",
+    tags = ["Reports"],
+    summary = "Report the time logged per agent and period.",
+    description = "Sums the minutes logged by each agent in each period, ordered by period and agent.",
+    params(ReportQuery),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Report generated successfully.", body = Vec<WorklogReportEntry>),
+        (status = 401, description = "Only admins can see the reports."),
+        (status = 500, description = "An error occurred while generating the report.")
+    )
+)]
+pub async fn worklog_report(
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Query(query): Query<ReportQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to report worklogs");
+
+    access.require_role(Role::Admin)?;
+
+    match Worklog::report(&state, &query).await {
+        Ok(report) => {
+            info!("Worklog report generated with {} entries.", report.len());
+            Ok(Json(report))
+        }
+        Err(e) => {
+            error!("Error generating the worklog report: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Reports the satisfaction ratings per agent and period.
+///
+/// This endpoint aggregates the ratings credited to each agent in each day, week or month,
+/// optionally within a range of rating days or for a single agent.
+/// Only admins can see the reports.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/ratings",
+    tags = ["Reports"],
+    summary = "Report the satisfaction ratings per agent and period.",
+    description = "Averages the ratings credited to each agent in each period, ordered by period and agent.",
+    params(ReportQuery),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Report generated successfully.", body = Vec<RatingReportEntry>),
+        (status = 401, description = "Only admins can see the reports."),
+        (status = 500, description = "An error occurred while generating the report.")
+    )
+)]
+pub async fn rating_report(
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Query(query): Query<ReportQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to report ratings");
+
+    access.require_role(Role::Admin)?;
+
+    match Rating::report(&state, &query).await {
+        Ok(report) => {
+            info!("Rating report generated with {} entries.", report.len());
+            Ok(Json(report))
+        }
+        Err(e) => {
+            error!("Error generating the rating report: {e}");
+            Err(e)
+        }
+    }
+}
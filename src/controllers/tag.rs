@@ -0,0 +1,198 @@
+use crate::database::AppState;
+use crate::errors::api_error::ApiError;
+use crate::models::auth::access::AccessControl;
+use crate::models::tag::{Tag, TagsPayload};
+use crate::validations::{
+    access::{require_ticket_access, require_ticket_write_access},
+    tags::normalize_tags,
+};
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+/// Retrieves a list of all tags in use.
+///
+/// This endpoint lists every tag used by at least one ticket, with the number of tickets using it.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tags",
+    tags = ["Tags"],
+    summary = "List all tags in use.",
+    description = "Fetches all tags used by tickets, with their usage count. If there are no tags, returns an empty array.",
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Tags retrieved successfully.", body = Vec<Tag>),
+        (status = 500, description = "An error occurred while retrieving the tags.")
+    )
+)]
+pub async fn find_all_tags(
+    State(state): State<Arc<AppState>>,
+    _access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve all tags.");
+
+    match Tag::find_all(&state).await {
+        Ok(tags) => {
+            info!("Tags listed successfully.");
+            Ok(Json(tags))
+        }
+        Err(e) => {
+            error!("Error retrieving all tags: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Retrieves the tags of a ticket.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/{id}/tags",
+    tags = ["Tags"],
+    summary = "List the tags of a ticket.",
+    description = "Fetches the tags of the ticket, in alphabetical order.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Tags retrieved successfully.", body = Vec<String>),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 500, description = "An error occurred while retrieving the tags.")
+    )
+)]
+pub async fn find_ticket_tags(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve tags of ticket with id: {id}");
+
+    require_ticket_access(&state, &access, id).await?;
+
+    match Tag::find_by_ticket(&state, id).await {
+        Ok(tags) => {
+            info!("Tags of ticket {id} listed successfully.");
+            Ok(Json(tags))
+        }
+        Err(e) => {
+            error!("Error retrieving tags of ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Adds tags to a ticket.
+///
+/// Tags are free-form, and are stored trimmed and in lowercase.
+/// Tags the ticket already has are ignored. Returns the resulting tags of the ticket.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/{id}/tags",
+    tags = ["Tags"],
+    summary = "Add tags to a ticket.",
+    description = "This endpoint adds the provided tags to the ticket and returns its resulting tags.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    request_body = TagsPayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Tags added successfully.", body = Vec<String>),
+        (status = 400, description = "Invalid tags, including empty or too long tags, or too many tags."),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 500, description = "An error occurred while adding the tags.")
+    )
+)]
+pub async fn add_ticket_tags(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<TagsPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to add tags to ticket with id: {id}");
+
+    require_ticket_write_access(&state, &access, id).await?;
+
+    let mut tags = Tag::find_by_ticket(&state, id).await?;
+    tags.extend(payload.tags);
+    let tags = normalize_tags(&tags)?;
+
+    match Tag::replace(&state, id, &tags, access.user()).await {
+        Ok(_) => {
+            info!("Tags added to ticket {id}.");
+            Ok(Json(tags))
+        }
+        Err(e) => {
+            error!("Error adding tags to ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Removes tags from a ticket.
+///
+/// Tags the ticket does not have are ignored. Returns the resulting tags of the ticket.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tickets/{id}/tags",
+    tags = ["Tags"],
+    summary = "Remove tags from a ticket.",
+    description = "This endpoint removes the provided tags from the ticket and returns its resulting tags.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    request_body = TagsPayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Tags removed successfully.", body = Vec<String>),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 500, description = "An error occurred while removing the tags.")
+    )
+)]
+pub async fn remove_ticket_tags(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<TagsPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to remove tags from ticket with id: {id}");
+
+    require_ticket_write_access(&state, &access, id).await?;
+
+    let removed: Vec<String> = payload
+        .tags
+        .iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .collect();
+
+    let mut tags = Tag::find_by_ticket(&state, id).await?;
+    tags.retain(|tag| !removed.contains(tag));
+
+    match Tag::replace(&state, id, &tags, access.user()).await {
+        Ok(_) => {
+            info!("Tags removed from ticket {id}.");
+            Ok(Json(tags))
+        }
+        Err(e) => {
+            error!("Error removing tags from ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
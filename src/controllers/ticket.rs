@@ -1,12 +1,24 @@
 use crate::database::AppState;
-use crate::models::ticket::TicketPublic;
+use crate::models::auth::access::AccessControl;
+use crate::models::pagination::Page;
+use crate::models::precondition::Preconditions;
+use crate::models::ticket::{
+    AssignTicketPayload, MergeTicketsPayload, TicketFilters, TicketNumber, TicketPublic,
+    TicketSearchQuery, TicketSearchResult, TicketTransitions, TicketVisibility,
+};
+use crate::models::ticket_event::{TicketEvent, TicketEventPublic};
 use crate::models::user::{Role, Status, User};
 use crate::models::{ticket::CreateTicketPayload, ticket::UpdateTicketPayload, DeletePayload};
-use crate::validations::existence::ticket_exists;
+use crate::validations::{
+    access::require_ticket_write_access,
+    assignment::is_user_assignable,
+    existence::{category_exists, ticket_exists},
+    tags::normalize_tags,
+};
 use crate::{errors::api_error::ApiError, models::ticket::Ticket};
 use axum::Extension;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -59,20 +71,25 @@ pub async fn count_tickets(
 
 /// Retrieves a list of all tickets.
 ///
-/// This endpoint fetches all tickets stored in the database.
-/// If there are no tickets, returns an empty array.
+/// This endpoint fetches the tickets visible to the current user: regular users only see
+/// the tickets they requested or watch. Internal notes are only included for moderators and admins.
+/// The `breached` filter lists the tickets whose SLA is breached, so they can be triaged.
+/// Tickets can also be filtered by category, tag, status, requester, creation date and text.
+/// The listing is sorted and paginated with a cursor, returning the next cursor and the total count.
+/// If there are no tickets, returns an empty page.
 #[utoipa::path(
     get,
     path = "/api/v1/tickets",
     tags = ["Tickets"],
     summary = "List all tickets.",
-    description = "Fetches all tickets stored in the database. If there are no tickets, returns an empty array.",
+    description = "Fetches a page of the tickets matching the filters. Regular users only see the tickets they requested or watch. If there are no tickets, returns an empty page.",
+    params(TicketFilters),
     security(
         (),
         ("jwt_token" = ["jwt_token"])
     ),
     responses(
-        (status = 200, description = "Tickets retrieved successfully.", body = Vec<TicketPublic>),
+        (status = 200, description = "Tickets retrieved successfully.", body = Page<TicketPublic>),
         (status = 404, description = "No tickets found in the database."),
         (status = 500, description = "An error occurred while retrieving the tickets.")
     )
@@ -80,6 +97,7 @@ pub async fn count_tickets(
 pub async fn find_all_tickets(
     State(state): State<Arc<AppState>>,
     Extension(current_user): Extension<User>,
+    Query(filters): Query<TicketFilters>,
 ) -> Result<impl IntoResponse, ApiError> {
     debug!("Received request to retrieve all tickets.");
 
@@ -87,7 +105,9 @@ pub async fn find_all_tickets(
         return Err(ApiError::Unauthorized);
     }
 
-    match Ticket::find_all(&state).await {
+    let visibility = TicketVisibility::of(&current_user);
+
+    match Ticket::find_page(&state, &filters, &visibility).await {
         Ok(tickets) => {
             info!("Tickets listed successfully.");
             Ok(Json(tickets))
@@ -99,48 +119,128 @@ pub async fn find_all_tickets(
     }
 }
 
+/// Searches the tickets by the words of their title, description and solution.
+///
+/// This endpoint returns the matching tickets ranked by relevance, the title weighing
+/// more than the description and solution, each with a highlighted snippet.
+/// Only the tickets visible to the current user are searched.
+/// Internal notes are only included for moderators and admins.
+/// If there are no matches, returns an empty array.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/search",
+    tags = ["Tickets"],
+    summary = "Search tickets.",
+    description = "Full-text search of the tickets, ranked by relevance with highlighted snippets. If there are no matches, returns an empty array.",
+    params(TicketSearchQuery),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Tickets searched successfully.", body = Vec<TicketSearchResult>),
+        (status = 400, description = "Invalid search query."),
+        (status = 500, description = "An error occurred while searching the tickets.")
+    )
+)]
+pub async fn search_tickets(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<User>,
+    Query(query): Query<TicketSearchQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to search tickets for: {}", query.q);
+
+    if current_user.status != Status::Active {
+        return Err(ApiError::Unauthorized);
+    }
+
+    query.validate()?;
+
+    let visibility = TicketVisibility::of(&current_user);
+
+    match Ticket::search(&state, &query, &visibility).await {
+        Ok(results) => {
+            info!("Tickets searched successfully, {} found.", results.len());
+            Ok(Json(results))
+        }
+        Err(e) => {
+            error!("Error searching tickets for {}: {e}", query.q);
+            Err(e)
+        }
+    }
+}
+
+/// Resolves the ID of a ticket from either its UUID or its number, like `TCK-000123`.
+async fn resolve_ticket_id(state: &AppState, reference: &str) -> Result<Uuid, ApiError> {
+    if let Ok(id) = reference.parse::<Uuid>() {
+        return Ok(id);
+    }
+
+    let number = reference.parse::<TicketNumber>().map_err(|_| {
+        error!("Invalid ticket reference: {reference}");
+        ApiError::NotFound
+    })?;
+
+    match Ticket::find_id_by_number(state, number).await? {
+        Some(id) => Ok(id),
+        None => {
+            error!("No ticket found with number: {number}");
+            Err(ApiError::NotFound)
+        }
+    }
+}
+
 /// Retrieves a specific ticket by its ID.
 ///
-/// This endpoint searches for a ticket with the specified ID.
-/// If the ticket is found, it returns the ticket details.
+/// This endpoint searches for a ticket with the specified ID, or with the specified number like `TCK-000123`.
+/// If the ticket is found and visible to the current user, it returns the ticket details.
+/// A merged ticket points at the ticket it was merged into through `merged_into`.
+/// Internal notes are only included for moderators and admins.
+/// The `ETag` of the ticket is returned, and `If-None-Match` is answered with 304 while it holds.
+/// It changes with everything in the response, like comments, worklogs or internal notes.
 #[utoipa::path(
     get,
     path = "/api/v1/tickets/{id}",
     tags = ["Tickets"],
-    summary = "Get a specific ticket by ID.",
-    description = "This endpoint retrieves a ticket's details from the database using its ID. Returns the ticket if found, or a 404 status if not found.",
+    summary = "Get a specific ticket by ID or number.",
+    description = "This endpoint retrieves a ticket's details from the database using its ID or its number. Returns the ticket if found, or a 404 status if not found.",
     params(
-        ("id", description = "The unique identifier of the ticket to retrieve.", example = Uuid::new_v4)
+        ("id" = String, Path, description = "The unique identifier or the number of the ticket to retrieve.", example = "TCK-000123"),
+        ("If-None-Match" = Option<String>, Header, description = "Respond with 304 if the ticket still has this ETag.")
     ),
     security(
         (),
         ("jwt_token" = ["jwt_token"])
     ),
     responses(
-        (status = 200, description = "Ticket retrieved successfully.", body = Ticket),
+        (status = 200, description = "Ticket retrieved successfully.", body = Ticket, headers(
+            ("ETag" = String, description = "The version of the ticket.")
+        )),
+        (status = 304, description = "The ticket still has the ETag sent in If-None-Match."),
         (status = 404, description = "No ticket found with the specified ID."),
         (status = 500, description = "An error occurred while retrieving the ticket.")
     )
 )]
 pub async fn find_ticket_by_id(
-    Path(id): Path<Uuid>,
+    Path(reference): Path<String>,
     State(state): State<Arc<AppState>>,
     Extension(current_user): Extension<User>,
+    preconditions: Preconditions,
 ) -> impl IntoResponse {
-    debug!("Received request to retrieve ticket with id: {id}");
+    debug!("Received request to retrieve ticket with id: {reference}");
 
     if current_user.status != Status::Active {
         return Err(ApiError::Unauthorized);
     }
 
-    if current_user.role != Role::Admin && current_user.role != Role::Moderator {
-        return Err(ApiError::Unauthorized);
-    }
+    let id = resolve_ticket_id(&state, &reference).await?;
+
+    let visibility = TicketVisibility::of(&current_user);
 
-    match Ticket::find_by_id(&state, id).await {
+    match Ticket::find_by_id(&state, id, &visibility).await {
         Ok(Some(ticket)) => {
             info!("Ticket found: {id}");
-            Ok(Json(ticket))
+            Ok(preconditions.respond(ticket.updated_at, ticket))
         }
         Ok(None) => {
             error!("No ticket found with id: {id}");
@@ -172,6 +272,7 @@ pub async fn find_ticket_by_id(
     responses(
         (status = 201, description = "Ticket created successfully.", body = Uuid),
         (status = 400, description = "Invalid input, including empty name or name too short/long."),
+        (status = 404, description = "Category ID not found."),
         (status = 409, description = "Conflict: Ticket with the same name already exists."),
         (status = 500, description = "An error occurred while creating the ticket.")
     )
@@ -195,7 +296,10 @@ pub async fn create_ticket(
         CreateTicketPayload {
             title: payload.title,
             description: payload.description,
-            requester: Some(current_user.username),
+            requester: Some(current_user.username.clone()),
+            priority: payload.priority,
+            category: payload.category,
+            tags: payload.tags,
         }
     } else {
         if payload.requester.is_some() {
@@ -203,20 +307,34 @@ pub async fn create_ticket(
                 title: payload.title,
                 description: payload.description,
                 requester: payload.requester,
+                priority: payload.priority,
+                category: payload.category,
+                tags: payload.tags,
             }
         } else {
             CreateTicketPayload {
                 title: payload.title,
                 description: payload.description,
-                requester: Some(current_user.username),
+                requester: Some(current_user.username.clone()),
+                priority: payload.priority,
+                category: payload.category,
+                tags: payload.tags,
             }
         }
     };
 
     // Validations
     payload.validate()?;
+    if let Some(category) = payload.category {
+        category_exists(&state, category).await?;
+    }
+
+    let payload = CreateTicketPayload {
+        tags: payload.tags.as_deref().map(normalize_tags).transpose()?,
+        ..payload
+    };
 
-    match Ticket::create(&state, &payload).await {
+    match Ticket::create(&state, &payload, &current_user).await {
         Ok(new_ticket) => {
             info!("Ticket created! ID: {}", &new_ticket.id);
             Ok((StatusCode::CREATED, Json(new_ticket.id)))
@@ -234,13 +352,19 @@ pub async fn create_ticket(
 /// It accepts the ticket ID and the new details for the ticket.
 /// The endpoint validates the new name to ensure it is not empty,
 /// does not conflict with an existing ticket's name, and meets length requirements.
+/// Only the requester, moderators and admins can update a ticket,
+/// and tickets the current user cannot see are not found.
 /// If the ticket is successfully updated, it returns the UUID of the updated ticket.
+/// With `If-Match`, the ticket is only updated if it still has that `ETag`, otherwise 412 is returned.
 #[utoipa::path(
     put,
     path = "/api/v1/tickets",
     tags = ["Tickets"],
     summary = "Update an existing ticket.",
     description = "This endpoint updates the details of an existing ticket in the database.",
+    params(
+        ("If-Match" = Option<String>, Header, description = "Only apply the change if the ticket still has this ETag.")
+    ),
     request_body = UpdateTicketPayload,
     security(
         (),
@@ -248,30 +372,36 @@ pub async fn create_ticket(
     ),
     responses(
         (status = 200, description = "Ticket updated successfully.", body = Uuid),
+        (status = 412, description = "The ticket no longer has the ETag sent in If-Match."),
         (status = 400, description = "Invalid input, including empty name or name too short/long."),
-        (status = 404, description = "Ticket ID not found."),
+        (status = 401, description = "Only the requester, moderators and admins can update the ticket."),
+        (status = 404, description = "Ticket or category ID not found."),
         (status = 409, description = "Conflict: Ticket with the same name already exists."),
+        (status = 422, description = "The status transition is not allowed."),
         (status = 500, description = "An error occurred while updating the ticket.")
     )
 )]
 pub async fn update_ticket(
     State(state): State<Arc<AppState>>,
-    Extension(current_user): Extension<User>,
+    access: AccessControl,
+    preconditions: Preconditions,
     Json(payload): Json<UpdateTicketPayload>,
 ) -> Result<impl IntoResponse, ApiError> {
     debug!("Received request to update ticket with ID: {}", payload.id);
 
-    if current_user.status != Status::Active {
-        return Err(ApiError::Unauthorized);
-    }
+    let current_user = access.user();
 
-    // If not admin, ignore requester, status, closed_by and solution fields
+    // If not admin, ignore requester, priority, closed_by and solution fields.
+    // The allowed status changes are checked against the transition table.
     let payload = if current_user.role != Role::Admin && current_user.role != Role::Moderator {
         UpdateTicketPayload {
             id: payload.id,
             title: payload.title,
             description: payload.description,
-            status: None,
+            status: payload.status,
+            priority: None,
+            category: payload.category,
+            tags: payload.tags,
             requester: None,
             closed_by: None,
             solution: None,
@@ -282,6 +412,9 @@ pub async fn update_ticket(
             title: payload.title,
             description: payload.description,
             status: payload.status,
+            priority: payload.priority,
+            category: payload.category,
+            tags: payload.tags,
             requester: payload.requester,
             closed_by: payload.closed_by,
             solution: payload.solution,
@@ -290,9 +423,17 @@ pub async fn update_ticket(
 
     // Validations
     payload.validate()?;
-    ticket_exists(&state, payload.id).await?;
+    require_ticket_write_access(&state, &access, payload.id).await?;
+    if let Some(Some(category)) = payload.category {
+        category_exists(&state, category).await?;
+    }
 
-    match Ticket::update(&state, &payload).await {
+    let payload = UpdateTicketPayload {
+        tags: payload.tags.as_deref().map(normalize_tags).transpose()?,
+        ..payload
+    };
+
+    match Ticket::update(&state, &payload, current_user, &preconditions).await {
         Ok(ticket_id) => {
             info!("Ticket updated! ID: {ticket_id}");
             Ok(Json(ticket_id))
@@ -308,13 +449,18 @@ pub async fn update_ticket(
 ///
 /// This endpoint allows tickets to delete a specific ticket by its ID.
 /// It checks if the ticket exists before attempting to delete it.
+/// Deleted tickets are moved to the trash, from where admins can restore or purge them.
 /// If the ticket is successfully deleted, a 204 status code is returned.
+/// With `If-Match`, the ticket is only deleted if it still has that `ETag`, otherwise 412 is returned.
 #[utoipa::path(
     delete,
      path = "/api/v1/tickets",
      tags = ["Tickets"],
      summary = "Delete an existing ticket.",
-     description = "This endpoint deletes a specific ticket from the database using its ID.",
+     description = "This endpoint moves a specific ticket to the trash using its ID.",
+     params(
+        ("If-Match" = Option<String>, Header, description = "Only apply the change if the ticket still has this ETag.")
+     ),
      request_body = DeletePayload,
      security(
         (),
@@ -322,6 +468,7 @@ pub async fn update_ticket(
     ),
      responses(
          (status = 200, description = "Ticket deleted successfully"),
+         (status = 412, description = "The ticket no longer has the ETag sent in If-Match"),
          (status = 404, description = "Ticket ID not found"),
          (status = 500, description = "An error occurred while deleting the ticket")
      )
@@ -329,6 +476,7 @@ pub async fn update_ticket(
 pub async fn delete_ticket(
     State(state): State<Arc<AppState>>,
     Extension(current_user): Extension<User>,
+    preconditions: Preconditions,
     Json(payload): Json<DeletePayload>,
 ) -> Result<impl IntoResponse, ApiError> {
     debug!("Received request to delete ticket with ID: {}", payload.id);
@@ -344,7 +492,7 @@ pub async fn delete_ticket(
     // Validations
     ticket_exists(&state, payload.id).await?;
 
-    match Ticket::delete(&state, &payload).await {
+    match Ticket::delete(&state, &payload, &current_user, &preconditions).await {
         Ok(_) => {
             info!("Ticket deleted! ID: {}", &payload.id);
             Ok(StatusCode::NO_CONTENT)
@@ -355,3 +503,524 @@ pub async fn delete_ticket(
         }
     }
 }
+
+/// Retrieves the tickets in the trash.
+///
+/// This endpoint lists the deleted tickets, most recently deleted first,
+/// until they are restored or purged. Only admins can access the trash.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/trash",
+    tags = ["Tickets"],
+    summary = "List the tickets in the trash.",
+    description = "Fetches the deleted tickets, most recently deleted first. If the trash is empty, returns an empty array.",
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Trash retrieved successfully.", body = Vec<TicketPublic>),
+        (status = 403, description = "Only admins can access the trash."),
+        (status = 500, description = "An error occurred while retrieving the trash.")
+    )
+)]
+pub async fn find_trash(
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve the trash.");
+
+    access.require_role(Role::Admin)?;
+
+    match Ticket::find_trash(&state).await {
+        Ok(tickets) => {
+            info!("Trash listed successfully.");
+            Ok(Json(tickets))
+        }
+        Err(e) => {
+            error!("Error retrieving the trash: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Restores a ticket from the trash.
+///
+/// This endpoint makes a deleted ticket visible again, as it was before its deletion.
+/// Only admins can restore tickets.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/trash/{id}/restore",
+    tags = ["Tickets"],
+    summary = "Restore a ticket from the trash.",
+    description = "This endpoint restores a deleted ticket and returns its ID.",
+    params(
+        ("id", description = "The unique identifier of the deleted ticket.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Ticket restored successfully.", body = Uuid),
+        (status = 403, description = "Only admins can restore tickets."),
+        (status = 404, description = "No ticket found in the trash with the specified ID."),
+        (status = 500, description = "An error occurred while restoring the ticket.")
+    )
+)]
+pub async fn restore_ticket(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to restore ticket with ID: {id}");
+
+    access.require_role(Role::Admin)?;
+
+    match Ticket::restore(&state, id, access.user()).await {
+        Ok(ticket_id) => {
+            info!("Ticket restored! ID: {ticket_id}");
+            Ok(Json(ticket_id))
+        }
+        Err(e) => {
+            error!("Error restoring ticket with ID {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Permanently deletes a ticket from the trash.
+///
+/// This endpoint removes the ticket with its comments, tags and attachment files.
+/// Its audit history is kept. Only admins can purge tickets.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tickets/trash/{id}",
+    tags = ["Tickets"],
+    summary = "Purge a ticket from the trash.",
+    description = "This endpoint permanently deletes a ticket that is in the trash.",
+    params(
+        ("id", description = "The unique identifier of the deleted ticket.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 204, description = "Ticket purged successfully."),
+        (status = 403, description = "Only admins can purge tickets."),
+        (status = 404, description = "No ticket found in the trash with the specified ID."),
+        (status = 500, description = "An error occurred while purging the ticket.")
+    )
+)]
+pub async fn purge_ticket(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to purge ticket with ID: {id}");
+
+    access.require_role(Role::Admin)?;
+
+    match Ticket::purge(&state, id, Some(access.user())).await {
+        Ok(_) => {
+            info!("Ticket purged! ID: {id}");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            error!("Error purging ticket with ID {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Retrieves the allowed status transitions of a ticket.
+///
+/// This endpoint lists the statuses the current user can move the ticket to,
+/// according to the ticket status transition table and the user's role.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/{id}/transitions",
+    tags = ["Tickets"],
+    summary = "List the allowed status transitions of a ticket.",
+    description = "This endpoint returns the current status of the ticket and the statuses the current user can move it to.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Transitions retrieved successfully.", body = TicketTransitions),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 500, description = "An error occurred while retrieving the ticket.")
+    )
+)]
+pub async fn find_ticket_transitions(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve the transitions of ticket with id: {id}");
+
+    let ticket = match Ticket::find_by_id(&state, id, &TicketVisibility::of(access.user())).await {
+        Ok(Some(ticket)) => ticket,
+        Ok(None) => {
+            error!("No ticket found with id: {id}");
+            return Err(ApiError::NotFound);
+        }
+        Err(e) => {
+            error!("Error retrieving ticket with id {id}: {e}");
+            return Err(e);
+        }
+    };
+
+    let allowed = ticket.status.allowed_transitions(&access.user().role);
+
+    info!("Transitions of ticket {id} retrieved.");
+    Ok(Json(TicketTransitions {
+        current: ticket.status,
+        allowed,
+    }))
+}
+
+/// Retrieves the audit history of a ticket.
+///
+/// This endpoint lists every recorded change of the ticket, from the oldest to the newest:
+/// its creation, each field update with the old and new values, and its deletion.
+/// The history of deleted tickets is only available to moderators and admins.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/{id}/history",
+    tags = ["Tickets"],
+    summary = "List the audit history of a ticket.",
+    description = "This endpoint returns who changed which field of the ticket, from what to what and when.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "History retrieved successfully.", body = Vec<TicketEventPublic>),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 500, description = "An error occurred while retrieving the history.")
+    )
+)]
+pub async fn find_ticket_history(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve the history of ticket with id: {id}");
+
+    match Ticket::find_by_id(&state, id, &TicketVisibility::of(access.user())).await {
+        Ok(Some(_)) => {}
+        // Deleted tickets keep their history.
+        Ok(None) => access.require_any_role(&[Role::Admin, Role::Moderator])?,
+        Err(e) => {
+            error!("Error retrieving ticket with id {id}: {e}");
+            return Err(e);
+        }
+    }
+
+    match TicketEvent::find_by_ticket(&state, id).await {
+        Ok(events) if events.is_empty() => {
+            error!("No ticket found with id: {id}");
+            Err(ApiError::NotFound)
+        }
+        Ok(events) => {
+            info!("History of ticket {id} retrieved.");
+            Ok(Json(events))
+        }
+        Err(e) => {
+            error!("Error retrieving history of ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Merges duplicate tickets into a target ticket.
+///
+/// This endpoint moves the comments, attachments, worklogs and watchers of the source tickets to the target,
+/// whose watchers also get the requesters of the sources. The sources become merged tickets
+/// pointing at the target, and the merge is recorded in the history of every ticket.
+/// Only moderators and admins can merge tickets.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/{id}/merge",
+    tags = ["Tickets"],
+    summary = "Merge duplicate tickets into a ticket.",
+    description = "This endpoint folds the source tickets into the target ticket and returns the target ID.",
+    params(
+        ("id", description = "The unique identifier of the target ticket.", example = Uuid::new_v4)
+    ),
+    request_body = MergeTicketsPayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Tickets merged successfully.", body = Uuid),
+        (status = 400, description = "No source tickets or too many source tickets."),
+        (status = 404, description = "Target or source ticket ID not found."),
+        (status = 422, description = "The tickets cannot be merged, e.g. a source ticket is already merged."),
+        (status = 500, description = "An error occurred while merging the tickets.")
+    )
+)]
+pub async fn merge_tickets(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<MergeTicketsPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!(
+        "Received request to merge tickets {:?} into ticket with ID: {id}",
+        payload.sources
+    );
+
+    access.require_any_role(&[Role::Admin, Role::Moderator])?;
+
+    // Validations
+    payload.validate()?;
+
+    match Ticket::merge(&state, id, &payload.sources, access.user()).await {
+        Ok(ticket_id) => {
+            info!("Tickets merged into: {ticket_id}");
+            Ok(Json(ticket_id))
+        }
+        Err(e) => {
+            error!("Error merging tickets into ticket with ID {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Assigns a ticket to an agent.
+///
+/// This endpoint sets the moderator or admin responsible for working on the ticket.
+/// Only active moderators and admins can be assigned.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/{id}/assign",
+    tags = ["Tickets"],
+    summary = "Assign a ticket to an agent.",
+    description = "This endpoint assigns the ticket to the provided moderator or admin.",
+    params(
+        ("id", description = "The unique identifier of the ticket to assign.", example = Uuid::new_v4)
+    ),
+    request_body = AssignTicketPayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Ticket assigned successfully.", body = Uuid),
+        (status = 400, description = "The provided user cannot be assigned to tickets."),
+        (status = 404, description = "Ticket or user ID not found."),
+        (status = 500, description = "An error occurred while assigning the ticket.")
+    )
+)]
+pub async fn assign_ticket(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<AssignTicketPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!(
+        "Received request to assign ticket with ID {id} to: {}",
+        payload.assignee
+    );
+
+    access.require_any_role(&[Role::Admin, Role::Moderator])?;
+
+    // Validations
+    ticket_exists(&state, id).await?;
+    is_user_assignable(&state, payload.assignee).await?;
+
+    match Ticket::assign(&state, id, Some(payload.assignee), access.user()).await {
+        Ok(ticket_id) => {
+            info!("Ticket {ticket_id} assigned to: {}", payload.assignee);
+            Ok(Json(ticket_id))
+        }
+        Err(e) => {
+            error!("Error assigning ticket with ID {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Removes the assignee of a ticket.
+///
+/// This endpoint puts the ticket back in the unassigned queue.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/{id}/unassign",
+    tags = ["Tickets"],
+    summary = "Unassign a ticket.",
+    description = "This endpoint removes the current assignee of the ticket.",
+    params(
+        ("id", description = "The unique identifier of the ticket to unassign.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Ticket unassigned successfully.", body = Uuid),
+        (status = 404, description = "Ticket ID not found."),
+        (status = 500, description = "An error occurred while unassigning the ticket.")
+    )
+)]
+pub async fn unassign_ticket(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to unassign ticket with ID: {id}");
+
+    access.require_any_role(&[Role::Admin, Role::Moderator])?;
+
+    // Validations
+    ticket_exists(&state, id).await?;
+
+    match Ticket::assign(&state, id, None, access.user()).await {
+        Ok(ticket_id) => {
+            info!("Ticket unassigned! ID: {ticket_id}");
+            Ok(Json(ticket_id))
+        }
+        Err(e) => {
+            error!("Error unassigning ticket with ID {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Claims a ticket for the current user.
+///
+/// This endpoint assigns the ticket to the moderator or admin making the request.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/{id}/claim",
+    tags = ["Tickets"],
+    summary = "Claim a ticket.",
+    description = "This endpoint assigns the ticket to the current user.",
+    params(
+        ("id", description = "The unique identifier of the ticket to claim.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Ticket claimed successfully.", body = Uuid),
+        (status = 404, description = "Ticket ID not found."),
+        (status = 500, description = "An error occurred while claiming the ticket.")
+    )
+)]
+pub async fn claim_ticket(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to claim ticket with ID: {id}");
+
+    access.require_any_role(&[Role::Admin, Role::Moderator])?;
+
+    // Validations
+    ticket_exists(&state, id).await?;
+
+    match Ticket::assign(&state, id, Some(access.user().id), access.user()).await {
+        Ok(ticket_id) => {
+            info!("Ticket {ticket_id} claimed by: {}", access.user().username);
+            Ok(Json(ticket_id))
+        }
+        Err(e) => {
+            error!("Error claiming ticket with ID {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Retrieves the queue of the current user.
+///
+/// This endpoint fetches the tickets assigned to the current user that are not closed or cancelled,
+/// oldest first. If there are no tickets, returns an empty array.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/queue/mine",
+    tags = ["Tickets"],
+    summary = "List the tickets assigned to the current user.",
+    description = "Fetches the open tickets assigned to the current user, oldest first. If there are no tickets, returns an empty array.",
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Tickets retrieved successfully.", body = Vec<TicketPublic>),
+        (status = 500, description = "An error occurred while retrieving the tickets.")
+    )
+)]
+pub async fn find_my_queue(
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve the queue of the current user.");
+
+    access.require_any_role(&[Role::Admin, Role::Moderator])?;
+
+    match Ticket::find_queue(&state, Some(access.user().id)).await {
+        Ok(tickets) => {
+            info!("Queue of {} listed successfully.", access.user().username);
+            Ok(Json(tickets))
+        }
+        Err(e) => {
+            error!(
+                "Error retrieving the queue of {}: {e}",
+                access.user().username
+            );
+            Err(e)
+        }
+    }
+}
+
+/// Retrieves the unassigned queue.
+///
+/// This endpoint fetches the tickets that are not closed or cancelled and have nobody assigned,
+/// oldest first. If there are no tickets, returns an empty array.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/queue/unassigned",
+    tags = ["Tickets"],
+    summary = "List the unassigned tickets.",
+    description = "Fetches the open tickets without an assignee, oldest first. If there are no tickets, returns an empty array.",
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Tickets retrieved successfully.", body = Vec<TicketPublic>),
+        (status = 500, description = "An error occurred while retrieving the tickets.")
+    )
+)]
+pub async fn find_unassigned_queue(
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve the unassigned queue.");
+
+    access.require_any_role(&[Role::Admin, Role::Moderator])?;
+
+    match Ticket::find_queue(&state, None).await {
+        Ok(tickets) => {
+            info!("Unassigned queue listed successfully.");
+            Ok(Json(tickets))
+        }
+        Err(e) => {
+            error!("Error retrieving the unassigned queue: {e}");
+            Err(e)
+        }
+    }
+}
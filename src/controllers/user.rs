@@ -1,6 +1,9 @@
 use crate::database::AppState;
 use crate::models::auth::access::AccessControl;
-use crate::models::user::{Role, UserPublic};
+use crate::models::auth::revocation::SessionRevocation;
+use crate::models::pagination::Page;
+use crate::models::precondition::Preconditions;
+use crate::models::user::{Role, UserFilters, UserPublic};
 use crate::models::{
     user::{CreateUserPayload, UpdateUserPayload},
     DeletePayload,
@@ -8,7 +11,7 @@ use crate::models::{
 use crate::validations::{existence::user_exists, uniqueness::is_user_unique};
 use crate::{errors::api_error::ApiError, models::user::User};
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
@@ -59,20 +62,22 @@ pub async fn count_users(
 
 /// Retrieves a list of all users.
 ///
-/// This endpoint fetches all users stored in the database.
-/// If there are no users, returns an empty array.
+/// This endpoint fetches the users matching the role, status, creation date and text filters.
+/// The listing is sorted and paginated with a cursor, returning the next cursor and the total count.
+/// If there are no users, returns an empty page.
 #[utoipa::path(
     get,
     path = "/api/v1/users",
     tags = ["Users"],
     summary = "List all users.",
-    description = "Fetches all users stored in the database. If there are no users, returns an empty array.",
+    description = "Fetches a page of the users matching the filters. If there are no users, returns an empty page.",
+    params(UserFilters),
     security(
         (),
         ("jwt_token" = ["jwt_token"])
     ),
     responses(
-        (status = 200, description = "Users retrieved successfully.", body = Vec<UserPublic>),
+        (status = 200, description = "Users retrieved successfully.", body = Page<UserPublic>),
         (status = 404, description = "No users found in the database."),
         (status = 500, description = "An error occurred while retrieving the users.")
     )
@@ -80,12 +85,13 @@ pub async fn count_users(
 pub async fn find_all_users(
     State(state): State<Arc<AppState>>,
     access: AccessControl,
+    Query(filters): Query<UserFilters>,
 ) -> Result<impl IntoResponse, ApiError> {
     debug!("Received request to retrieve all users.");
 
     access.require_any_role(&[Role::Admin, Role::Moderator])?;
 
-    match User::find_all(&state).await {
+    match User::find_all(&state, &filters).await {
         Ok(users) => {
             info!("Users listed successfully.");
             Ok(Json(users))
@@ -101,6 +107,7 @@ pub async fn find_all_users(
 ///
 /// This endpoint searches for a user with the specified ID.
 /// If the user is found, it returns the user details.
+/// The `ETag` of the user is returned, and `If-None-Match` is answered with 304 while it holds.
 #[utoipa::path(
     get,
     path = "/api/v1/users/{id}",
@@ -108,14 +115,18 @@ pub async fn find_all_users(
     summary = "Get a specific user by ID.",
     description = "This endpoint retrieves a user's details from the database using its ID. Returns the user if found, or a 404 status if not found.",
     params(
-        ("id", description = "The unique identifier of the user to retrieve.", example = Uuid::new_v4)
+        ("id", description = "The unique identifier of the user to retrieve.", example = Uuid::new_v4),
+        ("If-None-Match" = Option<String>, Header, description = "Respond with 304 if the user still has this ETag.")
     ),
     security(
         (),
         ("jwt_token" = ["jwt_token"])
     ),
     responses(
-        (status = 200, description = "User retrieved successfully.", body = UserPublic),
+        (status = 200, description = "User retrieved successfully.", body = UserPublic, headers(
+            ("ETag" = String, description = "The version of the user.")
+        )),
+        (status = 304, description = "The user still has the ETag sent in If-None-Match."),
         (status = 404, description = "No user found with the specified ID."),
         (status = 500, description = "An error occurred while retrieving the user.")
     )
@@ -124,6 +135,7 @@ pub async fn find_user_by_id(
     Path(id): Path<Uuid>,
     State(state): State<Arc<AppState>>,
     access: AccessControl,
+    preconditions: Preconditions,
 ) -> impl IntoResponse {
     debug!("Received request to retrieve user with id: {id}");
 
@@ -132,7 +144,7 @@ pub async fn find_user_by_id(
     match User::find_by_id(&state, id).await {
         Ok(Some(user)) => {
             info!("User found: {id}");
-            Ok(Json(user))
+            Ok(preconditions.respond(user.updated_at, user))
         }
         Ok(None) => {
             error!("No user found with id: {id}");
@@ -206,12 +218,16 @@ pub async fn create_user(
 /// The endpoint validates the new name to ensure it is not empty,
 /// does not conflict with an existing user's name, and meets length requirements.
 /// If the user is successfully updated, it returns the UUID of the updated user.
+/// With `If-Match`, the user is only updated if it still has that `ETag`, otherwise 412 is returned.
 #[utoipa::path(
     put,
     path = "/api/v1/users",
     tags = ["Users"],
     summary = "Update an existing user.",
     description = "This endpoint updates the details of an existing user in the database.",
+    params(
+        ("If-Match" = Option<String>, Header, description = "Only apply the change if the user still has this ETag.")
+    ),
     request_body = UpdateUserPayload,
     security(
         (),
@@ -219,6 +235,7 @@ pub async fn create_user(
     ),
     responses(
         (status = 200, description = "User updated successfully.", body = Uuid),
+        (status = 412, description = "The user no longer has the ETag sent in If-Match."),
         (status = 400, description = "Invalid input, including empty name or name too short/long."),
         (status = 404, description = "User ID not found."),
         (status = 409, description = "Conflict: User with the same name already exists."),
@@ -228,6 +245,7 @@ pub async fn create_user(
 pub async fn update_user(
     State(state): State<Arc<AppState>>,
     access: AccessControl,
+    preconditions: Preconditions,
     Json(payload): Json<UpdateUserPayload>,
 ) -> Result<impl IntoResponse, ApiError> {
     debug!("Received request to update user with ID: {}", payload.id);
@@ -238,7 +256,7 @@ pub async fn update_user(
     payload.validate()?;
     user_exists(&state, payload.id).await?;
 
-    match User::update(&state, &payload).await {
+    match User::update(&state, &payload, &preconditions).await {
         Ok(user_id) => {
             info!("User updated! ID: {user_id}");
             Ok(Json(user_id))
@@ -255,12 +273,16 @@ pub async fn update_user(
 /// This endpoint allows users to delete a specific user by its ID.
 /// It checks if the user exists before attempting to delete it.
 /// If the user is successfully deleted, a 204 status code is returned.
+/// With `If-Match`, the user is only deleted if it still has that `ETag`, otherwise 412 is returned.
 #[utoipa::path(
     delete,
      path = "/api/v1/users",
      tags = ["Users"],
      summary = "Delete an existing user.",
      description = "This endpoint deletes a specific user from the database using its ID.",
+     params(
+        ("If-Match" = Option<String>, Header, description = "Only apply the change if the user still has this ETag.")
+     ),
      request_body = DeletePayload,
      security(
         (),
@@ -268,6 +290,7 @@ pub async fn update_user(
     ),
      responses(
          (status = 204, description = "User deleted successfully"),
+         (status = 412, description = "The user no longer has the ETag sent in If-Match"),
          (status = 404, description = "User ID not found"),
          (status = 500, description = "An error occurred while deleting the user")
      )
@@ -275,6 +298,7 @@ pub async fn update_user(
 pub async fn delete_user(
     State(state): State<Arc<AppState>>,
     access: AccessControl,
+    preconditions: Preconditions,
     Json(payload): Json<DeletePayload>,
 ) -> Result<impl IntoResponse, ApiError> {
     debug!("Received request to delete user with ID: {}", payload.id);
@@ -284,7 +308,7 @@ pub async fn delete_user(
     // Validations
     user_exists(&state, payload.id).await?;
 
-    match User::delete(&state, &payload).await {
+    match User::delete(&state, &payload, &preconditions).await {
         Ok(_) => {
             info!("User deleted! ID: {}", &payload.id);
             Ok(StatusCode::NO_CONTENT)
@@ -295,3 +319,51 @@ pub async fn delete_user(
         }
     }
 }
+
+/// Revokes every session of a user.
+///
+/// This endpoint revokes every JWT, refresh token and API token issued to the user until now,
+/// for instance when an employee leaves or their credentials were stolen.
+/// The user can log in again afterwards, unless their account is also made inactive.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{id}/sessions",
+    tags = ["Users"],
+    summary = "Revoke all sessions of a user.",
+    description = "This endpoint revokes every JWT, refresh token and API token issued to the user until now. Only admins can revoke sessions.",
+    params(
+        ("id", description = "The unique identifier of the user.", example = Uuid::new_v4)
+    ),
+    security(
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 204, description = "Sessions revoked successfully"),
+        (status = 401, description = "Only admins can revoke sessions"),
+        (status = 404, description = "User ID not found"),
+        (status = 500, description = "An error occurred while revoking the sessions")
+    )
+)]
+pub async fn revoke_user_sessions(
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to revoke the sessions of user with ID: {id}");
+
+    access.require_role(Role::Admin)?;
+
+    // Validations
+    user_exists(&state, id).await?;
+
+    match SessionRevocation::revoke(&state, &SessionRevocation::new(id)).await {
+        Ok(_) => {
+            info!("Sessions revoked! User ID: {id}");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            error!("Error revoking the sessions of user with ID {id}: {e}");
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::database::AppState;
+use crate::errors::api_error::ApiError;
+use crate::models::auth::access::AccessControl;
+use crate::models::ticket::RequesterInfo;
+use crate::models::user::Role;
+use crate::models::watcher::{WatchPayload, Watcher};
+use crate::validations::{access::require_ticket_access, existence::user_exists};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+/// Retrieves the watchers of a ticket.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/{id}/watchers",
+    tags = ["Watchers"],
+    summary = "List the watchers of a ticket.",
+    description = "Fetches the users subscribed to the ticket, in subscription order.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Watchers retrieved successfully.", body = Vec<RequesterInfo>),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 500, description = "An error occurred while retrieving the watchers.")
+    )
+)]
+pub async fn find_watchers(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve watchers of ticket with id: {id}");
+
+    require_ticket_access(&state, &access, id).await?;
+
+    match Watcher::find_by_ticket(&state, id).await {
+        Ok(watchers) => {
+            info!("Watchers of ticket {id} listed successfully.");
+            Ok(Json(watchers))
+        }
+        Err(e) => {
+            error!("Error retrieving watchers of ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Subscribes a user to a ticket.
+///
+/// Users can subscribe themselves to the tickets they can see. The requester of the ticket,
+/// moderators and admins can also subscribe other users, who then get read access to the ticket.
+/// Subscribing a user who already watches the ticket has no effect.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/{id}/watchers",
+    tags = ["Watchers"],
+    summary = "Subscribe a user to a ticket.",
+    description = "This endpoint subscribes the given user, or the current user by default, to the ticket.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    request_body(content = Option<WatchPayload>, description = "The user to subscribe, the current user when omitted."),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 204, description = "User subscribed successfully."),
+        (status = 404, description = "No ticket or user found with the specified ID."),
+        (status = 500, description = "An error occurred while subscribing the user.")
+    )
+)]
+pub async fn subscribe(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    payload: Option<Json<WatchPayload>>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to subscribe to ticket with id: {id}");
+
+    let ticket = require_ticket_access(&state, &access, id).await?;
+
+    let Json(payload) = payload.unwrap_or_default();
+    let user_id = payload.user.unwrap_or(access.user().id);
+    if user_id != access.user().id {
+        access.require_owner_or_any_role(ticket.requester.id, &[Role::Admin, Role::Moderator])?;
+        user_exists(&state, user_id).await?;
+    }
+
+    match Watcher::subscribe(&state, &Watcher::new(id, user_id)).await {
+        Ok(_) => {
+            info!("User {user_id} subscribed to ticket {id}.");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            error!("Error subscribing user {user_id} to ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Unsubscribes a user from a ticket.
+///
+/// Watchers can unsubscribe themselves, while the requester of the ticket,
+/// moderators and admins can unsubscribe any watcher.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tickets/{id}/watchers/{user_id}",
+    tags = ["Watchers"],
+    summary = "Unsubscribe a user from a ticket.",
+    description = "This endpoint removes the subscription of the user to the ticket.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4),
+        ("user_id", description = "The unique identifier of the watcher.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 204, description = "User unsubscribed successfully."),
+        (status = 404, description = "The user does not watch the ticket."),
+        (status = 500, description = "An error occurred while unsubscribing the user.")
+    )
+)]
+pub async fn unsubscribe(
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to unsubscribe user {user_id} from ticket with id: {id}");
+
+    let ticket = require_ticket_access(&state, &access, id).await?;
+
+    if user_id != access.user().id {
+        access.require_owner_or_any_role(ticket.requester.id, &[Role::Admin, Role::Moderator])?;
+    }
+
+    match Watcher::unsubscribe(&state, id, user_id).await {
+        Ok(_) => {
+            info!("User {user_id} unsubscribed from ticket {id}.");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            error!("Error unsubscribing user {user_id} from ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use crate::database::AppState;
+use crate::errors::api_error::ApiError;
+use crate::models::auth::access::AccessControl;
+use crate::models::user::Role;
+use crate::models::worklog::{CreateWorklogPayload, UpdateWorklogPayload, Worklog, WorklogPublic};
+use crate::models::DeletePayload;
+use crate::validations::access::require_ticket_access;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Retrieves the worklog with the given ID, ensuring it belongs to the ticket
+/// and that the current user logged it, unless they are an admin.
+async fn find_ticket_worklog(
+    state: &AppState,
+    access: &AccessControl,
+    ticket_id: Uuid,
+    worklog_id: Uuid,
+) -> Result<Worklog, ApiError> {
+    let worklog = match Worklog::find_by_id(state, worklog_id).await? {
+        Some(worklog) if worklog.ticket_id == ticket_id => worklog,
+        _ => {
+            error!("No worklog found with id {worklog_id} on ticket {ticket_id}");
+            return Err(ApiError::NotFound);
+        }
+    };
+
+    access.require_owner_or_any_role(worklog.agent, &[Role::Admin])?;
+
+    Ok(worklog)
+}
+
+/// Retrieves all worklogs of a ticket.
+///
+/// This endpoint fetches the time logged on the ticket, ordered by the day of the work.
+/// Only moderators and admins can see the worklogs.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tickets/{id}/worklogs",
+    tags = ["Worklogs"],
+    summary = "List all worklogs of a ticket.",
+    description = "Fetches all worklogs of the ticket, from the oldest to the newest work day. If there are no worklogs, returns an empty array.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Worklogs retrieved successfully.", body = Vec<WorklogPublic>),
+        (status = 401, description = "Only moderators and admins can see the worklogs."),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 500, description = "An error occurred while retrieving the worklogs.")
+    )
+)]
+pub async fn find_worklogs(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to retrieve worklogs of ticket with id: {id}");
+
+    access.require_any_role(&[Role::Admin, Role::Moderator])?;
+    require_ticket_access(&state, &access, id).await?;
+
+    match Worklog::find_by_ticket(&state, id).await {
+        Ok(worklogs) => {
+            info!("Worklogs of ticket {id} listed successfully.");
+            Ok(Json(worklogs))
+        }
+        Err(e) => {
+            error!("Error retrieving worklogs of ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Logs time spent on a ticket.
+///
+/// This endpoint records the minutes the current user worked on the ticket on a given day.
+/// Only moderators and admins can log time.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tickets/{id}/worklogs",
+    tags = ["Worklogs"],
+    summary = "Log time spent on a ticket.",
+    description = "This endpoint adds a worklog, by the current user, to the ticket.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    request_body = CreateWorklogPayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 201, description = "Worklog created successfully.", body = Uuid),
+        (status = 400, description = "Invalid input, including minutes out of range or note too long."),
+        (status = 401, description = "Only moderators and admins can log time."),
+        (status = 404, description = "No ticket found with the specified ID."),
+        (status = 500, description = "An error occurred while creating the worklog.")
+    )
+)]
+pub async fn create_worklog(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<CreateWorklogPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to log time on ticket with id: {id}");
+
+    access.require_any_role(&[Role::Admin, Role::Moderator])?;
+    require_ticket_access(&state, &access, id).await?;
+
+    // Validations
+    payload.validate()?;
+
+    let worklog = Worklog::new(id, access.user().id, &payload);
+
+    match Worklog::create(&state, &worklog).await {
+        Ok(_) => {
+            info!("Worklog created! ID: {}", worklog.id);
+            Ok((StatusCode::CREATED, Json(worklog.id)))
+        }
+        Err(e) => {
+            error!("Error logging time on ticket {id}: {e}");
+            Err(e)
+        }
+    }
+}
+
+/// Updates an existing worklog.
+///
+/// This endpoint updates the minutes, note or day of an existing worklog.
+/// Only the agent who logged the time and admins can edit it.
+#[utoipa::path(
+    put,
+    path = "/api/v1/tickets/{id}/worklogs",
+    tags = ["Worklogs"],
+    summary = "Update an existing worklog.",
+    description = "This endpoint updates an existing worklog of the ticket.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    request_body = UpdateWorklogPayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 200, description = "Worklog updated successfully.", body = Uuid),
+        (status = 400, description = "Invalid input, including minutes out of range or note too long."),
+        (status = 404, description = "Ticket or worklog ID not found."),
+        (status = 500, description = "An error occurred while updating the worklog.")
+    )
+)]
+pub async fn update_worklog(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<UpdateWorklogPayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to update worklog with ID: {}", payload.id);
+
+    access.require_any_role(&[Role::Admin, Role::Moderator])?;
+    require_ticket_access(&state, &access, id).await?;
+    find_ticket_worklog(&state, &access, id, payload.id).await?;
+
+    // Validations
+    payload.validate()?;
+
+    match Worklog::update(&state, &payload).await {
+        Ok(worklog_id) => {
+            info!("Worklog updated! ID: {worklog_id}");
+            Ok(Json(worklog_id))
+        }
+        Err(e) => {
+            error!("Error updating worklog with ID {}: {e}", payload.id);
+            Err(e)
+        }
+    }
+}
+
+/// Deletes an existing worklog.
+///
+/// This endpoint deletes a specific worklog of the ticket by its ID.
+/// Only the agent who logged the time and admins can delete it.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tickets/{id}/worklogs",
+    tags = ["Worklogs"],
+    summary = "Delete an existing worklog.",
+    description = "This endpoint deletes a specific worklog of the ticket using its ID.",
+    params(
+        ("id", description = "The unique identifier of the ticket.", example = Uuid::new_v4)
+    ),
+    request_body = DeletePayload,
+    security(
+        (),
+        ("jwt_token" = ["jwt_token"])
+    ),
+    responses(
+        (status = 204, description = "Worklog deleted successfully"),
+        (status = 404, description = "Ticket or worklog ID not found"),
+        (status = 500, description = "An error occurred while deleting the worklog")
+    )
+)]
+pub async fn delete_worklog(
+    Path(id): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+    access: AccessControl,
+    Json(payload): Json<DeletePayload>,
+) -> Result<impl IntoResponse, ApiError> {
+    debug!("Received request to delete worklog with ID: {}", payload.id);
+
+    access.require_any_role(&[Role::Admin, Role::Moderator])?;
+    require_ticket_access(&state, &access, id).await?;
+    find_ticket_worklog(&state, &access, id, payload.id).await?;
+
+    match Worklog::delete(&state, &payload).await {
+        Ok(_) => {
+            info!("Worklog deleted! ID: {}", &payload.id);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(e) => {
+            error!("Error deleting worklog with ID {}: {e}", payload.id);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,12 @@
+CREATE TABLE ticket_comments (
+    id UUID PRIMARY KEY,
+    ticket_id UUID NOT NULL,
+    author UUID NOT NULL,
+    content TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    FOREIGN KEY (ticket_id) REFERENCES tickets(id) ON DELETE CASCADE,
+    FOREIGN KEY (author) REFERENCES users(id)
+);
+
+CREATE INDEX idx_ticket_comments_ticket_id ON ticket_comments (ticket_id);
@@ -0,0 +1 @@
+ALTER TABLE ticket_comments ADD COLUMN internal BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,4 @@
+ALTER TABLE tickets ADD COLUMN assignee UUID NULL;
+ALTER TABLE tickets ADD FOREIGN KEY (assignee) REFERENCES users(id);
+
+CREATE INDEX idx_tickets_assignee ON tickets (assignee);
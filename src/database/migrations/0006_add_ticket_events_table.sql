@@ -0,0 +1,16 @@
+CREATE TYPE ticket_event_kind AS ENUM ('created', 'updated', 'deleted');
+
+-- Events are kept after the ticket is deleted, so `ticket_id` has no foreign key.
+CREATE TABLE ticket_events (
+    id UUID PRIMARY KEY,
+    ticket_id UUID NOT NULL,
+    actor UUID NULL,
+    kind ticket_event_kind NOT NULL,
+    field VARCHAR(50) NULL,
+    old_value TEXT NULL,
+    new_value TEXT NULL,
+    created_at TIMESTAMP NOT NULL,
+    FOREIGN KEY (actor) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_ticket_events_ticket_id ON ticket_events (ticket_id);
@@ -0,0 +1,8 @@
+CREATE TYPE ticket_priority AS ENUM ('low', 'normal', 'high', 'urgent');
+
+ALTER TABLE tickets ADD COLUMN priority ticket_priority NOT NULL DEFAULT 'normal';
+ALTER TABLE tickets ADD COLUMN response_due_at TIMESTAMP NULL;
+ALTER TABLE tickets ADD COLUMN due_at TIMESTAMP NULL;
+ALTER TABLE tickets ADD COLUMN responded_at TIMESTAMP NULL;
+
+CREATE INDEX idx_tickets_due_at ON tickets (due_at);
@@ -0,0 +1,14 @@
+CREATE TABLE ticket_attachments (
+    id UUID PRIMARY KEY,
+    ticket_id UUID NOT NULL,
+    uploaded_by UUID NOT NULL,
+    filename VARCHAR(255) NOT NULL,
+    content_type VARCHAR(255) NOT NULL,
+    size BIGINT NOT NULL,
+    storage_key VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    FOREIGN KEY (ticket_id) REFERENCES tickets(id) ON DELETE CASCADE,
+    FOREIGN KEY (uploaded_by) REFERENCES users(id)
+);
+
+CREATE INDEX idx_ticket_attachments_ticket_id ON ticket_attachments (ticket_id);
@@ -0,0 +1,21 @@
+CREATE TABLE categories (
+    id UUID PRIMARY KEY,
+    name VARCHAR(50) NOT NULL UNIQUE,
+    description TEXT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+
+ALTER TABLE tickets ADD COLUMN category UUID NULL;
+ALTER TABLE tickets ADD FOREIGN KEY (category) REFERENCES categories(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tickets_category ON tickets (category);
+
+CREATE TABLE ticket_tags (
+    ticket_id UUID NOT NULL,
+    tag VARCHAR(30) NOT NULL,
+    PRIMARY KEY (ticket_id, tag),
+    FOREIGN KEY (ticket_id) REFERENCES tickets(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_ticket_tags_tag ON ticket_tags (tag);
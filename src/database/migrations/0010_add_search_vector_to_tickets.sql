@@ -0,0 +1,23 @@
+ALTER TABLE tickets ADD COLUMN search_vector TSVECTOR NOT NULL DEFAULT ''::TSVECTOR;
+
+CREATE FUNCTION tickets_search_vector_update() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector :=
+        setweight(to_tsvector('english', COALESCE(NEW.title, '')), 'A') ||
+        setweight(to_tsvector('english', COALESCE(NEW.description, '')), 'B') ||
+        setweight(to_tsvector('english', COALESCE(NEW.solution, '')), 'B');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER tickets_search_vector_insert
+    BEFORE INSERT ON tickets
+    FOR EACH ROW EXECUTE FUNCTION tickets_search_vector_update();
+
+CREATE TRIGGER tickets_search_vector_update
+    BEFORE UPDATE OF title, description, solution ON tickets
+    FOR EACH ROW EXECUTE FUNCTION tickets_search_vector_update();
+
+UPDATE tickets SET title = title;
+
+CREATE INDEX idx_tickets_search_vector ON tickets USING GIN (search_vector);
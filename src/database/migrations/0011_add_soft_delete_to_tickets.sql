@@ -0,0 +1,9 @@
+ALTER TYPE ticket_event_kind ADD VALUE 'restored';
+ALTER TYPE ticket_event_kind ADD VALUE 'purged';
+
+-- Deleted tickets stay in the trash until they are restored or purged.
+ALTER TABLE tickets ADD COLUMN deleted_at TIMESTAMP NULL;
+ALTER TABLE tickets ADD COLUMN deleted_by UUID NULL;
+ALTER TABLE tickets ADD FOREIGN KEY (deleted_by) REFERENCES users(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tickets_deleted_at ON tickets (deleted_at);
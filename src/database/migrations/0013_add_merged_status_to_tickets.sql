@@ -0,0 +1,7 @@
+ALTER TYPE ticket_status ADD VALUE 'merged';
+
+-- Merged tickets point at the ticket they were folded into.
+ALTER TABLE tickets ADD COLUMN merged_into UUID NULL;
+ALTER TABLE tickets ADD FOREIGN KEY (merged_into) REFERENCES tickets(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tickets_merged_into ON tickets (merged_into);
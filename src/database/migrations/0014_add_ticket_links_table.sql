@@ -0,0 +1,18 @@
+CREATE TYPE ticket_link_kind AS ENUM ('parent_of', 'blocks', 'relates_to', 'duplicate_of');
+
+-- Links read from the source: the source is the parent of, blocks, relates to or duplicates the target.
+CREATE TABLE ticket_links (
+    id UUID PRIMARY KEY,
+    source_id UUID NOT NULL,
+    target_id UUID NOT NULL,
+    kind ticket_link_kind NOT NULL,
+    created_by UUID NULL,
+    created_at TIMESTAMP NOT NULL,
+    UNIQUE (source_id, target_id, kind),
+    CHECK (source_id <> target_id),
+    FOREIGN KEY (source_id) REFERENCES tickets(id) ON DELETE CASCADE,
+    FOREIGN KEY (target_id) REFERENCES tickets(id) ON DELETE CASCADE,
+    FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_ticket_links_target_id ON ticket_links (target_id);
@@ -0,0 +1,15 @@
+CREATE TABLE ticket_worklogs (
+    id UUID PRIMARY KEY,
+    ticket_id UUID NOT NULL,
+    agent UUID NOT NULL,
+    minutes INTEGER NOT NULL CHECK (minutes > 0),
+    note TEXT,
+    work_date DATE NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    FOREIGN KEY (ticket_id) REFERENCES tickets(id) ON DELETE CASCADE,
+    FOREIGN KEY (agent) REFERENCES users(id)
+);
+
+CREATE INDEX idx_ticket_worklogs_ticket_id ON ticket_worklogs (ticket_id);
+CREATE INDEX idx_ticket_worklogs_agent_work_date ON ticket_worklogs (agent, work_date);
@@ -0,0 +1,13 @@
+CREATE TABLE ticket_ratings (
+    ticket_id UUID PRIMARY KEY,
+    rater UUID NOT NULL,
+    agent UUID,
+    score SMALLINT NOT NULL CHECK (score BETWEEN 1 AND 5),
+    comment TEXT,
+    created_at TIMESTAMP NOT NULL,
+    FOREIGN KEY (ticket_id) REFERENCES tickets(id) ON DELETE CASCADE,
+    FOREIGN KEY (rater) REFERENCES users(id),
+    FOREIGN KEY (agent) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_ticket_ratings_agent_created_at ON ticket_ratings (agent, created_at);
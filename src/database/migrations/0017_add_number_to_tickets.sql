@@ -0,0 +1,18 @@
+CREATE SEQUENCE ticket_number_seq;
+
+ALTER TABLE tickets ADD COLUMN number BIGINT;
+
+-- Existing tickets are numbered in the order they were created.
+UPDATE tickets t
+SET number = numbered.number
+FROM (SELECT id, ROW_NUMBER() OVER (ORDER BY created_at, id) AS number FROM tickets) numbered
+WHERE numbered.id = t.id;
+
+SELECT setval('ticket_number_seq', COALESCE((SELECT MAX(number) FROM tickets), 0) + 1, false);
+
+ALTER TABLE tickets
+    ALTER COLUMN number SET DEFAULT nextval('ticket_number_seq'),
+    ALTER COLUMN number SET NOT NULL,
+    ADD CONSTRAINT tickets_number_key UNIQUE (number);
+
+ALTER SEQUENCE ticket_number_seq OWNED BY tickets.number;
@@ -0,0 +1,26 @@
+CREATE TYPE api_token_scope AS ENUM (
+    'tickets:read',
+    'tickets:write',
+    'users:read',
+    'users:write',
+    'categories:read',
+    'categories:write',
+    'tags:read',
+    'tags:write',
+    'reports:read'
+);
+
+CREATE TABLE api_tokens (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    prefix VARCHAR(16) NOT NULL,
+    token_hash VARCHAR(64) UNIQUE NOT NULL,
+    scopes api_token_scope[] NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    last_used_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_api_tokens_user_id ON api_tokens (user_id);
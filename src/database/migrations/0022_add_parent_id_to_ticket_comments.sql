@@ -0,0 +1,4 @@
+ALTER TABLE ticket_comments ADD COLUMN parent_id UUID NULL;
+ALTER TABLE ticket_comments ADD FOREIGN KEY (parent_id) REFERENCES ticket_comments(id) ON DELETE CASCADE;
+
+CREATE INDEX idx_ticket_comments_parent_id ON ticket_comments (parent_id);
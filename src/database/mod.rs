@@ -1,8 +1,19 @@
 pub mod connection;
 pub mod repositories;
 
+use crate::{
+    models::auth::revocation::RevocationCache,
+    storage::Storage,
+    utils::{keys::JwtKeys, oidc::OidcClient},
+};
 use sqlx::PgPool;
+use std::sync::Arc;
 
 pub struct AppState {
     pub db: PgPool,
+    pub storage: Arc<dyn Storage>,
+    pub revocations: RevocationCache,
+    pub jwt_keys: JwtKeys,
+    /// Single sign-on, when an OpenID Connect provider is configured.
+    pub oidc: Option<OidcClient>,
 }
@@ -0,0 +1,118 @@
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::{
+        auth::api_token::{ApiToken, ApiTokenPublic},
+        DeletePayload,
+    },
+};
+use chrono::NaiveDateTime;
+use tracing::debug;
+use uuid::Uuid;
+
+#[async_trait::async_trait]
+pub trait ApiTokenRepository {
+    async fn find_by_user(state: &AppState, user_id: Uuid)
+        -> Result<Vec<ApiTokenPublic>, ApiError>;
+    async fn create(state: &AppState, api_token: &ApiToken) -> Result<(), ApiError>;
+    async fn delete(
+        state: &AppState,
+        user_id: Uuid,
+        payload: &DeletePayload,
+    ) -> Result<(), ApiError>;
+    async fn touch(
+        state: &AppState,
+        token_hash: &str,
+        used_at: NaiveDateTime,
+    ) -> Result<Option<ApiToken>, ApiError>;
+}
+
+pub struct ApiTokenRepositoryImpl;
+
+#[async_trait::async_trait]
+impl ApiTokenRepository for ApiTokenRepositoryImpl {
+    async fn find_by_user(
+        state: &AppState,
+        user_id: Uuid,
+    ) -> Result<Vec<ApiTokenPublic>, ApiError> {
+        debug!("Attempting to retrieve API tokens of user with ID: {user_id}");
+
+        let api_tokens: Vec<ApiTokenPublic> = sqlx::query_as(
+            r#"
+        SELECT id, name, prefix, scopes, expires_at, last_used_at, created_at
+        FROM api_tokens
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        "#,
+        )
+        .bind(user_id)
+        .fetch_all(&state.db)
+        .await?;
+
+        Ok(api_tokens)
+    }
+
+    async fn create(state: &AppState, api_token: &ApiToken) -> Result<(), ApiError> {
+        debug!(
+            "Attempting to create API token {} for user with ID: {}",
+            api_token.name, api_token.user_id
+        );
+
+        sqlx::query(r#"INSERT INTO api_tokens (id, user_id, name, prefix, token_hash, scopes, expires_at, last_used_at, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#)
+            .bind(api_token.id)
+            .bind(api_token.user_id)
+            .bind(&api_token.name)
+            .bind(&api_token.prefix)
+            .bind(&api_token.token_hash)
+            .bind(&api_token.scopes)
+            .bind(api_token.expires_at)
+            .bind(api_token.last_used_at)
+            .bind(api_token.created_at)
+            .execute(&state.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete(
+        state: &AppState,
+        user_id: Uuid,
+        payload: &DeletePayload,
+    ) -> Result<(), ApiError> {
+        debug!("Attempting to revoke API token with ID: {}", payload.id);
+
+        // Users only revoke their own tokens: those of others are not found.
+        let result = sqlx::query(r#"DELETE FROM api_tokens WHERE id = $1 AND user_id = $2"#)
+            .bind(payload.id)
+            .bind(user_id)
+            .execute(&state.db)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn touch(
+        state: &AppState,
+        token_hash: &str,
+        used_at: NaiveDateTime,
+    ) -> Result<Option<ApiToken>, ApiError> {
+        let api_token: Option<ApiToken> = sqlx::query_as(
+            r#"
+        UPDATE api_tokens
+        SET last_used_at = $2
+        WHERE token_hash = $1 AND expires_at > $2
+        RETURNING *
+        "#,
+        )
+        .bind(token_hash)
+        .bind(used_at)
+        .fetch_optional(&state.db)
+        .await?;
+
+        Ok(api_token)
+    }
+}
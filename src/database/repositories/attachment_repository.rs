@@ -0,0 +1,165 @@
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::{
+        attachment::{Attachment, AttachmentPublic},
+        ticket::RequesterInfo,
+    },
+};
+use sqlx::Row;
+use std::collections::HashMap;
+use tracing::debug;
+use uuid::Uuid;
+
+#[async_trait::async_trait]
+pub trait AttachmentRepository {
+    async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<AttachmentPublic>, ApiError>;
+    async fn find_by_tickets(
+        state: &AppState,
+        ticket_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<AttachmentPublic>>, ApiError>;
+    async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Attachment>, ApiError>;
+    async fn create(state: &AppState, attachment: &Attachment, data: &[u8])
+        -> Result<(), ApiError>;
+    async fn delete(state: &AppState, attachment: &Attachment) -> Result<(), ApiError>;
+}
+
+pub struct AttachmentRepositoryImpl;
+
+#[async_trait::async_trait]
+impl AttachmentRepository for AttachmentRepositoryImpl {
+    async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<AttachmentPublic>, ApiError> {
+        debug!("Attempting to retrieve attachments of ticket with ID: {ticket_id}");
+
+        let mut attachments = Self::find_by_tickets(state, &[ticket_id]).await?;
+
+        Ok(attachments.remove(&ticket_id).unwrap_or_default())
+    }
+
+    async fn find_by_tickets(
+        state: &AppState,
+        ticket_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<AttachmentPublic>>, ApiError> {
+        debug!(
+            "Attempting to retrieve attachments of {} tickets",
+            ticket_ids.len()
+        );
+
+        let rows = sqlx::query(
+            r#"
+        SELECT
+            f.id AS attachment_id,
+            f.ticket_id,
+            f.filename,
+            f.content_type,
+            f.size,
+            f.created_at,
+
+            -- uploaded_by
+            u.id AS uploaded_by_id,
+            u.username AS uploaded_by_username,
+            u.email AS uploaded_by_email,
+            u.first_name AS uploaded_by_first_name,
+            u.last_name AS uploaded_by_last_name
+        FROM ticket_attachments f
+        JOIN users u ON u.id = f.uploaded_by
+        WHERE f.ticket_id = ANY($1)
+        ORDER BY f.created_at ASC
+        "#,
+        )
+        .bind(ticket_ids)
+        .fetch_all(&state.db)
+        .await?;
+
+        let mut attachments: HashMap<Uuid, Vec<AttachmentPublic>> = HashMap::new();
+
+        for row in rows {
+            let attachment = AttachmentPublic {
+                id: row.get("attachment_id"),
+                ticket_id: row.get("ticket_id"),
+                uploaded_by: RequesterInfo {
+                    id: row.get("uploaded_by_id"),
+                    username: row.get("uploaded_by_username"),
+                    email: row.get("uploaded_by_email"),
+                    first_name: row.get("uploaded_by_first_name"),
+                    last_name: row.get("uploaded_by_last_name"),
+                },
+                filename: row.get("filename"),
+                content_type: row.get("content_type"),
+                size: row.get("size"),
+                created_at: row.get("created_at"),
+            };
+
+            attachments
+                .entry(attachment.ticket_id)
+                .or_default()
+                .push(attachment);
+        }
+
+        Ok(attachments)
+    }
+
+    async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Attachment>, ApiError> {
+        debug!("Attempting to retrieve attachment with id: {id}");
+
+        let attachment: Option<Attachment> =
+            sqlx::query_as(r#"SELECT * FROM ticket_attachments WHERE id = $1;"#)
+                .bind(id)
+                .fetch_optional(&state.db)
+                .await?;
+
+        Ok(attachment)
+    }
+
+    async fn create(
+        state: &AppState,
+        attachment: &Attachment,
+        data: &[u8],
+    ) -> Result<(), ApiError> {
+        debug!(
+            "Attempting to create attachment on ticket with ID: {}",
+            attachment.ticket_id
+        );
+
+        // The file is stored first, so a row never points to missing contents.
+        state.storage.save(&attachment.storage_key, data).await?;
+
+        let result = sqlx::query(r#"INSERT INTO ticket_attachments (id, ticket_id, uploaded_by, filename, content_type, size, storage_key, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#)
+        .bind(attachment.id)
+        .bind(attachment.ticket_id)
+        .bind(attachment.uploaded_by)
+        .bind(&attachment.filename)
+        .bind(&attachment.content_type)
+        .bind(attachment.size)
+        .bind(&attachment.storage_key)
+        .bind(attachment.created_at)
+        .execute(&state.db)
+        .await;
+
+        if let Err(e) = result {
+            state.storage.remove(&attachment.storage_key).await?;
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+
+    async fn delete(state: &AppState, attachment: &Attachment) -> Result<(), ApiError> {
+        debug!("Attempting to delete attachment with ID: {}", attachment.id);
+
+        sqlx::query(r#"DELETE FROM ticket_attachments WHERE id = $1;"#)
+            .bind(attachment.id)
+            .execute(&state.db)
+            .await?;
+
+        state.storage.remove(&attachment.storage_key).await?;
+
+        Ok(())
+    }
+}
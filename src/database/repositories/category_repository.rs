@@ -0,0 +1,126 @@
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::{
+        category::{Category, CreateCategoryPayload, UpdateCategoryPayload},
+        DeletePayload,
+    },
+};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+#[async_trait::async_trait]
+pub trait CategoryRepository {
+    async fn find_all(state: &AppState) -> Result<Vec<Category>, ApiError>;
+    async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Category>, ApiError>;
+    async fn create(
+        state: &AppState,
+        payload: &CreateCategoryPayload,
+    ) -> Result<Category, ApiError>;
+    async fn update(state: &AppState, payload: &UpdateCategoryPayload) -> Result<Uuid, ApiError>;
+    async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError>;
+}
+
+pub struct CategoryRepositoryImpl;
+
+#[async_trait::async_trait]
+impl CategoryRepository for CategoryRepositoryImpl {
+    async fn find_all(state: &AppState) -> Result<Vec<Category>, ApiError> {
+        debug!("Attempting to retrieve all categories from the database...");
+
+        let categories: Vec<Category> =
+            sqlx::query_as(r#"SELECT * FROM categories ORDER BY name ASC;"#)
+                .fetch_all(&state.db)
+                .await?;
+
+        Ok(categories)
+    }
+
+    async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Category>, ApiError> {
+        debug!("Attempting to retrieve category with id: {id}");
+
+        let category: Option<Category> =
+            sqlx::query_as(r#"SELECT * FROM categories WHERE id = $1;"#)
+                .bind(id)
+                .fetch_optional(&state.db)
+                .await?;
+
+        Ok(category)
+    }
+
+    async fn create(
+        state: &AppState,
+        payload: &CreateCategoryPayload,
+    ) -> Result<Category, ApiError> {
+        debug!("Attempting to create category with name: {}", payload.name);
+
+        let new_category = Category::new(&payload.name, payload.description.clone());
+
+        sqlx::query(r#"INSERT INTO categories (id, name, description, created_at, updated_at) VALUES ($1, $2, $3, $4, $5)"#)
+        .bind(new_category.id)
+        .bind(&new_category.name)
+        .bind(&new_category.description)
+        .bind(new_category.created_at)
+        .bind(new_category.updated_at)
+        .execute(&state.db)
+        .await?;
+
+        Ok(new_category)
+    }
+
+    async fn update(state: &AppState, payload: &UpdateCategoryPayload) -> Result<Uuid, ApiError> {
+        debug!("Attempting to update category with ID: {}", payload.id);
+
+        let category_id = payload.id;
+        let mut updated = false;
+
+        // Update `name` if provided.
+        if let Some(name) = &payload.name {
+            sqlx::query(r#"UPDATE categories SET name = $1 WHERE id = $2;"#)
+                .bind(name)
+                .bind(category_id)
+                .execute(&state.db)
+                .await?;
+
+            info!("Updated name of category with ID: {category_id}");
+            updated = true;
+        }
+
+        // Update `description` if provided.
+        if let Some(description) = &payload.description {
+            sqlx::query(r#"UPDATE categories SET description = $1 WHERE id = $2;"#)
+                .bind(description)
+                .bind(category_id)
+                .execute(&state.db)
+                .await?;
+
+            info!("Updated description of category with ID: {category_id}");
+            updated = true;
+        }
+
+        // Update `updated_at` field.
+        if updated {
+            sqlx::query(r#"UPDATE categories SET updated_at = $1 WHERE id = $2;"#)
+                .bind(chrono::Utc::now().naive_utc())
+                .bind(category_id)
+                .execute(&state.db)
+                .await?;
+        } else {
+            return Err(ApiError::NotModified);
+        }
+
+        Ok(category_id)
+    }
+
+    async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError> {
+        debug!("Attempting to delete category with ID: {}", payload.id);
+
+        // Tickets of the category are left without one.
+        sqlx::query(r#"DELETE FROM categories WHERE id = $1;"#)
+            .bind(payload.id)
+            .execute(&state.db)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::{
+        comment::{Comment, CommentPublic, CreateCommentPayload, UpdateCommentPayload},
+        ticket::RequesterInfo,
+        DeletePayload,
+    },
+};
+use sqlx::Row;
+use std::collections::HashMap;
+use tracing::debug;
+use uuid::Uuid;
+
+#[async_trait::async_trait]
+pub trait CommentRepository {
+    async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+        include_internal: bool,
+    ) -> Result<Vec<CommentPublic>, ApiError>;
+    async fn find_by_tickets(
+        state: &AppState,
+        ticket_ids: &[Uuid],
+        include_internal: bool,
+    ) -> Result<HashMap<Uuid, Vec<CommentPublic>>, ApiError>;
+    async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Comment>, ApiError>;
+    async fn create(
+        state: &AppState,
+        ticket_id: Uuid,
+        author: Uuid,
+        payload: &CreateCommentPayload,
+    ) -> Result<Comment, ApiError>;
+    async fn update(state: &AppState, payload: &UpdateCommentPayload) -> Result<Uuid, ApiError>;
+    async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError>;
+}
+
+pub struct CommentRepositoryImpl;
+
+#[async_trait::async_trait]
+impl CommentRepository for CommentRepositoryImpl {
+    async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+        include_internal: bool,
+    ) -> Result<Vec<CommentPublic>, ApiError> {
+        debug!("Attempting to retrieve comments of ticket with ID: {ticket_id}");
+
+        let mut comments = Self::find_by_tickets(state, &[ticket_id], include_internal).await?;
+
+        Ok(comments.remove(&ticket_id).unwrap_or_default())
+    }
+
+    async fn find_by_tickets(
+        state: &AppState,
+        ticket_ids: &[Uuid],
+        include_internal: bool,
+    ) -> Result<HashMap<Uuid, Vec<CommentPublic>>, ApiError> {
+        debug!(
+            "Attempting to retrieve comments of {} tickets",
+            ticket_ids.len()
+        );
+
+        // Internal notes are only returned when explicitly requested.
+        let rows = sqlx::query(
+            r#"
+        SELECT
+            c.id AS comment_id,
+            c.ticket_id,
+            c.content,
+            c.internal,
+            c.parent_id,
+            c.created_at AS comment_created_at,
+            c.updated_at AS comment_updated_at,
+
+            -- author
+            u.id AS author_id,
+            u.username AS author_username,
+            u.email AS author_email,
+            u.first_name AS author_first_name,
+            u.last_name AS author_last_name
+        FROM ticket_comments c
+        JOIN users u ON u.id = c.author
+        WHERE c.ticket_id = ANY($1) AND ($2 OR NOT c.internal)
+        ORDER BY c.created_at ASC
+        "#,
+        )
+        .bind(ticket_ids)
+        .bind(include_internal)
+        .fetch_all(&state.db)
+        .await?;
+
+        let mut comments: HashMap<Uuid, Vec<CommentPublic>> = HashMap::new();
+
+        for row in rows {
+            let comment = CommentPublic {
+                id: row.get("comment_id"),
+                ticket_id: row.get("ticket_id"),
+                author: RequesterInfo {
+                    id: row.get("author_id"),
+                    username: row.get("author_username"),
+                    email: row.get("author_email"),
+                    first_name: row.get("author_first_name"),
+                    last_name: row.get("author_last_name"),
+                },
+                content: row.get("content"),
+                internal: row.get("internal"),
+                parent_id: row.get("parent_id"),
+                created_at: row.get("comment_created_at"),
+                updated_at: row.get("comment_updated_at"),
+            };
+
+            comments.entry(comment.ticket_id).or_default().push(comment);
+        }
+
+        Ok(comments)
+    }
+
+    async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Comment>, ApiError> {
+        debug!("Attempting to retrieve comment with id: {id}");
+
+        let comment: Option<Comment> =
+            sqlx::query_as(r#"SELECT * FROM ticket_comments WHERE id = $1;"#)
+                .bind(id)
+                .fetch_optional(&state.db)
+                .await?;
+
+        Ok(comment)
+    }
+
+    async fn create(
+        state: &AppState,
+        ticket_id: Uuid,
+        author: Uuid,
+        payload: &CreateCommentPayload,
+    ) -> Result<Comment, ApiError> {
+        debug!("Attempting to create comment on ticket with ID: {ticket_id}");
+
+        let new_comment = Comment::new(
+            ticket_id,
+            author,
+            &payload.content,
+            payload.internal.unwrap_or_default(),
+            payload.parent_id,
+        );
+
+        sqlx::query(r#"INSERT INTO ticket_comments (id, ticket_id, author, content, internal, parent_id, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#)
+        .bind(new_comment.id)
+        .bind(new_comment.ticket_id)
+        .bind(new_comment.author)
+        .bind(&new_comment.content)
+        .bind(new_comment.internal)
+        .bind(new_comment.parent_id)
+        .bind(new_comment.created_at)
+        .bind(new_comment.updated_at)
+        .execute(&state.db)
+        .await?;
+
+        Ok(new_comment)
+    }
+
+    async fn update(state: &AppState, payload: &UpdateCommentPayload) -> Result<Uuid, ApiError> {
+        debug!("Attempting to update comment with ID: {}", payload.id);
+
+        let result = sqlx::query(
+            r#"UPDATE ticket_comments SET content = $1, updated_at = $2 WHERE id = $3;"#,
+        )
+        .bind(&payload.content)
+        .bind(chrono::Utc::now().naive_utc())
+        .bind(payload.id)
+        .execute(&state.db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotModified);
+        }
+
+        Ok(payload.id)
+    }
+
+    async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError> {
+        debug!("Attempting to delete comment with ID: {}", payload.id);
+
+        sqlx::query(r#"DELETE FROM ticket_comments WHERE id = $1;"#)
+            .bind(payload.id)
+            .execute(&state.db)
+            .await?;
+
+        Ok(())
+    }
+}
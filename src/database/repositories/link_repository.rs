@@ -0,0 +1,246 @@
+use super::{
+    ticket_event_repository::{TicketEventRepository, TicketEventRepositoryImpl},
+    ticket_repository::visible_to,
+};
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::{
+        link::{LinkedTicket, TicketLink, TicketLinkKind, TicketLinkPublic},
+        ticket_event::TicketEvent,
+        user::User,
+    },
+};
+use sqlx::{PgConnection, Row};
+use std::collections::HashMap;
+use tracing::debug;
+use uuid::Uuid;
+
+#[async_trait::async_trait]
+pub trait LinkRepository {
+    async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+        viewer: Option<Uuid>,
+    ) -> Result<Vec<TicketLinkPublic>, ApiError>;
+    async fn find_by_tickets(
+        state: &AppState,
+        ticket_ids: &[Uuid],
+        viewer: Option<Uuid>,
+    ) -> Result<HashMap<Uuid, Vec<TicketLinkPublic>>, ApiError>;
+    async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<TicketLink>, ApiError>;
+    async fn exists(
+        state: &AppState,
+        source_id: Uuid,
+        target_id: Uuid,
+        kind: TicketLinkKind,
+    ) -> Result<bool, ApiError>;
+    async fn is_reachable(
+        state: &AppState,
+        from: Uuid,
+        to: Uuid,
+        kind: TicketLinkKind,
+    ) -> Result<bool, ApiError>;
+    async fn count_open_children(conn: &mut PgConnection, parent_id: Uuid)
+        -> Result<i64, ApiError>;
+    async fn create(state: &AppState, link: &TicketLink, actor: &User) -> Result<(), ApiError>;
+    async fn delete(state: &AppState, link: &TicketLink, actor: &User) -> Result<(), ApiError>;
+}
+
+pub struct LinkRepositoryImpl;
+
+#[async_trait::async_trait]
+impl LinkRepository for LinkRepositoryImpl {
+    async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+        viewer: Option<Uuid>,
+    ) -> Result<Vec<TicketLinkPublic>, ApiError> {
+        debug!("Attempting to retrieve links of ticket with ID: {ticket_id}");
+
+        let mut links = Self::find_by_tickets(state, &[ticket_id], viewer).await?;
+
+        Ok(links.remove(&ticket_id).unwrap_or_default())
+    }
+
+    async fn find_by_tickets(
+        state: &AppState,
+        ticket_ids: &[Uuid],
+        viewer: Option<Uuid>,
+    ) -> Result<HashMap<Uuid, Vec<TicketLinkPublic>>, ApiError> {
+        debug!(
+            "Attempting to retrieve links of {} tickets",
+            ticket_ids.len()
+        );
+
+        // Links are listed from both ends, only showing the linked tickets visible to the viewer.
+        let rows = sqlx::query(&format!(
+            r#"
+        SELECT l.id, l.source_id AS ticket_id, l.kind, FALSE AS incoming, l.created_at,
+            t.id AS linked_id, t.title AS linked_title, t.status AS linked_status
+        FROM ticket_links l
+        JOIN tickets t ON t.id = l.target_id
+        WHERE l.source_id = ANY($1) AND {visibility}
+        UNION ALL
+        SELECT l.id, l.target_id AS ticket_id, l.kind, TRUE AS incoming, l.created_at,
+            t.id AS linked_id, t.title AS linked_title, t.status AS linked_status
+        FROM ticket_links l
+        JOIN tickets t ON t.id = l.source_id
+        WHERE l.target_id = ANY($1) AND {visibility}
+        ORDER BY created_at ASC
+        "#,
+            visibility = visible_to(2)
+        ))
+        .bind(ticket_ids)
+        .bind(viewer)
+        .fetch_all(&state.db)
+        .await?;
+
+        let mut links: HashMap<Uuid, Vec<TicketLinkPublic>> = HashMap::new();
+
+        for row in rows {
+            let kind: TicketLinkKind = row.get("kind");
+            let link = TicketLinkPublic {
+                id: row.get("id"),
+                relation: kind.relation(row.get("incoming")),
+                ticket: LinkedTicket {
+                    id: row.get("linked_id"),
+                    title: row.get("linked_title"),
+                    status: row.get("linked_status"),
+                },
+                created_at: row.get("created_at"),
+            };
+
+            links.entry(row.get("ticket_id")).or_default().push(link);
+        }
+
+        Ok(links)
+    }
+
+    async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<TicketLink>, ApiError> {
+        debug!("Attempting to retrieve link with ID: {id}");
+
+        let link: Option<TicketLink> =
+            sqlx::query_as(r#"SELECT * FROM ticket_links WHERE id = $1;"#)
+                .bind(id)
+                .fetch_optional(&state.db)
+                .await?;
+
+        Ok(link)
+    }
+
+    async fn exists(
+        state: &AppState,
+        source_id: Uuid,
+        target_id: Uuid,
+        kind: TicketLinkKind,
+    ) -> Result<bool, ApiError> {
+        let exists: bool = sqlx::query_scalar(
+            r#"SELECT EXISTS (SELECT 1 FROM ticket_links WHERE source_id = $1 AND target_id = $2 AND kind = $3);"#,
+        )
+        .bind(source_id)
+        .bind(target_id)
+        .bind(kind)
+        .fetch_one(&state.db)
+        .await?;
+
+        Ok(exists)
+    }
+
+    async fn is_reachable(
+        state: &AppState,
+        from: Uuid,
+        to: Uuid,
+        kind: TicketLinkKind,
+    ) -> Result<bool, ApiError> {
+        let reachable: bool = sqlx::query_scalar(
+            r#"
+        WITH RECURSIVE reachable(id) AS (
+            SELECT target_id FROM ticket_links WHERE source_id = $1 AND kind = $3
+            UNION
+            SELECT l.target_id FROM ticket_links l JOIN reachable r ON l.source_id = r.id
+            WHERE l.kind = $3
+        )
+        SELECT EXISTS (SELECT 1 FROM reachable WHERE id = $2);
+        "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(kind)
+        .fetch_one(&state.db)
+        .await?;
+
+        Ok(reachable)
+    }
+
+    /// Counts the open children on the connection, within the transaction closing the parent.
+    async fn count_open_children(
+        conn: &mut PgConnection,
+        parent_id: Uuid,
+    ) -> Result<i64, ApiError> {
+        let count: i64 = sqlx::query_scalar(
+            r#"
+        SELECT COUNT(*)
+        FROM ticket_links l
+        JOIN tickets c ON c.id = l.target_id
+        WHERE l.source_id = $1
+            AND l.kind = 'parent_of'
+            AND c.deleted_at IS NULL
+            AND c.status NOT IN ('closed', 'cancelled', 'merged');
+        "#,
+        )
+        .bind(parent_id)
+        .fetch_one(conn)
+        .await?;
+
+        Ok(count)
+    }
+
+    async fn create(state: &AppState, link: &TicketLink, actor: &User) -> Result<(), ApiError> {
+        debug!(
+            "Attempting to link ticket {} to ticket {} as {}",
+            link.source_id, link.target_id, link.kind
+        );
+
+        sqlx::query(r#"INSERT INTO ticket_links (id, source_id, target_id, kind, created_by, created_at) VALUES ($1, $2, $3, $4, $5, $6)"#)
+            .bind(link.id)
+            .bind(link.source_id)
+            .bind(link.target_id)
+            .bind(link.kind)
+            .bind(link.created_by)
+            .bind(link.created_at)
+            .execute(&state.db)
+            .await?;
+
+        let event = TicketEvent::updated(
+            link.source_id,
+            actor.id,
+            "links",
+            None,
+            Some(format!("{} {}", link.kind, link.target_id)),
+        );
+        TicketEventRepositoryImpl::create(state, &[event]).await?;
+
+        Ok(())
+    }
+
+    async fn delete(state: &AppState, link: &TicketLink, actor: &User) -> Result<(), ApiError> {
+        debug!("Attempting to delete link with ID: {}", link.id);
+
+        sqlx::query(r#"DELETE FROM ticket_links WHERE id = $1;"#)
+            .bind(link.id)
+            .execute(&state.db)
+            .await?;
+
+        let event = TicketEvent::updated(
+            link.source_id,
+            actor.id,
+            "links",
+            Some(format!("{} {}", link.kind, link.target_id)),
+            None,
+        );
+        TicketEventRepositoryImpl::create(state, &[event]).await?;
+
+        Ok(())
+    }
+}
@@ -1,2 +1,15 @@
+pub mod api_token_repository;
+pub mod attachment_repository;
+pub mod category_repository;
+pub mod comment_repository;
+pub mod link_repository;
+pub mod oidc_repository;
+pub mod rating_repository;
+pub mod refresh_token_repository;
+pub mod revocation_repository;
+pub mod tag_repository;
+pub mod ticket_event_repository;
 pub mod ticket_repository;
 pub mod user_repository;
+pub mod watcher_repository;
+pub mod worklog_repository;
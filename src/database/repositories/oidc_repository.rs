@@ -0,0 +1,247 @@
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::{
+        auth::oidc::{IdTokenClaims, OidcLoginState},
+        user::{Role, User},
+    },
+    utils::hashing::{encrypt_password, generate_token},
+};
+use chrono::{NaiveDateTime, Utc};
+use sqlx::PgConnection;
+use tracing::{debug, info};
+
+#[async_trait::async_trait]
+pub trait OidcRepository {
+    async fn create_login_state(
+        state: &AppState,
+        login_state: &OidcLoginState,
+    ) -> Result<(), ApiError>;
+    async fn take_login_state(
+        state: &AppState,
+        login_state: &str,
+        now: NaiveDateTime,
+    ) -> Result<Option<OidcLoginState>, ApiError>;
+    async fn sign_in(
+        state: &AppState,
+        claims: &IdTokenClaims,
+        role: Option<Role>,
+    ) -> Result<User, ApiError>;
+}
+
+pub struct OidcRepositoryImpl;
+
+/// Builds a free username for a provisioned user from the claims, suffixed when already taken.
+async fn available_username(
+    conn: &mut PgConnection,
+    claims: &IdTokenClaims,
+) -> Result<String, ApiError> {
+    let base: String = claims
+        .preferred_username
+        .as_deref()
+        .or_else(|| claims.email.as_deref()?.split('@').next())
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        .take(20)
+        .collect();
+    let base = if base.len() < 3 {
+        String::from("user")
+    } else {
+        base
+    };
+
+    let mut username = base.clone();
+    loop {
+        let exists: bool =
+            sqlx::query_scalar(r#"SELECT EXISTS (SELECT 1 FROM users WHERE username = $1)"#)
+                .bind(&username)
+                .fetch_one(&mut *conn)
+                .await?;
+
+        if !exists {
+            return Ok(username);
+        }
+
+        username = format!("{base}-{}", &generate_token()[..4]);
+    }
+}
+
+/// Truncates a claim to the size of its column.
+fn truncated(claim: Option<&str>, max_chars: usize) -> Option<String> {
+    claim.map(|value| value.chars().take(max_chars).collect())
+}
+
+#[async_trait::async_trait]
+impl OidcRepository for OidcRepositoryImpl {
+    async fn create_login_state(
+        state: &AppState,
+        login_state: &OidcLoginState,
+    ) -> Result<(), ApiError> {
+        let mut tx = state.db.begin().await?;
+
+        // Logins abandoned at the identity provider are never completed.
+        sqlx::query(r#"DELETE FROM oidc_login_states WHERE expires_at <= $1"#)
+            .bind(login_state.created_at)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(r#"INSERT INTO oidc_login_states (state, code_verifier, nonce, expires_at, created_at) VALUES ($1, $2, $3, $4, $5)"#)
+            .bind(&login_state.state)
+            .bind(&login_state.code_verifier)
+            .bind(&login_state.nonce)
+            .bind(login_state.expires_at)
+            .bind(login_state.created_at)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn take_login_state(
+        state: &AppState,
+        login_state: &str,
+        now: NaiveDateTime,
+    ) -> Result<Option<OidcLoginState>, ApiError> {
+        let login_state: Option<OidcLoginState> =
+            sqlx::query_as(r#"DELETE FROM oidc_login_states WHERE state = $1 RETURNING *"#)
+                .bind(login_state)
+                .fetch_optional(&state.db)
+                .await?;
+
+        Ok(login_state.filter(|login_state| login_state.expires_at > now))
+    }
+
+    async fn sign_in(
+        state: &AppState,
+        claims: &IdTokenClaims,
+        role: Option<Role>,
+    ) -> Result<User, ApiError> {
+        debug!(
+            "Attempting to sign in identity {} of issuer {}",
+            claims.sub, claims.iss
+        );
+
+        let now = Utc::now().naive_utc();
+        let mut tx = state.db.begin().await?;
+
+        let linked: Option<User> = sqlx::query_as(
+            r#"
+        SELECT u.*
+        FROM user_identities i
+        JOIN users u ON u.id = i.user_id
+        WHERE i.issuer = $1 AND i.subject = $2
+        FOR UPDATE OF u
+        "#,
+        )
+        .bind(&claims.iss)
+        .bind(&claims.sub)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let mut user = match linked {
+            Some(user) => {
+                sqlx::query(r#"UPDATE user_identities SET last_login_at = $3 WHERE issuer = $1 AND subject = $2"#)
+                    .bind(&claims.iss)
+                    .bind(&claims.sub)
+                    .bind(now)
+                    .execute(&mut *tx)
+                    .await?;
+
+                user
+            }
+            None => {
+                // Only an email verified by the provider proves the identity belongs to the user having it.
+                let existing: Option<User> = match claims.verified_email() {
+                    Some(email) => {
+                        sqlx::query_as(
+                            r#"SELECT * FROM users WHERE LOWER(email) = LOWER($1) FOR UPDATE"#,
+                        )
+                        .bind(email)
+                        .fetch_optional(&mut *tx)
+                        .await?
+                    }
+                    None => None,
+                };
+
+                let user = match existing {
+                    Some(user) => {
+                        info!(
+                            "Linking identity {} of issuer {} to user: {}",
+                            claims.sub, claims.iss, user.username
+                        );
+                        user
+                    }
+                    None => {
+                        // Provisioned users sign in with the provider only: nobody knows their password.
+                        let user = User::new(
+                            &available_username(&mut tx, claims).await?,
+                            truncated(claims.verified_email(), 100),
+                            encrypt_password(&generate_token())?.as_str(),
+                            truncated(claims.given_name.as_deref(), 50),
+                            truncated(claims.family_name.as_deref(), 50),
+                            role.clone(),
+                            None,
+                        );
+
+                        sqlx::query(r#"INSERT INTO users (id, username, email, password_hash, first_name, last_name, role, status, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"#)
+                            .bind(user.id)
+                            .bind(&user.username)
+                            .bind(&user.email)
+                            .bind(&user.password_hash)
+                            .bind(&user.first_name)
+                            .bind(&user.last_name)
+                            .bind(&user.role)
+                            .bind(&user.status)
+                            .bind(user.created_at)
+                            .bind(user.updated_at)
+                            .execute(&mut *tx)
+                            .await?;
+
+                        info!(
+                            "Provisioned user {} for identity {} of issuer {}",
+                            user.username, claims.sub, claims.iss
+                        );
+                        user
+                    }
+                };
+
+                sqlx::query(r#"INSERT INTO user_identities (issuer, subject, user_id, created_at, last_login_at) VALUES ($1, $2, $3, $4, $5)"#)
+                    .bind(&claims.iss)
+                    .bind(&claims.sub)
+                    .bind(user.id)
+                    .bind(now)
+                    .bind(now)
+                    .execute(&mut *tx)
+                    .await?;
+
+                user
+            }
+        };
+
+        if let Some(role) = role.filter(|role| *role != user.role) {
+            info!(
+                "Updating role of user {} from {} to {} from the identity provider",
+                user.username,
+                user.role.to_string(),
+                role.to_string()
+            );
+
+            sqlx::query(r#"UPDATE users SET role = $1, updated_at = $2 WHERE id = $3"#)
+                .bind(&role)
+                .bind(now)
+                .bind(user.id)
+                .execute(&mut *tx)
+                .await?;
+
+            user.role = role;
+            user.updated_at = now;
+        }
+
+        tx.commit().await?;
+
+        Ok(user)
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::{
+        rating::{Rating, RatingReportEntry},
+        report::ReportQuery,
+        ticket::RequesterInfo,
+    },
+};
+use sqlx::Row;
+use tracing::debug;
+
+#[async_trait::async_trait]
+pub trait RatingRepository {
+    async fn create(state: &AppState, rating: &Rating) -> Result<(), ApiError>;
+    async fn report(
+        state: &AppState,
+        query: &ReportQuery,
+    ) -> Result<Vec<RatingReportEntry>, ApiError>;
+}
+
+pub struct RatingRepositoryImpl;
+
+#[async_trait::async_trait]
+impl RatingRepository for RatingRepositoryImpl {
+    async fn create(state: &AppState, rating: &Rating) -> Result<(), ApiError> {
+        debug!("Attempting to rate ticket with ID: {}", rating.ticket_id);
+
+        // The resolution is credited to the assignee, or to whoever closed the ticket.
+        let result = sqlx::query(
+            r#"
+        INSERT INTO ticket_ratings (ticket_id, rater, agent, score, comment, created_at)
+        SELECT t.id, $2, COALESCE(t.assignee, t.closed_by), $3, $4, $5
+        FROM tickets t
+        WHERE t.id = $1
+        ON CONFLICT (ticket_id) DO NOTHING
+        "#,
+        )
+        .bind(rating.ticket_id)
+        .bind(rating.rater)
+        .bind(rating.score)
+        .bind(&rating.comment)
+        .bind(rating.created_at)
+        .execute(&state.db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::AlreadyRated);
+        }
+
+        Ok(())
+    }
+
+    async fn report(
+        state: &AppState,
+        query: &ReportQuery,
+    ) -> Result<Vec<RatingReportEntry>, ApiError> {
+        let period = query.period.unwrap_or_default();
+
+        debug!(
+            "Attempting to report ratings per agent and {}",
+            period.as_sql()
+        );
+
+        let rows = sqlx::query(
+            r#"
+        SELECT
+            date_trunc($1::TEXT, r.created_at)::DATE AS period,
+            COUNT(*) AS ratings,
+            AVG(r.score)::FLOAT8 AS average,
+            AVG(CASE WHEN r.score >= 4 THEN 1 ELSE 0 END)::FLOAT8 AS satisfaction,
+
+            -- agent
+            u.id AS agent_id,
+            u.username AS agent_username,
+            u.email AS agent_email,
+            u.first_name AS agent_first_name,
+            u.last_name AS agent_last_name
+        FROM ticket_ratings r
+        JOIN users u ON u.id = r.agent
+        WHERE ($2::DATE IS NULL OR r.created_at >= $2)
+            AND ($3::DATE IS NULL OR r.created_at < $3 + 1)
+            AND ($4::UUID IS NULL OR r.agent = $4)
+        GROUP BY period, u.id
+        ORDER BY period ASC, u.username ASC
+        "#,
+        )
+        .bind(period.as_sql())
+        .bind(query.from)
+        .bind(query.to)
+        .bind(query.agent)
+        .fetch_all(&state.db)
+        .await?;
+
+        let report = rows
+            .iter()
+            .map(|row| RatingReportEntry {
+                agent: RequesterInfo {
+                    id: row.get("agent_id"),
+                    username: row.get("agent_username"),
+                    email: row.get("agent_email"),
+                    first_name: row.get("agent_first_name"),
+                    last_name: row.get("agent_last_name"),
+                },
+                period: row.get("period"),
+                ratings: row.get("ratings"),
+                average: row.get("average"),
+                satisfaction: row.get("satisfaction"),
+            })
+            .collect();
+
+        Ok(report)
+    }
+}
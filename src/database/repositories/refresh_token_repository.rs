@@ -0,0 +1,124 @@
+use crate::{
+    database::AppState,
+    errors::{api_error::ApiError, auth_error::AuthError},
+    models::{
+        auth::refresh_token::RefreshToken,
+        user::{Status, User},
+    },
+};
+use chrono::Utc;
+use sqlx::PgConnection;
+use tracing::{debug, warn};
+
+#[async_trait::async_trait]
+pub trait RefreshTokenRepository {
+    async fn create(state: &AppState, refresh_token: &RefreshToken) -> Result<(), ApiError>;
+    async fn create_in(
+        conn: &mut PgConnection,
+        refresh_token: &RefreshToken,
+    ) -> Result<(), ApiError>;
+    async fn rotate(
+        state: &AppState,
+        token_hash: &str,
+    ) -> Result<(User, RefreshToken, String), ApiError>;
+}
+
+pub struct RefreshTokenRepositoryImpl;
+
+#[async_trait::async_trait]
+impl RefreshTokenRepository for RefreshTokenRepositoryImpl {
+    async fn create(state: &AppState, refresh_token: &RefreshToken) -> Result<(), ApiError> {
+        let mut conn = state.db.acquire().await?;
+        Self::create_in(&mut conn, refresh_token).await
+    }
+
+    /// Stores the token on the connection, as part of its current transaction if any.
+    async fn create_in(
+        conn: &mut PgConnection,
+        refresh_token: &RefreshToken,
+    ) -> Result<(), ApiError> {
+        debug!(
+            "Issuing refresh token of family {} for user with ID: {}",
+            refresh_token.family_id, refresh_token.user_id
+        );
+
+        sqlx::query(r#"INSERT INTO refresh_tokens (id, user_id, family_id, token_hash, expires_at, used_at, revoked_at, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#)
+            .bind(refresh_token.id)
+            .bind(refresh_token.user_id)
+            .bind(refresh_token.family_id)
+            .bind(&refresh_token.token_hash)
+            .bind(refresh_token.expires_at)
+            .bind(refresh_token.used_at)
+            .bind(refresh_token.revoked_at)
+            .bind(refresh_token.created_at)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn rotate(
+        state: &AppState,
+        token_hash: &str,
+    ) -> Result<(User, RefreshToken, String), ApiError> {
+        let mut tx = state.db.begin().await?;
+
+        // Locking the token lets a single exchange use it, any concurrent one counting as a reuse.
+        let current: Option<RefreshToken> =
+            sqlx::query_as(r#"SELECT * FROM refresh_tokens WHERE token_hash = $1 FOR UPDATE"#)
+                .bind(token_hash)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+        let current = match current {
+            Some(refresh_token) => refresh_token,
+            None => return Err(ApiError::from(AuthError::InvalidRefreshToken)),
+        };
+
+        let now = Utc::now().naive_utc();
+
+        if current.revoked_at.is_some() || current.expires_at <= now {
+            return Err(ApiError::from(AuthError::InvalidRefreshToken));
+        }
+
+        // A used token may have been stolen: neither its holder nor the legitimate user can be told apart,
+        // so every token of the family is revoked.
+        if current.used_at.is_some() {
+            warn!(
+                "Reuse of refresh token of family {} for user with ID: {}, revoking the family",
+                current.family_id, current.user_id
+            );
+
+            sqlx::query(r#"UPDATE refresh_tokens SET revoked_at = $1 WHERE family_id = $2 AND revoked_at IS NULL"#)
+                .bind(now)
+                .bind(current.family_id)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+
+            return Err(ApiError::from(AuthError::RefreshTokenReused));
+        }
+
+        let user: User = sqlx::query_as(r#"SELECT * FROM users WHERE id = $1"#)
+            .bind(current.user_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        if user.status != Status::Active {
+            return Err(ApiError::Unauthorized);
+        }
+
+        sqlx::query(r#"UPDATE refresh_tokens SET used_at = $1 WHERE id = $2"#)
+            .bind(now)
+            .bind(current.id)
+            .execute(&mut *tx)
+            .await?;
+
+        let (next, token) = RefreshToken::new(current.user_id, current.family_id);
+        Self::create_in(&mut tx, &next).await?;
+
+        tx.commit().await?;
+
+        Ok((user, next, token))
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::auth::revocation::{RevokedToken, SessionRevocation},
+};
+use chrono::{NaiveDateTime, Utc};
+use tracing::debug;
+use uuid::Uuid;
+
+#[async_trait::async_trait]
+pub trait RevocationRepository {
+    async fn find_active(
+        state: &AppState,
+    ) -> Result<(Vec<RevokedToken>, Vec<SessionRevocation>), ApiError>;
+    async fn revoke_token(
+        state: &AppState,
+        token: &RevokedToken,
+        session_id: Uuid,
+    ) -> Result<(), ApiError>;
+    async fn revoke_sessions(
+        state: &AppState,
+        revocation: &SessionRevocation,
+    ) -> Result<(), ApiError>;
+    async fn purge_expired(
+        state: &AppState,
+        expired_before: NaiveDateTime,
+    ) -> Result<u64, ApiError>;
+}
+
+pub struct RevocationRepositoryImpl;
+
+#[async_trait::async_trait]
+impl RevocationRepository for RevocationRepositoryImpl {
+    async fn find_active(
+        state: &AppState,
+    ) -> Result<(Vec<RevokedToken>, Vec<SessionRevocation>), ApiError> {
+        debug!("Attempting to retrieve the revocations of unexpired tokens");
+
+        let tokens: Vec<RevokedToken> =
+            sqlx::query_as(r#"SELECT * FROM revoked_tokens WHERE expires_at > $1"#)
+                .bind(Utc::now().naive_utc())
+                .fetch_all(&state.db)
+                .await?;
+
+        let sessions: Vec<SessionRevocation> =
+            sqlx::query_as(r#"SELECT * FROM session_revocations"#)
+                .fetch_all(&state.db)
+                .await?;
+
+        Ok((tokens, sessions))
+    }
+
+    async fn revoke_token(
+        state: &AppState,
+        token: &RevokedToken,
+        session_id: Uuid,
+    ) -> Result<(), ApiError> {
+        debug!(
+            "Attempting to revoke token {} of user with ID: {}",
+            token.jti, token.user_id
+        );
+
+        let mut tx = state.db.begin().await?;
+
+        sqlx::query(r#"INSERT INTO revoked_tokens (jti, user_id, expires_at, revoked_at) VALUES ($1, $2, $3, $4) ON CONFLICT (jti) DO NOTHING"#)
+            .bind(token.jti)
+            .bind(token.user_id)
+            .bind(token.expires_at)
+            .bind(token.revoked_at)
+            .execute(&mut *tx)
+            .await?;
+
+        // The refresh tokens of the session would otherwise issue new access tokens.
+        sqlx::query(r#"UPDATE refresh_tokens SET revoked_at = $1 WHERE family_id = $2 AND user_id = $3 AND revoked_at IS NULL"#)
+            .bind(token.revoked_at)
+            .bind(session_id)
+            .bind(token.user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn revoke_sessions(
+        state: &AppState,
+        revocation: &SessionRevocation,
+    ) -> Result<(), ApiError> {
+        debug!(
+            "Attempting to revoke every session of user with ID: {}",
+            revocation.user_id
+        );
+
+        let mut tx = state.db.begin().await?;
+
+        sqlx::query(r#"INSERT INTO session_revocations (user_id, revoked_at) VALUES ($1, $2) ON CONFLICT (user_id) DO UPDATE SET revoked_at = EXCLUDED.revoked_at"#)
+            .bind(revocation.user_id)
+            .bind(revocation.revoked_at)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(r#"UPDATE refresh_tokens SET revoked_at = $1 WHERE user_id = $2 AND revoked_at IS NULL"#)
+            .bind(revocation.revoked_at)
+            .bind(revocation.user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        // API tokens are not sessions, but they would keep giving access to the user all the same.
+        sqlx::query(r#"DELETE FROM api_tokens WHERE user_id = $1"#)
+            .bind(revocation.user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn purge_expired(
+        state: &AppState,
+        expired_before: NaiveDateTime,
+    ) -> Result<u64, ApiError> {
+        debug!("Attempting to purge the revocations of tokens expired before {expired_before}");
+
+        let result = sqlx::query(r#"DELETE FROM revoked_tokens WHERE expires_at < $1"#)
+            .bind(expired_before)
+            .execute(&state.db)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
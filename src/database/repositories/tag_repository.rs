@@ -0,0 +1,117 @@
+use super::ticket_event_repository::{TicketEventRepository, TicketEventRepositoryImpl};
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::{tag::Tag, ticket_event::TicketEvent, user::User},
+};
+use sqlx::PgConnection;
+use tracing::debug;
+use uuid::Uuid;
+
+#[async_trait::async_trait]
+pub trait TagRepository {
+    async fn find_all(state: &AppState) -> Result<Vec<Tag>, ApiError>;
+    async fn find_by_ticket(state: &AppState, ticket_id: Uuid) -> Result<Vec<String>, ApiError>;
+    async fn replace(
+        state: &AppState,
+        ticket_id: Uuid,
+        tags: &[String],
+        actor: &User,
+    ) -> Result<(), ApiError>;
+    async fn replace_in(
+        conn: &mut PgConnection,
+        ticket_id: Uuid,
+        tags: &[String],
+        actor: &User,
+    ) -> Result<(), ApiError>;
+}
+
+pub struct TagRepositoryImpl;
+
+#[async_trait::async_trait]
+impl TagRepository for TagRepositoryImpl {
+    async fn find_all(state: &AppState) -> Result<Vec<Tag>, ApiError> {
+        debug!("Attempting to retrieve all tags from the database...");
+
+        let tags: Vec<Tag> = sqlx::query_as(
+            r#"SELECT tag AS name, COUNT(*) AS tickets FROM ticket_tags JOIN tickets t ON t.id = ticket_id WHERE t.deleted_at IS NULL GROUP BY tag ORDER BY tag ASC;"#,
+        )
+        .fetch_all(&state.db)
+        .await?;
+
+        Ok(tags)
+    }
+
+    async fn find_by_ticket(state: &AppState, ticket_id: Uuid) -> Result<Vec<String>, ApiError> {
+        debug!("Attempting to retrieve tags of ticket with ID: {ticket_id}");
+
+        let tags: Vec<String> = sqlx::query_scalar(
+            r#"SELECT tag FROM ticket_tags WHERE ticket_id = $1 ORDER BY tag ASC;"#,
+        )
+        .bind(ticket_id)
+        .fetch_all(&state.db)
+        .await?;
+
+        Ok(tags)
+    }
+
+    async fn replace(
+        state: &AppState,
+        ticket_id: Uuid,
+        tags: &[String],
+        actor: &User,
+    ) -> Result<(), ApiError> {
+        let mut tx = state.db.begin().await?;
+
+        Self::replace_in(&mut tx, ticket_id, tags, actor).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Replaces the tags on the connection, as part of its current transaction if any.
+    async fn replace_in(
+        conn: &mut PgConnection,
+        ticket_id: Uuid,
+        tags: &[String],
+        actor: &User,
+    ) -> Result<(), ApiError> {
+        debug!("Attempting to replace tags of ticket with ID: {ticket_id}");
+
+        let previous: Vec<String> = sqlx::query_scalar(
+            r#"SELECT tag FROM ticket_tags WHERE ticket_id = $1 ORDER BY tag ASC;"#,
+        )
+        .bind(ticket_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        if previous == tags {
+            return Ok(());
+        }
+
+        sqlx::query(r#"DELETE FROM ticket_tags WHERE ticket_id = $1;"#)
+            .bind(ticket_id)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query(
+            r#"INSERT INTO ticket_tags (ticket_id, tag) SELECT $1, UNNEST($2::VARCHAR[]);"#,
+        )
+        .bind(ticket_id)
+        .bind(tags)
+        .execute(&mut *conn)
+        .await?;
+
+        let event = TicketEvent::updated(
+            ticket_id,
+            actor.id,
+            "tags",
+            Some(previous.join(", ")),
+            Some(tags.join(", ")),
+        );
+        TicketEventRepositoryImpl::create_in(conn, &[event]).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::{
+        ticket::RequesterInfo,
+        ticket_event::{TicketEvent, TicketEventPublic},
+    },
+};
+use sqlx::{PgConnection, Row};
+use tracing::debug;
+use uuid::Uuid;
+
+#[async_trait::async_trait]
+pub trait TicketEventRepository {
+    async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<TicketEventPublic>, ApiError>;
+    async fn create(state: &AppState, events: &[TicketEvent]) -> Result<(), ApiError>;
+    async fn create_in(conn: &mut PgConnection, events: &[TicketEvent]) -> Result<(), ApiError>;
+}
+
+pub struct TicketEventRepositoryImpl;
+
+#[async_trait::async_trait]
+impl TicketEventRepository for TicketEventRepositoryImpl {
+    async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<TicketEventPublic>, ApiError> {
+        debug!("Attempting to retrieve history of ticket with ID: {ticket_id}");
+
+        let rows = sqlx::query(
+            r#"
+        SELECT
+            e.id AS event_id,
+            e.ticket_id,
+            e.kind,
+            e.field,
+            e.old_value,
+            e.new_value,
+            e.created_at,
+
+            -- actor
+            u.id AS actor_id,
+            u.username AS actor_username,
+            u.email AS actor_email,
+            u.first_name AS actor_first_name,
+            u.last_name AS actor_last_name
+        FROM ticket_events e
+        LEFT JOIN users u ON u.id = e.actor
+        WHERE e.ticket_id = $1
+        ORDER BY e.created_at ASC, e.id ASC
+        "#,
+        )
+        .bind(ticket_id)
+        .fetch_all(&state.db)
+        .await?;
+
+        let events = rows
+            .iter()
+            .map(|row| TicketEventPublic {
+                id: row.get("event_id"),
+                ticket_id: row.get("ticket_id"),
+                actor: row
+                    .try_get::<Uuid, _>("actor_id")
+                    .ok()
+                    .map(|id| RequesterInfo {
+                        id,
+                        username: row.get("actor_username"),
+                        email: row.get("actor_email"),
+                        first_name: row.get("actor_first_name"),
+                        last_name: row.get("actor_last_name"),
+                    }),
+                kind: row.get("kind"),
+                field: row.get("field"),
+                old_value: row.get("old_value"),
+                new_value: row.get("new_value"),
+                created_at: row.get("created_at"),
+            })
+            .collect();
+
+        Ok(events)
+    }
+
+    async fn create(state: &AppState, events: &[TicketEvent]) -> Result<(), ApiError> {
+        let mut conn = state.db.acquire().await?;
+
+        Self::create_in(&mut conn, events).await
+    }
+
+    /// Records the events on the connection, as part of its current transaction if any.
+    async fn create_in(conn: &mut PgConnection, events: &[TicketEvent]) -> Result<(), ApiError> {
+        for event in events {
+            debug!(
+                "Recording {:?} event on ticket with ID: {}",
+                event.kind, event.ticket_id
+            );
+
+            sqlx::query(r#"INSERT INTO ticket_events (id, ticket_id, actor, kind, field, old_value, new_value, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#)
+            .bind(event.id)
+            .bind(event.ticket_id)
+            .bind(event.actor)
+            .bind(&event.kind)
+            .bind(&event.field)
+            .bind(&event.old_value)
+            .bind(&event.new_value)
+            .bind(event.created_at)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
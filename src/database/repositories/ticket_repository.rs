@@ -1,56 +1,62 @@
+use super::{
+    attachment_repository::{AttachmentRepository, AttachmentRepositoryImpl},
+    comment_repository::{CommentRepository, CommentRepositoryImpl},
+    link_repository::{LinkRepository, LinkRepositoryImpl},
+    tag_repository::{TagRepository, TagRepositoryImpl},
+    ticket_event_repository::{TicketEventRepository, TicketEventRepositoryImpl},
+    watcher_repository::{WatcherRepository, WatcherRepositoryImpl},
+};
 use crate::{
+    config::Config,
     database::AppState,
     errors::api_error::ApiError,
     models::{
+        category::CategoryInfo,
+        pagination::{page_size, Page},
+        precondition::Preconditions,
+        rating::RatingPublic,
         ticket::{
-            CreateTicketPayload, RequesterInfo, Ticket, TicketPublic, TicketStatus,
+            CreateTicketPayload, RequesterInfo, SlaStatus, Ticket, TicketFilters, TicketNumber,
+            TicketPublic, TicketSearchQuery, TicketSearchResult, TicketStatus, TicketVisibility,
             UpdateTicketPayload,
         },
+        ticket_event::TicketEvent,
+        user::User,
         DeletePayload,
     },
 };
-use sqlx::Row;
-use tracing::{debug, info};
+use chrono::NaiveDateTime;
+use sqlx::{
+    postgres::{PgArguments, PgRow},
+    query::Query,
+    Postgres, Row,
+};
+use tracing::{debug, error, info};
 use uuid::Uuid;
 
-#[async_trait::async_trait]
-pub trait TicketRepository {
-    async fn count(state: &AppState) -> Result<i64, ApiError>;
-    async fn find_all(state: &AppState) -> Result<Vec<TicketPublic>, ApiError>;
-    async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<TicketPublic>, ApiError>;
-    async fn create(state: &AppState, payload: &CreateTicketPayload) -> Result<Ticket, ApiError>;
-    async fn update(state: &AppState, payload: &UpdateTicketPayload) -> Result<Uuid, ApiError>;
-    async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError>;
-}
-
-pub struct TicketRepositoryImpl;
-
-#[async_trait::async_trait]
-impl TicketRepository for TicketRepositoryImpl {
-    async fn count(state: &AppState) -> Result<i64, ApiError> {
-        debug!("Attempting to count tickets from the database...");
-
-        let count: i64 = sqlx::query_scalar(r#"SELECT COUNT(*) FROM tickets;"#)
-            .fetch_one(&state.db)
-            .await?;
-
-        Ok(count)
-    }
-
-    async fn find_all(state: &AppState) -> Result<Vec<TicketPublic>, ApiError> {
-        debug!("Attempting to retrieve all tickets...");
-
-        let rows = sqlx::query(
-            r#"
+const TICKET_PUBLIC_QUERY: &str = r#"
         SELECT 
             t.id AS ticket_id,
             t.title,
             t.description,
             t.status::ticket_status,
+            t.priority::ticket_priority,
             t.solution,
             t.created_at AS ticket_created_at,
             t.updated_at AS ticket_updated_at,
             t.closed_at,
+            t.response_due_at,
+            t.due_at,
+            t.responded_at,
+            t.deleted_at,
+            t.merged_into,
+            t.number,
+            ARRAY(SELECT tt.tag FROM ticket_tags tt WHERE tt.ticket_id = t.id ORDER BY tt.tag) AS tags,
+            (SELECT COALESCE(SUM(w.minutes), 0) FROM ticket_worklogs w WHERE w.ticket_id = t.id) AS worked_minutes,
+
+            -- category
+            cat.id AS category_id,
+            cat.name AS category_name,
 
             -- requester
             u.id AS requester_id,
@@ -59,133 +65,482 @@ impl TicketRepository for TicketRepositoryImpl {
             u.first_name AS requester_first_name,
             u.last_name AS requester_last_name,
 
+            -- assignee
+            a.id AS assignee_id,
+            a.username AS assignee_username,
+            a.email AS assignee_email,
+            a.first_name AS assignee_first_name,
+            a.last_name AS assignee_last_name,
+
             -- closed_by
             cb.id AS closed_by_id,
             cb.username AS closed_by_username,
             cb.email AS closed_by_email,
             cb.first_name AS closed_by_first_name,
-            cb.last_name AS closed_by_last_name
+            cb.last_name AS closed_by_last_name,
+
+            -- deleted_by
+            del.id AS deleted_by_id,
+            del.username AS deleted_by_username,
+            del.email AS deleted_by_email,
+            del.first_name AS deleted_by_first_name,
+            del.last_name AS deleted_by_last_name,
+
+            -- rating
+            r.score AS rating_score,
+            r.comment AS rating_comment,
+            r.created_at AS rated_at
         FROM tickets t
         JOIN users u ON u.id = t.requester
+        LEFT JOIN users a ON a.id = t.assignee
         LEFT JOIN users cb ON cb.id = t.closed_by
-        "#,
-        )
+        LEFT JOIN users del ON del.id = t.deleted_by
+        LEFT JOIN categories cat ON cat.id = t.category
+        LEFT JOIN ticket_ratings r ON r.ticket_id = t.id
+        "#;
+
+/// Matches the tickets whose SLA is breached at `$2`, mirroring [`SlaStatus::evaluate`].
+const SLA_BREACHED_CONDITION: &str = r#"COALESCE(
+            COALESCE(t.responded_at, t.closed_at, $2) > t.response_due_at
+            OR COALESCE(t.closed_at, $2) > t.due_at,
+            FALSE
+        )"#;
+
+/// Matches the tickets of the listing filters, bound by [`bind_ticket_filters`] as `$1` to `$9`.
+fn ticket_filter_condition() -> String {
+    format!(
+        "($1::BOOLEAN IS NULL OR {SLA_BREACHED_CONDITION} = $1)
+            AND ($3::UUID IS NULL OR t.category = $3)
+            AND ($4::TEXT IS NULL OR EXISTS (
+                SELECT 1 FROM ticket_tags tt WHERE tt.ticket_id = t.id AND tt.tag = LOWER($4)
+            ))
+            AND ($5::ticket_status IS NULL OR t.status = $5)
+            AND ($6::UUID IS NULL OR t.requester = $6)
+            AND ($7::TIMESTAMP IS NULL OR t.created_at >= $7)
+            AND ($8::TIMESTAMP IS NULL OR t.created_at < $8)
+            AND ($9::TEXT IS NULL OR STRPOS(LOWER(t.title || ' ' || t.description), LOWER($9)) > 0)"
+    )
+}
+
+/// Binds the listing filters of [`ticket_filter_condition`], evaluating the SLA at `now`.
+fn bind_ticket_filters<'q>(
+    query: Query<'q, Postgres, PgArguments>,
+    filters: &'q TicketFilters,
+    now: NaiveDateTime,
+) -> Query<'q, Postgres, PgArguments> {
+    query
+        .bind(filters.breached)
+        .bind(now)
+        .bind(filters.category)
+        .bind(&filters.tag)
+        .bind(&filters.status)
+        .bind(filters.requester)
+        .bind(filters.created_after)
+        .bind(filters.created_before)
+        .bind(&filters.q)
+}
+
+/// Matches the tickets visible to the viewer bound as `$param`, see [`TicketVisibility`].
+/// Tickets in the trash are not visible to anyone.
+pub(super) fn visible_to(param: usize) -> String {
+    format!(
+        "(t.deleted_at IS NULL AND (${param}::UUID IS NULL OR t.requester = ${param} OR EXISTS (
+            SELECT 1 FROM ticket_watchers w WHERE w.ticket_id = t.id AND w.user_id = ${param}
+        )))"
+    )
+}
+
+/// Builds the user info joined under the given column prefix, if any.
+fn user_info_from_row(row: &PgRow, prefix: &str) -> Option<RequesterInfo> {
+    match row.try_get::<Uuid, _>(format!("{prefix}_id").as_str()) {
+        Ok(id) => Some(RequesterInfo {
+            id,
+            username: row.get(format!("{prefix}_username").as_str()),
+            email: row.get(format!("{prefix}_email").as_str()),
+            first_name: row.get(format!("{prefix}_first_name").as_str()),
+            last_name: row.get(format!("{prefix}_last_name").as_str()),
+        }),
+        Err(_) => None,
+    }
+}
+
+/// Builds a [`TicketPublic`] from a row of [`TICKET_PUBLIC_QUERY`], without its comments and attachments.
+fn ticket_public_from_row(row: &PgRow) -> TicketPublic {
+    let created_at = row.get("ticket_created_at");
+    let closed_at = row.get("closed_at");
+    let response_due_at = row.get("response_due_at");
+    let due_at = row.get("due_at");
+    let responded_at = row.get("responded_at");
+
+    TicketPublic {
+        id: row.get("ticket_id"),
+        number: TicketNumber(row.get("number")).to_string(),
+        title: row.get("title"),
+        description: row.get("description"),
+        status: row.get("status"),
+        priority: row.get("priority"),
+        category: row
+            .get::<Option<Uuid>, _>("category_id")
+            .map(|id| CategoryInfo {
+                id,
+                name: row.get("category_name"),
+            }),
+        tags: row.get("tags"),
+        solution: row.get("solution"),
+        created_at,
+        updated_at: row.get("ticket_updated_at"),
+        closed_at,
+        response_due_at,
+        due_at,
+        responded_at,
+        sla_status: SlaStatus::evaluate(
+            created_at,
+            response_due_at,
+            due_at,
+            responded_at,
+            closed_at,
+            chrono::Utc::now().naive_utc(),
+        ),
+        requester: user_info_from_row(row, "requester").expect("Ticket without requester"),
+        assignee: user_info_from_row(row, "assignee"),
+        closed_by: user_info_from_row(row, "closed_by"),
+        merged_into: row.get("merged_into"),
+        deleted_at: row.get("deleted_at"),
+        deleted_by: user_info_from_row(row, "deleted_by"),
+        worked_minutes: row.get("worked_minutes"),
+        rating: row
+            .get::<Option<i16>, _>("rating_score")
+            .map(|score| RatingPublic {
+                score,
+                comment: row.get("rating_comment"),
+                created_at: row.get("rated_at"),
+            }),
+        watchers: Vec::new(),
+        links: Vec::new(),
+        comments: Vec::new(),
+        attachments: Vec::new(),
+    }
+}
+
+/// Attaches the comments and linked tickets visible to the caller, the attachments
+/// and the watchers to each of the tickets.
+async fn attach_details(
+    state: &AppState,
+    tickets: &mut [TicketPublic],
+    visibility: &TicketVisibility,
+) -> Result<(), ApiError> {
+    let ticket_ids: Vec<Uuid> = tickets.iter().map(|ticket| ticket.id).collect();
+    let mut comments =
+        CommentRepositoryImpl::find_by_tickets(state, &ticket_ids, visibility.includes_internal())
+            .await?;
+    let mut attachments = AttachmentRepositoryImpl::find_by_tickets(state, &ticket_ids).await?;
+    let mut watchers = WatcherRepositoryImpl::find_by_tickets(state, &ticket_ids).await?;
+    let mut links =
+        LinkRepositoryImpl::find_by_tickets(state, &ticket_ids, visibility.viewer()).await?;
+
+    for ticket in tickets.iter_mut() {
+        ticket.comments = comments.remove(&ticket.id).unwrap_or_default();
+        ticket.attachments = attachments.remove(&ticket.id).unwrap_or_default();
+        ticket.watchers = watchers.remove(&ticket.id).unwrap_or_default();
+        ticket.links = links.remove(&ticket.id).unwrap_or_default();
+    }
+
+    Ok(())
+}
+
+#[async_trait::async_trait]
+pub trait TicketRepository {
+    async fn count(state: &AppState) -> Result<i64, ApiError>;
+    async fn find_all(
+        state: &AppState,
+        visibility: &TicketVisibility,
+    ) -> Result<Vec<TicketPublic>, ApiError>;
+    async fn find_page(
+        state: &AppState,
+        filters: &TicketFilters,
+        visibility: &TicketVisibility,
+    ) -> Result<Page<TicketPublic>, ApiError>;
+    async fn find_by_id(
+        state: &AppState,
+        id: Uuid,
+        visibility: &TicketVisibility,
+    ) -> Result<Option<TicketPublic>, ApiError>;
+    async fn find_id_by_number(
+        state: &AppState,
+        number: TicketNumber,
+    ) -> Result<Option<Uuid>, ApiError>;
+    async fn search(
+        state: &AppState,
+        query: &TicketSearchQuery,
+        visibility: &TicketVisibility,
+    ) -> Result<Vec<TicketSearchResult>, ApiError>;
+    async fn find_queue(
+        state: &AppState,
+        assignee: Option<Uuid>,
+    ) -> Result<Vec<TicketPublic>, ApiError>;
+    async fn create(
+        state: &AppState,
+        payload: &CreateTicketPayload,
+        actor: &User,
+    ) -> Result<Ticket, ApiError>;
+    async fn update(
+        state: &AppState,
+        payload: &UpdateTicketPayload,
+        actor: &User,
+        preconditions: &Preconditions,
+    ) -> Result<Uuid, ApiError>;
+    async fn assign(
+        state: &AppState,
+        id: Uuid,
+        assignee: Option<Uuid>,
+        actor: &User,
+    ) -> Result<Uuid, ApiError>;
+    async fn merge(
+        state: &AppState,
+        target: Uuid,
+        sources: &[Uuid],
+        actor: &User,
+    ) -> Result<Uuid, ApiError>;
+    async fn mark_responded(state: &AppState, id: Uuid) -> Result<(), ApiError>;
+    async fn delete(
+        state: &AppState,
+        payload: &DeletePayload,
+        actor: &User,
+        preconditions: &Preconditions,
+    ) -> Result<(), ApiError>;
+    async fn find_trash(state: &AppState) -> Result<Vec<TicketPublic>, ApiError>;
+    async fn restore(state: &AppState, id: Uuid, actor: &User) -> Result<Uuid, ApiError>;
+    async fn purge(state: &AppState, id: Uuid, actor: Option<&User>) -> Result<(), ApiError>;
+    async fn purge_expired(
+        state: &AppState,
+        deleted_before: NaiveDateTime,
+    ) -> Result<Vec<Uuid>, ApiError>;
+}
+
+pub struct TicketRepositoryImpl;
+
+#[async_trait::async_trait]
+impl TicketRepository for TicketRepositoryImpl {
+    async fn count(state: &AppState) -> Result<i64, ApiError> {
+        debug!("Attempting to count tickets from the database...");
+
+        let count: i64 =
+            sqlx::query_scalar(r#"SELECT COUNT(*) FROM tickets WHERE deleted_at IS NULL;"#)
+                .fetch_one(&state.db)
+                .await?;
+
+        Ok(count)
+    }
+
+    async fn find_all(
+        state: &AppState,
+        visibility: &TicketVisibility,
+    ) -> Result<Vec<TicketPublic>, ApiError> {
+        debug!("Attempting to retrieve all tickets...");
+
+        let rows = sqlx::query(&format!(
+            "{TICKET_PUBLIC_QUERY} WHERE {} ORDER BY t.id ASC",
+            visible_to(1)
+        ))
+        .bind(visibility.viewer())
         .fetch_all(&state.db)
         .await?;
 
-        let tickets = rows
-            .into_iter()
-            .map(|row| {
-                let closed_by = match row.try_get::<Uuid, _>("closed_by_id") {
-                    Ok(id) => Some(RequesterInfo {
-                        id,
-                        username: row.get("closed_by_username"),
-                        email: row.get("closed_by_email"),
-                        first_name: row.get("closed_by_first_name"),
-                        last_name: row.get("closed_by_last_name"),
-                    }),
-                    Err(_) => None,
-                };
-
-                TicketPublic {
-                    id: row.get("ticket_id"),
-                    title: row.get("title"),
-                    description: row.get("description"),
-                    status: row.get("status"),
-                    solution: row.get("solution"),
-                    created_at: row.get("ticket_created_at"),
-                    updated_at: row.get("ticket_updated_at"),
-                    closed_at: row.get("closed_at"),
-                    requester: RequesterInfo {
-                        id: row.get("requester_id"),
-                        username: row.get("requester_username"),
-                        email: row.get("requester_email"),
-                        first_name: row.get("requester_first_name"),
-                        last_name: row.get("requester_last_name"),
-                    },
-                    closed_by,
-                }
-            })
-            .collect();
+        let mut tickets: Vec<TicketPublic> = rows.iter().map(ticket_public_from_row).collect();
+        attach_details(state, &mut tickets, visibility).await?;
 
         Ok(tickets)
     }
 
-    async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<TicketPublic>, ApiError> {
-        debug!("Attempting to retrieve ticket with id: {id}");
+    async fn find_page(
+        state: &AppState,
+        filters: &TicketFilters,
+        visibility: &TicketVisibility,
+    ) -> Result<Page<TicketPublic>, ApiError> {
+        debug!("Attempting to retrieve a page of tickets...");
 
-        let row = sqlx::query(
-            r#"
-        SELECT 
-            t.id AS ticket_id,
-            t.title,
-            t.description,
-            t.status::ticket_status,
-            t.solution,
-            t.created_at AS ticket_created_at,
-            t.updated_at AS ticket_updated_at,
-            t.closed_at,
+        let now = chrono::Utc::now().naive_utc();
+        let limit = page_size(filters.limit);
+        let column = filters.sort.unwrap_or_default().column();
+        let direction = filters.direction.unwrap_or_default();
 
-            -- requester
-            u.id AS requester_id,
-            u.username AS requester_username,
-            u.email AS requester_email,
-            u.first_name AS requester_first_name,
-            u.last_name AS requester_last_name,
+        let total: i64 = bind_ticket_filters(
+            sqlx::query(&format!(
+                "SELECT COUNT(*) FROM tickets t WHERE {} AND {}",
+                ticket_filter_condition(),
+                visible_to(10)
+            )),
+            filters,
+            now,
+        )
+        .bind(visibility.viewer())
+        .fetch_one(&state.db)
+        .await?
+        .get(0);
 
-            -- closed_by
-            cb.id AS closed_by_id,
-            cb.username AS closed_by_username,
-            cb.email AS closed_by_email,
-            cb.first_name AS closed_by_first_name,
-            cb.last_name AS closed_by_last_name
-        FROM tickets t
-        JOIN users u ON u.id = t.requester
-        LEFT JOIN users cb ON cb.id = t.closed_by
-        WHERE t.id = $1
-        "#,
+        // Keyset pagination on the sort column, with the ticket ID breaking ties.
+        let rows = bind_ticket_filters(
+            sqlx::query(&format!(
+                "{TICKET_PUBLIC_QUERY}
+        WHERE {} AND {}
+            AND ($11::UUID IS NULL OR (t.{column}, t.id) {} (
+                SELECT c.{column}, c.id FROM tickets c WHERE c.id = $11
+            ))
+        ORDER BY t.{column} {}, t.id {}
+        LIMIT $12",
+                ticket_filter_condition(),
+                visible_to(10),
+                direction.after_cursor(),
+                direction.as_sql(),
+                direction.as_sql(),
+            )),
+            filters,
+            now,
         )
+        .bind(visibility.viewer())
+        .bind(filters.cursor)
+        .bind(limit + 1)
+        .fetch_all(&state.db)
+        .await?;
+
+        let mut page = Page::from_rows(
+            rows.iter().map(ticket_public_from_row).collect(),
+            limit,
+            total,
+            |ticket| ticket.id,
+        );
+        attach_details(state, &mut page.items, visibility).await?;
+
+        Ok(page)
+    }
+
+    async fn find_by_id(
+        state: &AppState,
+        id: Uuid,
+        visibility: &TicketVisibility,
+    ) -> Result<Option<TicketPublic>, ApiError> {
+        debug!("Attempting to retrieve ticket with id: {id}");
+
+        let row = sqlx::query(&format!(
+            "{TICKET_PUBLIC_QUERY} WHERE t.id = $1 AND {}",
+            visible_to(2)
+        ))
         .bind(id)
+        .bind(visibility.viewer())
         .fetch_optional(&state.db)
         .await?;
 
         if let Some(row) = row {
-            let closed_by = match row.try_get::<Uuid, _>("closed_by_id") {
-                Ok(id) => Some(RequesterInfo {
-                    id,
-                    username: row.get("closed_by_username"),
-                    email: row.get("closed_by_email"),
-                    first_name: row.get("closed_by_first_name"),
-                    last_name: row.get("closed_by_last_name"),
-                }),
-                Err(_) => None,
-            };
+            let mut ticket = ticket_public_from_row(&row);
+            ticket.comments = CommentRepositoryImpl::find_by_ticket(
+                state,
+                ticket.id,
+                visibility.includes_internal(),
+            )
+            .await?;
+            ticket.attachments = AttachmentRepositoryImpl::find_by_ticket(state, ticket.id).await?;
+            ticket.watchers = WatcherRepositoryImpl::find_by_ticket(state, ticket.id).await?;
+            ticket.links =
+                LinkRepositoryImpl::find_by_ticket(state, ticket.id, visibility.viewer()).await?;
 
-            let ticket = TicketPublic {
-                id: row.get("ticket_id"),
-                title: row.get("title"),
-                description: row.get("description"),
-                status: row.get("status"),
-                solution: row.get("solution"),
-                created_at: row.get("ticket_created_at"),
-                updated_at: row.get("ticket_updated_at"),
-                closed_at: row.get("closed_at"),
-                requester: RequesterInfo {
-                    id: row.get("requester_id"),
-                    username: row.get("requester_username"),
-                    email: row.get("requester_email"),
-                    first_name: row.get("requester_first_name"),
-                    last_name: row.get("requester_last_name"),
-                },
-                closed_by,
-            };
             Ok(Some(ticket))
         } else {
             Ok(None)
         }
     }
 
-    async fn create(state: &AppState, payload: &CreateTicketPayload) -> Result<Ticket, ApiError> {
+    async fn find_id_by_number(
+        state: &AppState,
+        number: TicketNumber,
+    ) -> Result<Option<Uuid>, ApiError> {
+        debug!("Attempting to retrieve the ID of ticket number: {number}");
+
+        let id: Option<Uuid> = sqlx::query_scalar(r#"SELECT id FROM tickets WHERE number = $1;"#)
+            .bind(number.0)
+            .fetch_optional(&state.db)
+            .await?;
+
+        Ok(id)
+    }
+
+    async fn search(
+        state: &AppState,
+        query: &TicketSearchQuery,
+        visibility: &TicketVisibility,
+    ) -> Result<Vec<TicketSearchResult>, ApiError> {
+        debug!("Attempting to search tickets for: {}", query.q);
+
+        // The title weighs more than the description and solution, see the search_vector trigger.
+        // The matches are delimited with the chr(2) and chr(3) control characters, removed from
+        // the text beforehand, so the snippet can be escaped before they become `<mark>` tags.
+        let rows = sqlx::query(&format!(
+            "WITH search AS (SELECT websearch_to_tsquery('english', $1) AS query)
+        SELECT found.*,
+            ts_rank(t.search_vector, search.query) AS rank,
+            ts_headline(
+                'english',
+                translate(t.description || COALESCE(' ' || t.solution, ''), chr(2) || chr(3), ''),
+                search.query,
+                'StartSel=' || chr(2) || ', StopSel=' || chr(3) || ', MaxFragments=2'
+            ) AS snippet
+        FROM ({TICKET_PUBLIC_QUERY}) found
+        JOIN tickets t ON t.id = found.ticket_id
+        CROSS JOIN search
+        WHERE t.search_vector @@ search.query AND {}
+        ORDER BY rank DESC, t.id DESC
+        LIMIT $2",
+            visible_to(3)
+        ))
+        .bind(&query.q)
+        .bind(page_size(query.limit))
+        .bind(visibility.viewer())
+        .fetch_all(&state.db)
+        .await?;
+
+        let mut tickets: Vec<TicketPublic> = rows.iter().map(ticket_public_from_row).collect();
+        attach_details(state, &mut tickets, visibility).await?;
+
+        Ok(tickets
+            .into_iter()
+            .zip(rows.iter())
+            .map(|(ticket, row)| TicketSearchResult {
+                ticket,
+                rank: row.get("rank"),
+                snippet: TicketSearchResult::highlight(row.get("snippet")),
+            })
+            .collect())
+    }
+
+    async fn find_queue(
+        state: &AppState,
+        assignee: Option<Uuid>,
+    ) -> Result<Vec<TicketPublic>, ApiError> {
+        debug!("Attempting to retrieve the ticket queue of assignee: {assignee:?}");
+
+        // Queues only list tickets that still need work, oldest first.
+        let rows = sqlx::query(&format!(
+            "{TICKET_PUBLIC_QUERY}
+        WHERE t.assignee IS NOT DISTINCT FROM $1
+            AND t.status NOT IN ('closed', 'cancelled', 'merged')
+            AND t.deleted_at IS NULL
+        ORDER BY t.created_at ASC"
+        ))
+        .bind(assignee)
+        .fetch_all(&state.db)
+        .await?;
+
+        // Queues are only available to moderators and admins.
+        let mut tickets: Vec<TicketPublic> = rows.iter().map(ticket_public_from_row).collect();
+        attach_details(state, &mut tickets, &TicketVisibility::All).await?;
+
+        Ok(tickets)
+    }
+
+    async fn create(
+        state: &AppState,
+        payload: &CreateTicketPayload,
+        actor: &User,
+    ) -> Result<Ticket, ApiError> {
         debug!("Attempting to create ticket with title: {}", payload.title);
 
         let requester_id: Uuid =
@@ -194,26 +549,52 @@ impl TicketRepository for TicketRepositoryImpl {
                 .fetch_one(&state.db)
                 .await?;
 
-        let new_ticket = Ticket::new(&payload.title, &payload.description, requester_id);
+        let new_ticket = Ticket {
+            category: payload.category,
+            ..Ticket::new(
+                &payload.title,
+                &payload.description,
+                requester_id,
+                payload.priority.clone().unwrap_or_default(),
+            )
+        };
 
-        sqlx::query(r#"INSERT INTO tickets (id, title, description, requester, status, closed_by, solution, created_at, updated_at, closed_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"#)
+        sqlx::query(r#"INSERT INTO tickets (id, title, description, requester, status, priority, category, assignee, closed_by, solution, created_at, updated_at, closed_at, response_due_at, due_at, responded_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)"#)
         .bind(new_ticket.id)
         .bind(&new_ticket.title)
         .bind(&new_ticket.description)
         .bind(new_ticket.requester)
         .bind(&new_ticket.status)
+        .bind(&new_ticket.priority)
+        .bind(new_ticket.category)
+        .bind(new_ticket.assignee)
         .bind(new_ticket.closed_by)
         .bind(&new_ticket.solution)
         .bind(new_ticket.created_at)
         .bind(new_ticket.updated_at)
         .bind(new_ticket.closed_at)
+        .bind(new_ticket.response_due_at)
+        .bind(new_ticket.due_at)
+        .bind(new_ticket.responded_at)
         .execute(&state.db)
         .await?;
 
+        TicketEventRepositoryImpl::create(state, &[TicketEvent::created(new_ticket.id, actor.id)])
+            .await?;
+
+        if let Some(tags) = &payload.tags {
+            TagRepositoryImpl::replace(state, new_ticket.id, tags, actor).await?;
+        }
+
         Ok(new_ticket)
     }
 
-    async fn update(state: &AppState, payload: &UpdateTicketPayload) -> Result<Uuid, ApiError> {
+    async fn update(
+        state: &AppState,
+        payload: &UpdateTicketPayload,
+        actor: &User,
+        preconditions: &Preconditions,
+    ) -> Result<Uuid, ApiError> {
         debug!("Attempting to update ticket with ID: {}", payload.id);
 
         let ticket_id = payload.id;
@@ -221,131 +602,544 @@ impl TicketRepository for TicketRepositoryImpl {
         let new_description = &payload.description;
         let new_requester = payload.requester;
         let new_status = &payload.status;
+        let new_priority = &payload.priority;
+        let new_category = payload.category;
+        let new_tags = &payload.tags;
         let new_closed_by = payload.closed_by;
         let new_solution = &payload.solution;
 
-        let mut updated = false;
+        // The ticket row stays locked until the update commits, so concurrent updates
+        // apply one after the other, each on top of the previous one.
+        let mut tx = state.db.begin().await?;
 
-        // Update `title` if provided.
-        if let Some(title) = new_title {
-            sqlx::query(r#"UPDATE tickets SET title = $1 WHERE id = $2;"#)
-                .bind(title)
-                .bind(ticket_id)
-                .execute(&state.db)
-                .await?;
+        // The current ticket is needed to validate the transition and record the old values.
+        let current: Ticket = sqlx::query_as(
+            r#"SELECT * FROM tickets WHERE id = $1 AND deleted_at IS NULL FOR UPDATE;"#,
+        )
+        .bind(ticket_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::NotFound)?;
 
-            info!("Updated title of ticket with ID: {}", payload.id);
-            updated = true;
-        }
+        // The ticket may have changed since the client retrieved it.
+        preconditions.check(current.updated_at)?;
 
-        // Update `description` if provided.
-        if let Some(description) = new_description {
-            sqlx::query(r#"UPDATE tickets SET description = $1 WHERE id = $2;"#)
-                .bind(description)
-                .bind(ticket_id)
-                .execute(&state.db)
-                .await?;
+        // Validate the status transition before applying any change.
+        let transition = match new_status {
+            Some(status) if status == &current.status => None,
+            Some(status) if !current.status.can_transition_to(status, &actor.role) => {
+                return Err(ApiError::InvalidStatusTransition {
+                    from: current.status.to_string(),
+                    to: status.to_string(),
+                });
+            }
+            Some(status)
+                if status == &TicketStatus::Closed
+                    && new_solution.is_none()
+                    && current.solution.is_none() =>
+            {
+                return Err(ApiError::SolutionRequired);
+            }
+            Some(status) if status == &TicketStatus::Closed => {
+                // A parent is only done once all its children are.
+                let count = LinkRepositoryImpl::count_open_children(&mut tx, ticket_id).await?;
+                if count > 0 {
+                    return Err(ApiError::OpenChildTickets { count });
+                }
 
-            info!("Updated description of ticket with ID: {}", payload.id);
-            updated = true;
-        }
+                Some((&current.status, status))
+            }
+            Some(status) => Some((&current.status, status)),
+            None => None,
+        };
 
-        // Update `requester` if provided
-        if let Some(requester) = new_requester {
-            sqlx::query(r#"UPDATE tickets SET requester = $1 WHERE id = $2;"#)
-                .bind(requester)
-                .bind(ticket_id)
-                .execute(&state.db)
-                .await?;
+        let updated = new_title.is_some()
+            || new_description.is_some()
+            || new_requester.is_some()
+            || transition.is_some()
+            || new_priority.is_some()
+            || new_category.is_some()
+            || new_tags.is_some()
+            || new_solution.is_some();
 
-            info!("Updated requester of ticket with ID: {}", payload.id);
-            updated = true;
+        if !updated {
+            return Err(ApiError::NotModified);
         }
 
-        // Update `status` if provided
-        if let Some(status) = new_status {
-            // Checks previous status value
-            let previous_status: Option<TicketStatus> =
-                sqlx::query_scalar(r#"SELECT status FROM tickets WHERE id = $1"#)
-                    .bind(ticket_id)
-                    .fetch_optional(&state.db)
-                    .await?;
-
-            // Update to new value
-            sqlx::query(r#"UPDATE tickets SET status = $1 WHERE id = $2;"#)
-                .bind(status.clone())
-                .bind(ticket_id)
-                .execute(&state.db)
-                .await?;
+        let now = chrono::Utc::now().naive_utc();
 
-            // Checks if the status has changed to `Closed` or `Cancelled`
-            if status == &TicketStatus::Closed || status == &TicketStatus::Cancelled {
-                if let Some(prev_status) = previous_status {
-                    // If the previous status was not "Closed" or "Cancelled", update the `closed_at` field
-                    if prev_status != TicketStatus::Closed || prev_status != TicketStatus::Cancelled
-                    {
-                        sqlx::query(r#"UPDATE tickets SET closed_at = $1 WHERE id = $2;"#)
-                            .bind(chrono::Utc::now().naive_utc())
-                            .bind(ticket_id)
-                            .execute(&state.db)
-                            .await?;
-                    }
-                }
+        // Move `status` if it has changed
+        let (status, closed_by, closed_at) = match transition {
+            Some((previous_status, status)) => {
+                let (closed_by, closed_at) = if status.is_final() {
+                    // Closing or cancelling records who did it and when.
+                    (Some(new_closed_by.unwrap_or(actor.id)), Some(now))
+                } else if previous_status.is_final() {
+                    // Reopening clears the closing fields.
+                    (None, None)
+                } else {
+                    (current.closed_by, current.closed_at)
+                };
+
+                info!(
+                    "Updated status of ticket with ID {} from {} to {}",
+                    payload.id,
+                    previous_status.to_string(),
+                    status.to_string()
+                );
+
+                (status.clone(), closed_by, closed_at)
             }
+            None => (current.status.clone(), current.closed_by, current.closed_at),
+        };
 
-            info!("Updated status of ticket with ID: {}", payload.id);
-            updated = true;
+        // Move the SLA due dates if `priority` is provided.
+        let (response_due_at, due_at) = match new_priority {
+            Some(priority) => {
+                let sla = Config::sla_targets(priority);
+
+                (
+                    Some(current.created_at + sla.response),
+                    Some(current.created_at + sla.resolution),
+                )
+            }
+            None => (current.response_due_at, current.due_at),
+        };
+
+        let updated = Ticket {
+            title: new_title.clone().unwrap_or_else(|| current.title.clone()),
+            description: new_description
+                .clone()
+                .unwrap_or_else(|| current.description.clone()),
+            requester: new_requester.unwrap_or(current.requester),
+            status,
+            priority: new_priority
+                .clone()
+                .unwrap_or_else(|| current.priority.clone()),
+            category: new_category.unwrap_or(current.category),
+            closed_by,
+            solution: new_solution.clone().or_else(|| current.solution.clone()),
+            updated_at: now,
+            closed_at,
+            response_due_at,
+            due_at,
+            ..current.clone()
+        };
+
+        // Record an event for every field whose value changed.
+        let events = TicketEvent::changes(&current, &updated, actor.id);
+
+        for event in &events {
+            if let Some(field) = &event.field {
+                info!("Updated {field} of ticket with ID: {}", payload.id);
+            }
         }
 
-        // Update `closed_by` if provided.
-        if let Some(closed_by) = new_closed_by {
-            sqlx::query(r#"UPDATE tickets SET closed_by = $1 WHERE id = $2;"#)
-                .bind(closed_by)
-                .bind(ticket_id)
-                .execute(&state.db)
-                .await?;
+        // Apply every change, `updated_at` included, in a single statement.
+        sqlx::query(
+            r#"
+        UPDATE tickets
+        SET
+            title = $1,
+            description = $2,
+            requester = $3,
+            status = $4,
+            closed_by = $5,
+            closed_at = $6,
+            priority = $7,
+            response_due_at = $8,
+            due_at = $9,
+            category = $10,
+            solution = $11,
+            updated_at = $12
+        WHERE id = $13;
+        "#,
+        )
+        .bind(&updated.title)
+        .bind(&updated.description)
+        .bind(updated.requester)
+        .bind(&updated.status)
+        .bind(updated.closed_by)
+        .bind(updated.closed_at)
+        .bind(&updated.priority)
+        .bind(updated.response_due_at)
+        .bind(updated.due_at)
+        .bind(updated.category)
+        .bind(&updated.solution)
+        .bind(updated.updated_at)
+        .bind(ticket_id)
+        .execute(&mut *tx)
+        .await?;
 
-            info!(
-                "Updated `closed_by` field of ticket with ID: {}",
-                payload.id
+        // Replace `tags` if provided.
+        if let Some(tags) = new_tags {
+            TagRepositoryImpl::replace_in(&mut tx, ticket_id, tags, actor).await?;
+
+            info!("Updated tags of ticket with ID: {}", payload.id);
+        }
+
+        TicketEventRepositoryImpl::create_in(&mut tx, &events).await?;
+
+        tx.commit().await?;
+
+        Ok(ticket_id)
+    }
+
+    async fn assign(
+        state: &AppState,
+        id: Uuid,
+        assignee: Option<Uuid>,
+        actor: &User,
+    ) -> Result<Uuid, ApiError> {
+        debug!("Attempting to assign ticket with ID {id} to: {assignee:?}");
+
+        let mut tx = state.db.begin().await?;
+
+        // Locking the ticket keeps concurrent assignments from recording the same previous assignee.
+        let previous: Option<Uuid> = sqlx::query_scalar(
+            r#"SELECT assignee FROM tickets WHERE id = $1 AND deleted_at IS NULL FOR UPDATE;"#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+        sqlx::query(r#"UPDATE tickets SET assignee = $1, updated_at = $2 WHERE id = $3;"#)
+            .bind(assignee)
+            .bind(chrono::Utc::now().naive_utc())
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        if previous != assignee {
+            let event = TicketEvent::updated(
+                id,
+                actor.id,
+                "assignee",
+                previous.map(|id| id.to_string()),
+                assignee.map(|id| id.to_string()),
             );
-            updated = true;
+            TicketEventRepositoryImpl::create_in(&mut tx, &[event]).await?;
         }
 
-        // Update `solution` if provided
-        if let Some(solution) = new_solution {
-            sqlx::query(r#"UPDATE tickets SET solution = $1 WHERE id = $2;"#)
-                .bind(solution)
-                .bind(ticket_id)
-                .execute(&state.db)
-                .await?;
+        tx.commit().await?;
+
+        Ok(id)
+    }
 
-            info!("Updated solution of ticket with ID: {}", payload.id);
-            updated = true;
+    async fn merge(
+        state: &AppState,
+        target: Uuid,
+        sources: &[Uuid],
+        actor: &User,
+    ) -> Result<Uuid, ApiError> {
+        debug!("Attempting to merge tickets {sources:?} into ticket with ID: {target}");
+
+        let mut sources = sources.to_vec();
+        sources.sort();
+        sources.dedup();
+
+        if sources.contains(&target) {
+            return Err(ApiError::InvalidMerge(String::from(
+                "A ticket cannot be merged into itself.",
+            )));
         }
 
-        // Update `updated_at` field.
-        if updated {
-            sqlx::query(r#"UPDATE tickets SET updated_at = $1 WHERE id = $2;"#)
-                .bind(chrono::Utc::now().naive_utc())
-                .bind(ticket_id)
-                .execute(&state.db)
-                .await?;
-        } else {
-            return Err(ApiError::NotModified);
+        // The conversation moves as a whole, so the merge is all or nothing.
+        let mut tx = state.db.begin().await?;
+
+        // Locking the tickets, always in the same order, keeps concurrent updates and merges from racing it.
+        let tickets: Vec<Ticket> = sqlx::query_as(
+            r#"SELECT * FROM tickets WHERE (id = $1 OR id = ANY($2)) AND deleted_at IS NULL ORDER BY id FOR UPDATE;"#,
+        )
+        .bind(target)
+        .bind(&sources)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let find = |id: Uuid| tickets.iter().find(|ticket| ticket.id == id);
+
+        match find(target) {
+            None => return Err(ApiError::NotFound),
+            Some(ticket) if ticket.status == TicketStatus::Merged => {
+                return Err(ApiError::InvalidMerge(String::from(
+                    "The target ticket is itself merged into another ticket.",
+                )));
+            }
+            Some(_) => {}
         }
 
-        Ok(ticket_id)
+        let mut events: Vec<TicketEvent> = Vec::new();
+        for id in &sources {
+            let source = find(*id).ok_or(ApiError::NotFound)?;
+
+            if source.status == TicketStatus::Merged {
+                return Err(ApiError::InvalidMerge(format!(
+                    "Ticket {id} is already merged."
+                )));
+            }
+
+            // Sources already closed or cancelled keep who closed them and when.
+            let merged = Ticket {
+                status: TicketStatus::Merged,
+                closed_by: source.closed_by.or(Some(actor.id)),
+                ..source.clone()
+            };
+
+            events.extend(TicketEvent::changes(source, &merged, actor.id));
+            events.push(TicketEvent::updated(
+                *id,
+                actor.id,
+                "merged_into",
+                None,
+                Some(target.to_string()),
+            ));
+            events.push(TicketEvent::updated(
+                target,
+                actor.id,
+                "merged_from",
+                None,
+                Some(id.to_string()),
+            ));
+        }
+
+        let now = chrono::Utc::now().naive_utc();
+
+        sqlx::query(r#"UPDATE ticket_comments SET ticket_id = $1 WHERE ticket_id = ANY($2);"#)
+            .bind(target)
+            .bind(&sources)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(r#"UPDATE ticket_attachments SET ticket_id = $1 WHERE ticket_id = ANY($2);"#)
+            .bind(target)
+            .bind(&sources)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(r#"UPDATE ticket_worklogs SET ticket_id = $1 WHERE ticket_id = ANY($2);"#)
+            .bind(target)
+            .bind(&sources)
+            .execute(&mut *tx)
+            .await?;
+
+        // The requesters of the duplicates keep following the outage they reported.
+        sqlx::query(
+            r#"
+        INSERT INTO ticket_watchers (ticket_id, user_id, created_at)
+        SELECT $1, candidate.user_id, MIN(candidate.created_at)
+        FROM (
+            SELECT w.user_id, w.created_at FROM ticket_watchers w WHERE w.ticket_id = ANY($2)
+            UNION ALL
+            SELECT t.requester, $3 FROM tickets t WHERE t.id = ANY($2)
+        ) candidate
+        WHERE candidate.user_id <> (SELECT requester FROM tickets WHERE id = $1)
+        GROUP BY candidate.user_id
+        ON CONFLICT DO NOTHING;
+        "#,
+        )
+        .bind(target)
+        .bind(&sources)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(r#"DELETE FROM ticket_watchers WHERE ticket_id = ANY($1);"#)
+            .bind(&sources)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"UPDATE tickets SET status = 'merged', merged_into = $1, closed_by = COALESCE(closed_by, $2), closed_at = COALESCE(closed_at, $3), updated_at = $3 WHERE id = ANY($4);"#,
+        )
+        .bind(target)
+        .bind(actor.id)
+        .bind(now)
+        .bind(&sources)
+        .execute(&mut *tx)
+        .await?;
+
+        // Tickets merged into the sources earlier now point at the survivor.
+        sqlx::query(r#"UPDATE tickets SET merged_into = $1 WHERE merged_into = ANY($2);"#)
+            .bind(target)
+            .bind(&sources)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(r#"UPDATE tickets SET updated_at = $1 WHERE id = $2;"#)
+            .bind(now)
+            .bind(target)
+            .execute(&mut *tx)
+            .await?;
+
+        TicketEventRepositoryImpl::create_in(&mut tx, &events).await?;
+
+        tx.commit().await?;
+
+        Ok(target)
+    }
+
+    async fn mark_responded(state: &AppState, id: Uuid) -> Result<(), ApiError> {
+        debug!("Attempting to record the first response of ticket with ID: {id}");
+
+        // Only the first response counts towards the SLA.
+        sqlx::query(
+            r#"UPDATE tickets SET responded_at = $1 WHERE id = $2 AND responded_at IS NULL;"#,
+        )
+        .bind(chrono::Utc::now().naive_utc())
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+        Ok(())
     }
 
-    async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError> {
-        debug!("Attempting to delete ticket with ID: {}", payload.id);
+    async fn delete(
+        state: &AppState,
+        payload: &DeletePayload,
+        actor: &User,
+        preconditions: &Preconditions,
+    ) -> Result<(), ApiError> {
+        debug!(
+            "Attempting to move ticket with ID {} to the trash",
+            payload.id
+        );
+
+        let mut tx = state.db.begin().await?;
+
+        let updated_at: NaiveDateTime = sqlx::query_scalar(
+            r#"SELECT updated_at FROM tickets WHERE id = $1 AND deleted_at IS NULL FOR UPDATE;"#,
+        )
+        .bind(payload.id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+        // The ticket may have changed since the client retrieved it.
+        preconditions.check(updated_at)?;
 
-        sqlx::query(r#"DELETE FROM tickets WHERE id = $1;"#)
+        sqlx::query(r#"UPDATE tickets SET deleted_at = $1, deleted_by = $2 WHERE id = $3;"#)
+            .bind(chrono::Utc::now().naive_utc())
+            .bind(actor.id)
             .bind(payload.id)
-            .execute(&state.db)
+            .execute(&mut *tx)
+            .await?;
+
+        TicketEventRepositoryImpl::create_in(
+            &mut tx,
+            &[TicketEvent::deleted(payload.id, actor.id)],
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn find_trash(state: &AppState) -> Result<Vec<TicketPublic>, ApiError> {
+        debug!("Attempting to retrieve the tickets in the trash...");
+
+        let rows = sqlx::query(&format!(
+            "{TICKET_PUBLIC_QUERY} WHERE t.deleted_at IS NOT NULL ORDER BY t.deleted_at DESC"
+        ))
+        .fetch_all(&state.db)
+        .await?;
+
+        // The trash is only available to admins.
+        let mut tickets: Vec<TicketPublic> = rows.iter().map(ticket_public_from_row).collect();
+        attach_details(state, &mut tickets, &TicketVisibility::All).await?;
+
+        Ok(tickets)
+    }
+
+    async fn restore(state: &AppState, id: Uuid, actor: &User) -> Result<Uuid, ApiError> {
+        debug!("Attempting to restore ticket with ID {id} from the trash");
+
+        let mut tx = state.db.begin().await?;
+
+        let result = sqlx::query(
+            r#"UPDATE tickets SET deleted_at = NULL, deleted_by = NULL, updated_at = $1 WHERE id = $2 AND deleted_at IS NOT NULL;"#,
+        )
+        .bind(chrono::Utc::now().naive_utc())
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound);
+        }
+
+        TicketEventRepositoryImpl::create_in(&mut tx, &[TicketEvent::restored(id, actor.id)])
             .await?;
 
+        tx.commit().await?;
+
+        Ok(id)
+    }
+
+    async fn purge(state: &AppState, id: Uuid, actor: Option<&User>) -> Result<(), ApiError> {
+        debug!("Attempting to purge ticket with ID {id} from the trash");
+
+        // The purge is recorded along with the deletion, so no ticket disappears without a trace.
+        let mut tx = state.db.begin().await?;
+
+        let storage_keys: Vec<String> = sqlx::query_scalar(
+            r#"SELECT storage_key FROM ticket_attachments WHERE ticket_id = $1;"#,
+        )
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let result =
+            sqlx::query(r#"DELETE FROM tickets WHERE id = $1 AND deleted_at IS NOT NULL;"#)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound);
+        }
+
+        TicketEventRepositoryImpl::create_in(
+            &mut tx,
+            &[TicketEvent::purged(id, actor.map(|actor| actor.id))],
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        // The files are removed once their rows are gone, so a failure cannot leave rows without files,
+        // only unreferenced files.
+        for key in &storage_keys {
+            if let Err(e) = state.storage.remove(key).await {
+                error!("Error removing file {key} of purged ticket with ID {id}: {e}");
+            }
+        }
+
         Ok(())
     }
+
+    async fn purge_expired(
+        state: &AppState,
+        deleted_before: NaiveDateTime,
+    ) -> Result<Vec<Uuid>, ApiError> {
+        debug!("Attempting to purge the tickets trashed before: {deleted_before}");
+
+        let ids: Vec<Uuid> =
+            sqlx::query_scalar(r#"SELECT id FROM tickets WHERE deleted_at < $1 ORDER BY id;"#)
+                .bind(deleted_before)
+                .fetch_all(&state.db)
+                .await?;
+
+        // A ticket failing to be purged is retried on the next sweep, without holding back the others.
+        let mut purged = Vec::with_capacity(ids.len());
+        for id in ids {
+            match Self::purge(state, id, None).await {
+                Ok(_) => purged.push(id),
+                Err(e) => error!("Error purging ticket with ID {id} from the trash: {e}"),
+            }
+        }
+
+        Ok(purged)
+    }
 }
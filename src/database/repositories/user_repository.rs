@@ -2,26 +2,72 @@ use crate::{
     database::AppState,
     errors::api_error::ApiError,
     models::{
-        user::{CreateUserPayload, UpdateUserPayload, User, UserPublic},
+        pagination::{page_size, Page},
+        precondition::Preconditions,
+        user::{CreateUserPayload, UpdateUserPayload, User, UserFilters, UserPublic},
         DeletePayload,
     },
     utils::hashing::encrypt_password,
 };
+use chrono::NaiveDateTime;
+use sqlx::{postgres::PgArguments, query::QueryAs, PgConnection, Postgres};
 use tracing::{debug, info};
 use uuid::Uuid;
 
 #[async_trait::async_trait]
 pub trait UserRepository {
     async fn count(state: &AppState) -> Result<i64, ApiError>;
-    async fn find_all(state: &AppState) -> Result<Vec<UserPublic>, ApiError>;
+    async fn find_all(
+        state: &AppState,
+        filters: &UserFilters,
+    ) -> Result<Page<UserPublic>, ApiError>;
     async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<UserPublic>, ApiError>;
     async fn create(state: &AppState, payload: &CreateUserPayload) -> Result<User, ApiError>;
-    async fn update(state: &AppState, payload: &UpdateUserPayload) -> Result<Uuid, ApiError>;
-    async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError>;
+    async fn update(
+        state: &AppState,
+        payload: &UpdateUserPayload,
+        preconditions: &Preconditions,
+    ) -> Result<Uuid, ApiError>;
+    async fn delete(
+        state: &AppState,
+        payload: &DeletePayload,
+        preconditions: &Preconditions,
+    ) -> Result<(), ApiError>;
 }
 
 pub struct UserRepositoryImpl;
 
+/// Matches the users of the listing filters, bound by `bind_user_filters` as `$1` to `$5`.
+const USER_FILTER_CONDITION: &str = "($1::user_role IS NULL OR u.role = $1)
+            AND ($2::user_status IS NULL OR u.status = $2)
+            AND ($3::TIMESTAMP IS NULL OR u.created_at >= $3)
+            AND ($4::TIMESTAMP IS NULL OR u.created_at < $4)
+            AND ($5::TEXT IS NULL OR STRPOS(LOWER(CONCAT_WS(' ', u.username, u.email, u.first_name, u.last_name)), LOWER($5)) > 0)";
+
+/// Binds the listing filters of [`USER_FILTER_CONDITION`].
+fn bind_user_filters<'q, O>(
+    query: QueryAs<'q, Postgres, O, PgArguments>,
+    filters: &'q UserFilters,
+) -> QueryAs<'q, Postgres, O, PgArguments> {
+    query
+        .bind(&filters.role)
+        .bind(&filters.status)
+        .bind(filters.created_after)
+        .bind(filters.created_before)
+        .bind(&filters.q)
+}
+
+/// Locks the user until the end of the transaction, returning its `updated_at`.
+async fn lock_user(conn: &mut PgConnection, id: Uuid) -> Result<NaiveDateTime, ApiError> {
+    let updated_at: Option<NaiveDateTime> =
+        sqlx::query_scalar(r#"SELECT updated_at FROM users WHERE id = $1 FOR UPDATE;"#)
+            .bind(id)
+            .fetch_optional(conn)
+            .await?;
+
+    updated_at.ok_or(ApiError::NotFound)
+}
+
 #[async_trait::async_trait]
 impl UserRepository for UserRepositoryImpl {
     async fn count(state: &AppState) -> Result<i64, ApiError> {
@@ -34,20 +80,51 @@ impl UserRepository for UserRepositoryImpl {
         Ok(count)
     }
 
-    async fn find_all(state: &AppState) -> Result<Vec<UserPublic>, ApiError> {
-        debug!("Attempting to retrieve all users from the database...");
+    async fn find_all(
+        state: &AppState,
+        filters: &UserFilters,
+    ) -> Result<Page<UserPublic>, ApiError> {
+        debug!("Attempting to retrieve a page of users from the database...");
+
+        let limit = page_size(filters.limit);
+        let column = filters.sort.unwrap_or_default().column();
+        let direction = filters.direction.unwrap_or_default();
+
+        let (total,): (i64,) = bind_user_filters(
+            sqlx::query_as(&format!(
+                "SELECT COUNT(*) FROM users u WHERE {USER_FILTER_CONDITION}"
+            )),
+            filters,
+        )
+        .fetch_one(&state.db)
+        .await?;
 
-        let users: Vec<UserPublic> = sqlx::query_as(
-            r#"
-        SELECT 
-            id, username, email, first_name, last_name, role, status, created_at, updated_at
-        FROM users;
+        // Keyset pagination on the sort column, with the user ID breaking ties.
+        let users: Vec<UserPublic> = bind_user_filters(
+            sqlx::query_as(&format!(
+                r#"
+        SELECT
+            u.id, u.username, u.email, u.first_name, u.last_name, u.role, u.status, u.created_at, u.updated_at
+        FROM users u
+        WHERE {USER_FILTER_CONDITION}
+            AND ($6::UUID IS NULL OR (u.{column}, u.id) {} (
+                SELECT c.{column}, c.id FROM users c WHERE c.id = $6
+            ))
+        ORDER BY u.{column} {}, u.id {}
+        LIMIT $7;
         "#,
+                direction.after_cursor(),
+                direction.as_sql(),
+                direction.as_sql(),
+            )),
+            filters,
         )
+        .bind(filters.cursor)
+        .bind(limit + 1)
         .fetch_all(&state.db)
         .await?;
 
-        Ok(users)
+        Ok(Page::from_rows(users, limit, total, |user| user.id))
     }
 
     async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<UserPublic>, ApiError> {
@@ -101,7 +178,11 @@ impl UserRepository for UserRepositoryImpl {
         Ok(new_user)
     }
 
-    async fn update(state: &AppState, payload: &UpdateUserPayload) -> Result<Uuid, ApiError> {
+    async fn update(
+        state: &AppState,
+        payload: &UpdateUserPayload,
+        preconditions: &Preconditions,
+    ) -> Result<Uuid, ApiError> {
         debug!("Attempting to update user with ID: {}", payload.id);
 
         let user_id = payload.id;
@@ -113,116 +194,83 @@ impl UserRepository for UserRepositoryImpl {
         let new_first_name = &payload.first_name;
         let new_last_name = &payload.last_name;
 
-        let mut updated = false;
-
-        // Update `username` if provided.
-        if let Some(username) = new_username {
-            sqlx::query(r#"UPDATE users SET username = $1 WHERE id = $2;"#)
-                .bind(username)
-                .bind(user_id)
-                .execute(&state.db)
-                .await?;
-
-            info!("Updated username of user with ID: {}", payload.id);
-            updated = true;
-        }
-
-        // Update `email` if provided.
-        if let Some(email) = new_email {
-            sqlx::query(r#"UPDATE users SET email = $1 WHERE id = $2;"#)
-                .bind(email)
-                .bind(user_id)
-                .execute(&state.db)
-                .await?;
-
-            info!("Updated email of user with ID: {}", payload.id);
-            updated = true;
-        }
-
-        // Encrypt and update the `password` if provided
-        if let Some(password) = new_password {
-            let encrypted_password = encrypt_password(&password)?;
-
-            sqlx::query(r#"UPDATE users SET password_hash = $1 WHERE id = $2;"#)
-                .bind(&encrypted_password)
-                .bind(user_id)
-                .execute(&state.db)
-                .await?;
-
-            info!("Updated password of user with ID: {}", payload.id);
-            updated = true;
+        if new_username.is_none()
+            && new_email.is_none()
+            && new_password.is_none()
+            && new_first_name.is_none()
+            && new_last_name.is_none()
+            && new_role.is_none()
+            && new_status.is_none()
+        {
+            return Err(ApiError::NotModified);
         }
 
-        // Update `first_name` if provided
-        if let Some(first_name) = new_first_name {
-            sqlx::query(r#"UPDATE users SET first_name = $1 WHERE id = $2;"#)
-                .bind(first_name)
-                .bind(user_id)
-                .execute(&state.db)
-                .await?;
+        // Encrypt the `password` if provided
+        let encrypted_password = match new_password {
+            Some(password) => Some(encrypt_password(password)?),
+            None => None,
+        };
 
-            info!("Updated first_name of user with ID: {}", payload.id);
-            updated = true;
-        }
+        let mut tx = state.db.begin().await?;
 
-        // Update `last_name` if provided
-        if let Some(last_name) = new_last_name {
-            sqlx::query(r#"UPDATE users SET last_name = $1 WHERE id = $2;"#)
-                .bind(last_name)
-                .bind(user_id)
-                .execute(&state.db)
-                .await?;
-
-            info!("Updated last_name of user with ID: {}", payload.id);
-            updated = true;
-        }
+        // The user may have changed since the client retrieved it.
+        preconditions.check(lock_user(&mut tx, user_id).await?)?;
 
-        // Update `role` if provided
-        if let Some(role) = new_role {
-            sqlx::query(r#"UPDATE users SET role = $1 WHERE id = $2;"#)
-                .bind(role)
-                .bind(user_id)
-                .execute(&state.db)
-                .await?;
-
-            info!("Updated role of user with ID: {}", payload.id);
-            updated = true;
-        }
-
-        // Update `status` if provided
-        if let Some(status) = new_status {
-            sqlx::query(r#"UPDATE users SET status = $1 WHERE id = $2;"#)
-                .bind(status)
-                .bind(user_id)
-                .execute(&state.db)
-                .await?;
+        // Every provided field, `updated_at` included, is applied in a single statement,
+        // so concurrent updates never leave the user half-updated.
+        sqlx::query(
+            r#"
+        UPDATE users
+        SET
+            username = COALESCE($1, username),
+            email = COALESCE($2, email),
+            password_hash = COALESCE($3, password_hash),
+            first_name = COALESCE($4, first_name),
+            last_name = COALESCE($5, last_name),
+            role = COALESCE($6, role),
+            status = COALESCE($7, status),
+            updated_at = $8
+        WHERE id = $9;
+        "#,
+        )
+        .bind(new_username)
+        .bind(new_email)
+        .bind(encrypted_password)
+        .bind(new_first_name)
+        .bind(new_last_name)
+        .bind(new_role)
+        .bind(new_status)
+        .bind(chrono::Utc::now().naive_utc())
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
 
-            info!("Updated status of user with ID: {}", payload.id);
-            updated = true;
-        }
+        tx.commit().await?;
 
-        // Updates `updated_at` field.
-        if updated {
-            sqlx::query(r#"UPDATE users SET updated_at = $1 WHERE id = $2;"#)
-                .bind(chrono::Utc::now().naive_utc())
-                .bind(user_id)
-                .execute(&state.db)
-                .await?;
-        } else {
-            return Err(ApiError::NotModified);
-        }
+        info!("Updated user with ID: {}", payload.id);
 
         Ok(user_id)
     }
 
-    async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError> {
+    async fn delete(
+        state: &AppState,
+        payload: &DeletePayload,
+        preconditions: &Preconditions,
+    ) -> Result<(), ApiError> {
         debug!("Attempting to delete user with ID: {}", payload.id);
 
+        let mut tx = state.db.begin().await?;
+
+        // The user may have changed since the client retrieved it.
+        preconditions.check(lock_user(&mut tx, payload.id).await?)?;
+
         sqlx::query(r#"DELETE FROM users WHERE id = $1;"#)
             .bind(payload.id)
-            .execute(&state.db)
+            .execute(&mut *tx)
             .await?;
 
+        tx.commit().await?;
+
         Ok(())
     }
 }
@@ -0,0 +1,112 @@
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::{ticket::RequesterInfo, watcher::Watcher},
+};
+use sqlx::Row;
+use std::collections::HashMap;
+use tracing::debug;
+use uuid::Uuid;
+
+#[async_trait::async_trait]
+pub trait WatcherRepository {
+    async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<RequesterInfo>, ApiError>;
+    async fn find_by_tickets(
+        state: &AppState,
+        ticket_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<RequesterInfo>>, ApiError>;
+    async fn create(state: &AppState, watcher: &Watcher) -> Result<(), ApiError>;
+    async fn delete(state: &AppState, ticket_id: Uuid, user_id: Uuid) -> Result<(), ApiError>;
+}
+
+pub struct WatcherRepositoryImpl;
+
+#[async_trait::async_trait]
+impl WatcherRepository for WatcherRepositoryImpl {
+    async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<RequesterInfo>, ApiError> {
+        debug!("Attempting to retrieve watchers of ticket with ID: {ticket_id}");
+
+        let mut watchers = Self::find_by_tickets(state, &[ticket_id]).await?;
+
+        Ok(watchers.remove(&ticket_id).unwrap_or_default())
+    }
+
+    async fn find_by_tickets(
+        state: &AppState,
+        ticket_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<RequesterInfo>>, ApiError> {
+        debug!(
+            "Attempting to retrieve watchers of {} tickets",
+            ticket_ids.len()
+        );
+
+        let rows = sqlx::query(
+            r#"
+        SELECT w.ticket_id, u.id, u.username, u.email, u.first_name, u.last_name
+        FROM ticket_watchers w
+        JOIN users u ON u.id = w.user_id
+        WHERE w.ticket_id = ANY($1)
+        ORDER BY w.created_at ASC
+        "#,
+        )
+        .bind(ticket_ids)
+        .fetch_all(&state.db)
+        .await?;
+
+        let mut watchers: HashMap<Uuid, Vec<RequesterInfo>> = HashMap::new();
+        for row in rows {
+            watchers
+                .entry(row.get("ticket_id"))
+                .or_default()
+                .push(RequesterInfo {
+                    id: row.get("id"),
+                    username: row.get("username"),
+                    email: row.get("email"),
+                    first_name: row.get("first_name"),
+                    last_name: row.get("last_name"),
+                });
+        }
+
+        Ok(watchers)
+    }
+
+    async fn create(state: &AppState, watcher: &Watcher) -> Result<(), ApiError> {
+        debug!(
+            "Attempting to subscribe user {} to ticket with ID: {}",
+            watcher.user_id, watcher.ticket_id
+        );
+
+        // Subscribing twice keeps the original subscription.
+        sqlx::query(r#"INSERT INTO ticket_watchers (ticket_id, user_id, created_at) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING;"#)
+            .bind(watcher.ticket_id)
+            .bind(watcher.user_id)
+            .bind(watcher.created_at)
+            .execute(&state.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete(state: &AppState, ticket_id: Uuid, user_id: Uuid) -> Result<(), ApiError> {
+        debug!("Attempting to unsubscribe user {user_id} from ticket with ID: {ticket_id}");
+
+        let result =
+            sqlx::query(r#"DELETE FROM ticket_watchers WHERE ticket_id = $1 AND user_id = $2;"#)
+                .bind(ticket_id)
+                .bind(user_id)
+                .execute(&state.db)
+                .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::{
+        report::ReportQuery,
+        ticket::RequesterInfo,
+        worklog::{UpdateWorklogPayload, Worklog, WorklogPublic, WorklogReportEntry},
+        DeletePayload,
+    },
+};
+use sqlx::{postgres::PgRow, Row};
+use tracing::debug;
+use uuid::Uuid;
+
+#[async_trait::async_trait]
+pub trait WorklogRepository {
+    async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<WorklogPublic>, ApiError>;
+    async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Worklog>, ApiError>;
+    async fn create(state: &AppState, worklog: &Worklog) -> Result<(), ApiError>;
+    async fn update(state: &AppState, payload: &UpdateWorklogPayload) -> Result<Uuid, ApiError>;
+    async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError>;
+    async fn report(
+        state: &AppState,
+        query: &ReportQuery,
+    ) -> Result<Vec<WorklogReportEntry>, ApiError>;
+}
+
+pub struct WorklogRepositoryImpl;
+
+fn agent_from_row(row: &PgRow) -> RequesterInfo {
+    RequesterInfo {
+        id: row.get("agent_id"),
+        username: row.get("agent_username"),
+        email: row.get("agent_email"),
+        first_name: row.get("agent_first_name"),
+        last_name: row.get("agent_last_name"),
+    }
+}
+
+#[async_trait::async_trait]
+impl WorklogRepository for WorklogRepositoryImpl {
+    async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<WorklogPublic>, ApiError> {
+        debug!("Attempting to retrieve worklogs of ticket with ID: {ticket_id}");
+
+        let rows = sqlx::query(
+            r#"
+        SELECT
+            w.id,
+            w.ticket_id,
+            w.minutes,
+            w.note,
+            w.work_date,
+            w.created_at,
+            w.updated_at,
+
+            -- agent
+            u.id AS agent_id,
+            u.username AS agent_username,
+            u.email AS agent_email,
+            u.first_name AS agent_first_name,
+            u.last_name AS agent_last_name
+        FROM ticket_worklogs w
+        JOIN users u ON u.id = w.agent
+        WHERE w.ticket_id = $1
+        ORDER BY w.work_date ASC, w.created_at ASC
+        "#,
+        )
+        .bind(ticket_id)
+        .fetch_all(&state.db)
+        .await?;
+
+        let worklogs = rows
+            .iter()
+            .map(|row| WorklogPublic {
+                id: row.get("id"),
+                ticket_id: row.get("ticket_id"),
+                agent: agent_from_row(row),
+                minutes: row.get("minutes"),
+                note: row.get("note"),
+                work_date: row.get("work_date"),
+                created_at: row.get("created_at"),
+                updated_at: row.get("updated_at"),
+            })
+            .collect();
+
+        Ok(worklogs)
+    }
+
+    async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Worklog>, ApiError> {
+        debug!("Attempting to retrieve worklog with id: {id}");
+
+        let worklog: Option<Worklog> =
+            sqlx::query_as(r#"SELECT * FROM ticket_worklogs WHERE id = $1;"#)
+                .bind(id)
+                .fetch_optional(&state.db)
+                .await?;
+
+        Ok(worklog)
+    }
+
+    async fn create(state: &AppState, worklog: &Worklog) -> Result<(), ApiError> {
+        debug!(
+            "Attempting to log {} minutes on ticket with ID: {}",
+            worklog.minutes, worklog.ticket_id
+        );
+
+        sqlx::query(r#"INSERT INTO ticket_worklogs (id, ticket_id, agent, minutes, note, work_date, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#)
+        .bind(worklog.id)
+        .bind(worklog.ticket_id)
+        .bind(worklog.agent)
+        .bind(worklog.minutes)
+        .bind(&worklog.note)
+        .bind(worklog.work_date)
+        .bind(worklog.created_at)
+        .bind(worklog.updated_at)
+        .execute(&state.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update(state: &AppState, payload: &UpdateWorklogPayload) -> Result<Uuid, ApiError> {
+        debug!("Attempting to update worklog with ID: {}", payload.id);
+
+        let result = sqlx::query(
+            r#"
+        UPDATE ticket_worklogs
+        SET
+            minutes = COALESCE($1, minutes),
+            note = COALESCE($2, note),
+            work_date = COALESCE($3, work_date),
+            updated_at = $4
+        WHERE id = $5;
+        "#,
+        )
+        .bind(payload.minutes)
+        .bind(&payload.note)
+        .bind(payload.work_date)
+        .bind(chrono::Utc::now().naive_utc())
+        .bind(payload.id)
+        .execute(&state.db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotModified);
+        }
+
+        Ok(payload.id)
+    }
+
+    async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError> {
+        debug!("Attempting to delete worklog with ID: {}", payload.id);
+
+        sqlx::query(r#"DELETE FROM ticket_worklogs WHERE id = $1;"#)
+            .bind(payload.id)
+            .execute(&state.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn report(
+        state: &AppState,
+        query: &ReportQuery,
+    ) -> Result<Vec<WorklogReportEntry>, ApiError> {
+        let period = query.period.unwrap_or_default();
+
+        debug!(
+            "Attempting to report worklogs per agent and {}",
+            period.as_sql()
+        );
+
+        // The time logged on tickets in the trash is still billed.
+        let rows = sqlx::query(
+            r#"
+        SELECT
+            date_trunc($1::TEXT, w.work_date::TIMESTAMP)::DATE AS period,
+            SUM(w.minutes) AS minutes,
+            COUNT(DISTINCT w.ticket_id) AS tickets,
+            COUNT(*) AS entries,
+
+            -- agent
+            u.id AS agent_id,
+            u.username AS agent_username,
+            u.email AS agent_email,
+            u.first_name AS agent_first_name,
+            u.last_name AS agent_last_name
+        FROM ticket_worklogs w
+        JOIN users u ON u.id = w.agent
+        WHERE ($2::DATE IS NULL OR w.work_date >= $2)
+            AND ($3::DATE IS NULL OR w.work_date <= $3)
+            AND ($4::UUID IS NULL OR w.agent = $4)
+        GROUP BY period, u.id
+        ORDER BY period ASC, u.username ASC
+        "#,
+        )
+        .bind(period.as_sql())
+        .bind(query.from)
+        .bind(query.to)
+        .bind(query.agent)
+        .fetch_all(&state.db)
+        .await?;
+
+        let report = rows
+            .iter()
+            .map(|row| WorklogReportEntry {
+                agent: agent_from_row(row),
+                period: row.get("period"),
+                minutes: row.get("minutes"),
+                tickets: row.get("tickets"),
+                entries: row.get("entries"),
+            })
+            .collect();
+
+        Ok(report)
+    }
+}
@@ -2,6 +2,7 @@ use super::{
     auth_error,
     config_error::{self, ConfigError},
     export_error::{self, ExportError},
+    oidc_error, storage_error,
 };
 use axum::{
     http::StatusCode,
@@ -36,6 +37,15 @@ pub enum ApiError {
     #[error("One or more config errors occurred: {0}")]
     ConfigError(#[from] config_error::ConfigError),
 
+    #[error("One or more storage errors occurred: {0}")]
+    StorageError(#[from] storage_error::StorageError),
+
+    #[error("One or more single sign-on errors occurred: {0}")]
+    OidcError(#[from] oidc_error::OidcError),
+
+    #[error("The multipart request could not be read: {0}")]
+    MultipartError(#[from] axum::extract::multipart::MultipartError),
+
     #[error("The provided data does not correspond to any existing resource.")]
     NotFound,
 
@@ -45,11 +55,53 @@ pub enum ApiError {
     #[error("No updates were made for the provided ID.")]
     NotModified,
 
+    #[error("The resource was modified since it was retrieved.")]
+    PreconditionFailed,
+
     #[error("You are not allowed to continue.")]
     Unauthorized,
 
     #[error("Incorrect password! Try again.")]
     WrongPassword,
+
+    #[error("The provided user cannot be assigned to tickets.")]
+    InvalidAssignee,
+
+    #[error("A ticket cannot move from {from} to {to}.")]
+    InvalidStatusTransition { from: String, to: String },
+
+    #[error("A solution is required to close a ticket.")]
+    SolutionRequired,
+
+    #[error("No file was provided.")]
+    AttachmentRequired,
+
+    #[error("The filename must be between 1 and 255 chars.")]
+    InvalidAttachmentName,
+
+    #[error("The file exceeds the maximum size of {max_size} bytes.")]
+    AttachmentTooLarge { max_size: usize },
+
+    #[error("The file type {0} is not allowed.")]
+    UnsupportedMediaType(String),
+
+    #[error("Invalid tags: {0}")]
+    InvalidTags(String),
+
+    #[error("Invalid merge: {0}")]
+    InvalidMerge(String),
+
+    #[error("Invalid link: {0}")]
+    InvalidLink(String),
+
+    #[error("A ticket cannot be closed while it has {count} open child tickets.")]
+    OpenChildTickets { count: i64 },
+
+    #[error("Only closed tickets can be rated.")]
+    TicketNotClosed,
+
+    #[error("The ticket was already rated.")]
+    AlreadyRated,
 }
 
 #[derive(serde::Serialize)]
@@ -111,7 +163,7 @@ impl IntoResponse for ApiError {
                 },
             ),
             ApiError::AuthError(e) => (
-                StatusCode::UNAUTHORIZED,
+                e.status(),
                 ErrorResponse {
                     code: String::from("AUTH_ERROR"),
                     message: String::from("One or more auth errors occurred."),
@@ -126,6 +178,30 @@ impl IntoResponse for ApiError {
                     details: Some(e.to_string()),
                 },
             ),
+            ApiError::StorageError(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorResponse {
+                    code: String::from("STORAGE_ERROR"),
+                    message: String::from("One or more storage errors occurred."),
+                    details: Some(e.to_string()),
+                },
+            ),
+            ApiError::OidcError(e) => (
+                e.status(),
+                ErrorResponse {
+                    code: String::from("OIDC_ERROR"),
+                    message: String::from("One or more single sign-on errors occurred."),
+                    details: Some(e.to_string()),
+                },
+            ),
+            ApiError::MultipartError(e) => (
+                e.status(),
+                ErrorResponse {
+                    code: String::from("MULTIPART_ERROR"),
+                    message: String::from("The multipart request could not be read."),
+                    details: Some(e.body_text()),
+                },
+            ),
             ApiError::NotFound => (
                 StatusCode::NOT_FOUND,
                 ErrorResponse {
@@ -166,6 +242,16 @@ impl IntoResponse for ApiError {
                     )),
                 },
             ),
+            ApiError::PreconditionFailed => (
+                StatusCode::PRECONDITION_FAILED,
+                ErrorResponse {
+                    code: String::from("PRECONDITION_FAILED"),
+                    message: String::from("The resource was modified since it was retrieved."),
+                    details: Some(String::from(
+                        "Please retrieve the resource again, reapply your changes and send its new ETag in If-Match.",
+                    )),
+                },
+            ),
             ApiError::AlreadyExists => (
                 StatusCode::CONFLICT,
                 ErrorResponse {
@@ -174,6 +260,124 @@ impl IntoResponse for ApiError {
                     details: Some(String::from("Please choose a different name.")),
                 },
             ),
+            ApiError::InvalidAssignee => (
+                StatusCode::BAD_REQUEST,
+                ErrorResponse {
+                    code: String::from("INVALID_ASSIGNEE"),
+                    message: String::from("The provided user cannot be assigned to tickets."),
+                    details: Some(String::from(
+                        "Only active moderators and admins can be assigned to tickets.",
+                    )),
+                },
+            ),
+            ApiError::InvalidStatusTransition { from, to } => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ErrorResponse {
+                    code: String::from("INVALID_STATUS_TRANSITION"),
+                    message: format!("A ticket cannot move from {from} to {to}."),
+                    details: Some(String::from(
+                        "Check the allowed transitions of the ticket and try again.",
+                    )),
+                },
+            ),
+            ApiError::SolutionRequired => (
+                StatusCode::BAD_REQUEST,
+                ErrorResponse {
+                    code: String::from("SOLUTION_REQUIRED"),
+                    message: String::from("A solution is required to close a ticket."),
+                    details: Some(String::from(
+                        "Please provide the solution of the ticket and try again.",
+                    )),
+                },
+            ),
+            ApiError::AttachmentRequired => (
+                StatusCode::BAD_REQUEST,
+                ErrorResponse {
+                    code: String::from("ATTACHMENT_REQUIRED"),
+                    message: String::from("No file was provided."),
+                    details: Some(String::from(
+                        "Please send the file in the `file` field of a multipart form.",
+                    )),
+                },
+            ),
+            ApiError::InvalidAttachmentName => (
+                StatusCode::BAD_REQUEST,
+                ErrorResponse {
+                    code: String::from("INVALID_ATTACHMENT_NAME"),
+                    message: String::from("The filename must be between 1 and 255 chars."),
+                    details: Some(String::from("Please rename the file and try again.")),
+                },
+            ),
+            ApiError::AttachmentTooLarge { max_size } => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                ErrorResponse {
+                    code: String::from("ATTACHMENT_TOO_LARGE"),
+                    message: format!("The file exceeds the maximum size of {max_size} bytes."),
+                    details: Some(String::from("Please send a smaller file.")),
+                },
+            ),
+            ApiError::UnsupportedMediaType(content_type) => (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                ErrorResponse {
+                    code: String::from("UNSUPPORTED_MEDIA_TYPE"),
+                    message: format!("The file type {content_type} is not allowed."),
+                    details: Some(String::from("Please send a file of an allowed type.")),
+                },
+            ),
+            ApiError::InvalidTags(reason) => (
+                StatusCode::BAD_REQUEST,
+                ErrorResponse {
+                    code: String::from("INVALID_TAGS"),
+                    message: String::from("One or more tags are invalid."),
+                    details: Some(reason.clone()),
+                },
+            ),
+            ApiError::InvalidMerge(reason) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ErrorResponse {
+                    code: String::from("INVALID_MERGE"),
+                    message: String::from("The tickets cannot be merged."),
+                    details: Some(reason.clone()),
+                },
+            ),
+            ApiError::InvalidLink(reason) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ErrorResponse {
+                    code: String::from("INVALID_LINK"),
+                    message: String::from("The tickets cannot be linked."),
+                    details: Some(reason.clone()),
+                },
+            ),
+            ApiError::OpenChildTickets { count } => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ErrorResponse {
+                    code: String::from("OPEN_CHILD_TICKETS"),
+                    message: format!(
+                        "A ticket cannot be closed while it has {count} open child tickets."
+                    ),
+                    details: Some(String::from(
+                        "Please close or cancel the child tickets and try again.",
+                    )),
+                },
+            ),
+            ApiError::TicketNotClosed => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ErrorResponse {
+                    code: String::from("TICKET_NOT_CLOSED"),
+                    message: String::from("Only closed tickets can be rated."),
+                    details: Some(String::from(
+                        "Please wait for the ticket to be closed and try again.",
+                    )),
+                },
+            ),
+            ApiError::AlreadyRated => (
+                StatusCode::CONFLICT,
+                ErrorResponse {
+                    code: String::from("ALREADY_RATED"),
+                    message: String::from("The ticket was already rated."),
+                    details: Some(String::from("A ticket can only be rated once.")),
+                },
+            ),
         };
 
         (status_code, Json(error_response)).into_response()
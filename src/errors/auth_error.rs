@@ -1,3 +1,5 @@
+use axum::http::StatusCode;
+
 #[derive(Debug, thiserror::Error)]
 pub enum AuthError {
     #[error("Authorization token is missing in the request. Please provide a valid JWT token.")]
@@ -6,4 +8,26 @@ pub enum AuthError {
     EmptyHeader,
     #[error("Invalid JWT token. Please provide a valid token.")]
     InvalidToken,
+    #[error("The JWT token was revoked. Please log in again.")]
+    RevokedToken,
+    #[error("Invalid or expired refresh token. Please log in again.")]
+    InvalidRefreshToken,
+    #[error("The refresh token was already used, so every session started from the same login was revoked. Please log in again.")]
+    RefreshTokenReused,
+    #[error("Invalid or expired API token. Please provide a valid token.")]
+    InvalidApiToken,
+    #[error("The API token does not have the {0} scope required by this request.")]
+    MissingScope(String),
+    #[error("API tokens cannot be used for this request. Please log in instead.")]
+    ApiTokenNotAllowed,
+}
+
+impl AuthError {
+    /// Valid API tokens used outside of their scopes are forbidden rather than unauthenticated.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AuthError::MissingScope(_) | AuthError::ApiTokenNotAllowed => StatusCode::FORBIDDEN,
+            _ => StatusCode::UNAUTHORIZED,
+        }
+    }
 }
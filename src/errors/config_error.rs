@@ -8,4 +8,10 @@ pub enum ConfigError {
 
     #[error("Error parsing data: {0}")]
     ParsingError(#[from] std::io::Error),
+
+    #[error("No JWT signing key is configured. Please set JWT_SIGNING_KEY_FILE or JWT_SECRET.")]
+    MissingJwtKey,
+
+    #[error("Invalid JWT key {kid}: {reason}")]
+    InvalidJwtKey { kid: String, reason: String },
 }
@@ -5,4 +5,7 @@ pub enum ExportError {
 
     #[error("Failed to generate CSV: {0}")]
     CSVError(#[from] csv::Error),
+
+    #[error("Invalid file name: {0}")]
+    InvalidFileName(#[from] axum::http::header::InvalidHeaderValue),
 }
@@ -2,3 +2,5 @@ pub mod api_error;
 pub mod auth_error;
 pub mod config_error;
 pub mod export_error;
+pub mod oidc_error;
+pub mod storage_error;
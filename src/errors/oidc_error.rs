@@ -0,0 +1,27 @@
+use axum::http::StatusCode;
+
+#[derive(Debug, thiserror::Error)]
+pub enum OidcError {
+    #[error("Single sign-on is not configured.")]
+    NotConfigured,
+    #[error("The identity provider could not be reached: {0}")]
+    ProviderUnavailable(String),
+    #[error("The identity provider denied the login: {0}")]
+    Denied(String),
+    #[error("The login expired or was already completed. Please log in again.")]
+    InvalidState,
+    #[error("Invalid ID token: {0}")]
+    InvalidIdToken(String),
+}
+
+impl OidcError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            OidcError::NotConfigured => StatusCode::NOT_FOUND,
+            OidcError::ProviderUnavailable(_) => StatusCode::BAD_GATEWAY,
+            OidcError::Denied(_) | OidcError::InvalidState | OidcError::InvalidIdToken(_) => {
+                StatusCode::UNAUTHORIZED
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("Failed to access the stored file: {0}")]
+    IoError(#[from] std::io::Error),
+}
@@ -6,8 +6,10 @@ pub async fn create_tickets_csv(tickets: Vec<TicketView>) -> Result<Vec<u8>, Api
 
     wtr.write_record(&[
         "Ticket",
+        "ID",
         "Updated at",
         "Requester",
+        "Assignee",
         "Created at",
         "Status",
         "Title",
@@ -15,13 +17,17 @@ pub async fn create_tickets_csv(tickets: Vec<TicketView>) -> Result<Vec<u8>, Api
         "Closed by",
         "Closed at",
         "Solution",
+        "Time spent (minutes)",
+        "Comments",
     ])?;
 
     for ticket in tickets {
         wtr.write_record(&[
+            ticket.number,
             ticket.id,
             ticket.updated_at,
             ticket.requester,
+            ticket.assignee,
             ticket.created_at,
             ticket.status,
             ticket.title,
@@ -29,6 +35,8 @@ pub async fn create_tickets_csv(tickets: Vec<TicketView>) -> Result<Vec<u8>, Api
             ticket.closed_by,
             ticket.closed_at,
             ticket.solution,
+            ticket.worked_minutes,
+            ticket.comments.join("\n"),
         ])?;
     }
 
@@ -28,7 +28,7 @@ pub async fn create_ticket_pdf(ticket: TicketView) -> Result<Vec<u8>, ApiError>
         },
     });
 
-    let content = Content {
+    let mut content = Content {
         operations: vec![
             // Updated_at label
             Operation::new("BT", vec![]),
@@ -52,7 +52,7 @@ pub async fn create_ticket_pdf(ticket: TicketView) -> Result<Vec<u8>, ApiError>
             Operation::new("BT", vec![]),
             Operation::new("Tf", vec!["F2".into(), 17.into()]),
             Operation::new("Td", vec![100.into(), 785.into()]),
-            Operation::new("Tj", vec![Object::string_literal(ticket.id)]),
+            Operation::new("Tj", vec![Object::string_literal(ticket.number)]),
             Operation::new("ET", vec![]),
             // Requester label
             Operation::new("BT", vec![]),
@@ -78,6 +78,18 @@ pub async fn create_ticket_pdf(ticket: TicketView) -> Result<Vec<u8>, ApiError>
             Operation::new("Td", vec![120.into(), 700.into()]),
             Operation::new("Tj", vec![Object::string_literal(ticket.created_at)]),
             Operation::new("ET", vec![]),
+            // Assignee label
+            Operation::new("BT", vec![]),
+            Operation::new("Tf", vec!["F2".into(), 12.into()]),
+            Operation::new("Td", vec![375.into(), 700.into()]),
+            Operation::new("Tj", vec![Object::string_literal("Assignee:")]),
+            Operation::new("ET", vec![]),
+            // Assignee value
+            Operation::new("BT", vec![]),
+            Operation::new("Tf", vec!["F1".into(), 12.into()]),
+            Operation::new("Td", vec![440.into(), 700.into()]),
+            Operation::new("Tj", vec![Object::string_literal(ticket.assignee)]),
+            Operation::new("ET", vec![]),
             // Status label
             Operation::new("BT", vec![]),
             Operation::new("Tf", vec!["F2".into(), 12.into()]),
@@ -150,9 +162,28 @@ pub async fn create_ticket_pdf(ticket: TicketView) -> Result<Vec<u8>, ApiError>
             Operation::new("Td", vec![110.into(), 540.into()]),
             Operation::new("Tj", vec![Object::string_literal(ticket.solution)]),
             Operation::new("ET", vec![]),
+            // Comments label
+            Operation::new("BT", vec![]),
+            Operation::new("Tf", vec!["F2".into(), 12.into()]),
+            Operation::new("Td", vec![50.into(), 500.into()]),
+            Operation::new("Tj", vec![Object::string_literal("Comments:")]),
+            Operation::new("ET", vec![]),
         ],
     };
 
+    // Comments values, one per line until the end of the page
+    for (index, comment) in ticket.comments.into_iter().take(30).enumerate() {
+        let y = 485 - (index as i64 * 15);
+
+        content.operations.extend([
+            Operation::new("BT", vec![]),
+            Operation::new("Tf", vec!["F1".into(), 10.into()]),
+            Operation::new("Td", vec![50.into(), y.into()]),
+            Operation::new("Tj", vec![Object::string_literal(comment)]),
+            Operation::new("ET", vec![]),
+        ]);
+    }
+
     let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode()?));
 
     let page_id = doc.add_object(dictionary! {
@@ -8,5 +8,7 @@ pub mod models;
 mod openapi;
 mod routes;
 pub mod server;
+pub mod storage;
+mod tasks;
 mod utils;
 pub mod validations;
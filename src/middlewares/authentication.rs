@@ -1,22 +1,38 @@
 use crate::{
     database::AppState,
     errors::{api_error::ApiError, auth_error::AuthError},
-    models::user::User,
+    models::{
+        auth::api_token::{ApiToken, ApiTokenScope, API_TOKEN_PREFIX},
+        user::{Status, User},
+    },
     utils::jwt::decode_jwt,
 };
 use axum::{
     body::Body,
-    extract::{Request, State},
+    extract::{OriginalUri, Request, State},
     http::{self, Response},
     middleware::Next,
 };
 use std::sync::Arc;
+use uuid::Uuid;
+
+/// Header carrying an API token, as an alternative to a bearer token.
+const API_KEY_HEADER: &str = "x-api-key";
 
 pub async fn authenticate(
     State(state): State<Arc<AppState>>,
     mut req: Request,
     next: Next,
 ) -> Result<Response<Body>, ApiError> {
+    if let Some(api_key) = req.headers().get(API_KEY_HEADER) {
+        let api_key = api_key
+            .to_str()
+            .map_err(|_| ApiError::from(AuthError::EmptyHeader))?
+            .to_string();
+
+        return authenticate_api_token(&state, req, next, &api_key).await;
+    }
+
     let auth_header = req.headers_mut().get(http::header::AUTHORIZATION);
 
     let auth_header = match auth_header {
@@ -30,16 +46,73 @@ pub async fn authenticate(
 
     let (_bearer, token) = (header.next(), header.next());
 
-    let token_data = match decode_jwt(token.expect("Error reading token").to_string()) {
+    let token = token.expect("Error reading token").to_string();
+
+    if token.starts_with(API_TOKEN_PREFIX) {
+        return authenticate_api_token(&state, req, next, &token).await;
+    }
+
+    let token_data = match decode_jwt(&state.jwt_keys, token) {
         Ok(data) => data,
         Err(_) => return Err(ApiError::from(AuthError::InvalidToken)),
     };
 
+    let jti = match Uuid::parse_str(&token_data.claims.jti) {
+        Ok(jti) => jti,
+        Err(_) => return Err(ApiError::from(AuthError::InvalidToken)),
+    };
+
     let current_user: User = sqlx::query_as(r#"SELECT * FROM users WHERE username = $1;"#)
         .bind(&token_data.claims.sub)
         .fetch_one(&state.db)
         .await?;
 
+    if state
+        .revocations
+        .is_revoked(jti, current_user.id, token_data.claims.iat)
+    {
+        return Err(ApiError::from(AuthError::RevokedToken));
+    }
+
+    req.extensions_mut().insert(current_user);
+    req.extensions_mut().insert(token_data.claims);
+    Ok(next.run(req).await)
+}
+
+/// Authenticates a request made with an API token, ensuring the token has the scope it requires.
+async fn authenticate_api_token(
+    state: &AppState,
+    mut req: Request,
+    next: Next,
+    token: &str,
+) -> Result<Response<Body>, ApiError> {
+    let api_token = match ApiToken::authenticate(state, token).await? {
+        Some(api_token) => api_token,
+        None => return Err(ApiError::from(AuthError::InvalidApiToken)),
+    };
+
+    // Nested routers only see the end of the path in the URI of the request.
+    let path = match req.extensions().get::<OriginalUri>() {
+        Some(uri) => uri.path().to_string(),
+        None => req.uri().path().to_string(),
+    };
+
+    match ApiTokenScope::required_for(req.method(), &path) {
+        Some(scope) if api_token.allows(scope) => {}
+        Some(scope) => return Err(ApiError::from(AuthError::MissingScope(scope.to_string()))),
+        None => return Err(ApiError::from(AuthError::ApiTokenNotAllowed)),
+    }
+
+    let current_user: User = sqlx::query_as(r#"SELECT * FROM users WHERE id = $1;"#)
+        .bind(api_token.user_id)
+        .fetch_one(&state.db)
+        .await?;
+
+    // Inactive users keep their tokens, which work again once they are reactivated.
+    if current_user.status != Status::Active {
+        return Err(ApiError::Unauthorized);
+    }
+
     req.extensions_mut().insert(current_user);
     Ok(next.run(req).await)
 }
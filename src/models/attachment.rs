@@ -0,0 +1,89 @@
+use super::ticket::RequesterInfo;
+use crate::{
+    database::{
+        repositories::attachment_repository::{AttachmentRepository, AttachmentRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(ToSchema, FromRow, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub ticket_id: Uuid,
+    pub uploaded_by: Uuid,
+    pub filename: String,
+    pub content_type: String,
+    pub size: i64,
+    pub storage_key: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(ToSchema, Clone, Serialize, Deserialize)]
+pub struct AttachmentPublic {
+    pub id: Uuid,
+    pub ticket_id: Uuid,
+    pub uploaded_by: RequesterInfo,
+    pub filename: String,
+    pub content_type: String,
+    pub size: i64,
+    pub created_at: NaiveDateTime,
+}
+
+/// Multipart form of an attachment upload, used by the API docs.
+#[derive(ToSchema)]
+pub struct UploadAttachmentForm {
+    #[schema(format = Binary, content_media_type = "application/octet-stream")]
+    pub file: String,
+}
+
+impl Attachment {
+    pub fn new(
+        ticket_id: Uuid,
+        uploaded_by: Uuid,
+        filename: &str,
+        content_type: &str,
+        size: i64,
+    ) -> Self {
+        let id = Uuid::now_v7();
+
+        Self {
+            id,
+            ticket_id,
+            uploaded_by,
+            filename: filename.to_string(),
+            content_type: content_type.to_string(),
+            size,
+            storage_key: format!("tickets/{ticket_id}/{id}"),
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<AttachmentPublic>, ApiError> {
+        AttachmentRepositoryImpl::find_by_ticket(state, ticket_id).await
+    }
+
+    pub async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Self>, ApiError> {
+        AttachmentRepositoryImpl::find_by_id(state, id).await
+    }
+
+    pub async fn create(state: &AppState, attachment: &Self, data: &[u8]) -> Result<(), ApiError> {
+        AttachmentRepositoryImpl::create(state, attachment, data).await
+    }
+
+    pub async fn load(&self, state: &AppState) -> Result<Vec<u8>, ApiError> {
+        Ok(state.storage.load(&self.storage_key).await?)
+    }
+
+    pub async fn delete(state: &AppState, attachment: &Self) -> Result<(), ApiError> {
+        AttachmentRepositoryImpl::delete(state, attachment).await
+    }
+}
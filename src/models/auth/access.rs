@@ -1,4 +1,5 @@
 use axum::{extract::FromRequestParts, http::request::Parts};
+use uuid::Uuid;
 
 use crate::{
     errors::api_error::ApiError,
@@ -32,6 +33,24 @@ impl AccessControl {
             Err(ApiError::Unauthorized)
         }
     }
+
+    /// Returns whether the user has at least one of the specified roles.
+    pub fn has_any_role(&self, roles: &[Role]) -> bool {
+        roles.contains(&self.0.role)
+    }
+
+    /// Ensures the user is the owner of a resource or has at least one of the specified roles.
+    pub fn require_owner_or_any_role(
+        &self,
+        owner_id: Uuid,
+        roles: &[Role],
+    ) -> Result<(), ApiError> {
+        if self.0.id == owner_id || roles.contains(&self.0.role) {
+            Ok(())
+        } else {
+            Err(ApiError::Unauthorized)
+        }
+    }
 }
 
 impl<S> FromRequestParts<S> for AccessControl
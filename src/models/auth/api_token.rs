@@ -0,0 +1,328 @@
+use crate::{
+    database::{
+        repositories::api_token_repository::{ApiTokenRepository, ApiTokenRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+    models::DeletePayload,
+    utils::hashing::{generate_token, hash_token},
+};
+use axum::http::Method;
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::{FromRow, Type};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Starts every API token, telling them apart from JWTs.
+pub const API_TOKEN_PREFIX: &str = "tkfy_";
+
+/// What an API token can access. Write scopes include the matching read scope.
+#[derive(ToSchema, PartialEq, Clone, Copy, Serialize, Deserialize, Type, Debug)]
+#[sqlx(type_name = "api_token_scope")]
+pub enum ApiTokenScope {
+    #[serde(rename = "tickets:read")]
+    #[sqlx(rename = "tickets:read")]
+    TicketsRead,
+    #[serde(rename = "tickets:write")]
+    #[sqlx(rename = "tickets:write")]
+    TicketsWrite,
+    #[serde(rename = "users:read")]
+    #[sqlx(rename = "users:read")]
+    UsersRead,
+    #[serde(rename = "users:write")]
+    #[sqlx(rename = "users:write")]
+    UsersWrite,
+    #[serde(rename = "categories:read")]
+    #[sqlx(rename = "categories:read")]
+    CategoriesRead,
+    #[serde(rename = "categories:write")]
+    #[sqlx(rename = "categories:write")]
+    CategoriesWrite,
+    #[serde(rename = "tags:read")]
+    #[sqlx(rename = "tags:read")]
+    TagsRead,
+    #[serde(rename = "tags:write")]
+    #[sqlx(rename = "tags:write")]
+    TagsWrite,
+    #[serde(rename = "reports:read")]
+    #[sqlx(rename = "reports:read")]
+    ReportsRead,
+}
+
+impl std::fmt::Display for ApiTokenScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scope = match self {
+            ApiTokenScope::TicketsRead => "tickets:read",
+            ApiTokenScope::TicketsWrite => "tickets:write",
+            ApiTokenScope::UsersRead => "users:read",
+            ApiTokenScope::UsersWrite => "users:write",
+            ApiTokenScope::CategoriesRead => "categories:read",
+            ApiTokenScope::CategoriesWrite => "categories:write",
+            ApiTokenScope::TagsRead => "tags:read",
+            ApiTokenScope::TagsWrite => "tags:write",
+            ApiTokenScope::ReportsRead => "reports:read",
+        };
+
+        write!(f, "{scope}")
+    }
+}
+
+impl ApiTokenScope {
+    /// Returns the scope required by a request to the API, from its method and full path.
+    ///
+    /// Requests outside of the scoped resources, such as managing API tokens, cannot be made with API tokens.
+    pub fn required_for(method: &Method, path: &str) -> Option<Self> {
+        let resource = path.strip_prefix("/api/v1/")?;
+
+        if resource.starts_with("users/me/tokens") {
+            return None;
+        }
+
+        let read = matches!(*method, Method::GET | Method::HEAD);
+
+        match (resource.split('/').next()?, read) {
+            ("tickets" | "export", true) => Some(Self::TicketsRead),
+            ("tickets", false) => Some(Self::TicketsWrite),
+            ("users", true) => Some(Self::UsersRead),
+            ("users", false) => Some(Self::UsersWrite),
+            ("categories", true) => Some(Self::CategoriesRead),
+            ("categories", false) => Some(Self::CategoriesWrite),
+            ("tags", true) => Some(Self::TagsRead),
+            ("tags", false) => Some(Self::TagsWrite),
+            ("reports", true) => Some(Self::ReportsRead),
+            _ => None,
+        }
+    }
+
+    /// Returns the write scope including this read scope, if any.
+    fn write_scope(&self) -> Option<Self> {
+        match self {
+            ApiTokenScope::TicketsRead => Some(ApiTokenScope::TicketsWrite),
+            ApiTokenScope::UsersRead => Some(ApiTokenScope::UsersWrite),
+            ApiTokenScope::CategoriesRead => Some(ApiTokenScope::CategoriesWrite),
+            ApiTokenScope::TagsRead => Some(ApiTokenScope::TagsWrite),
+            _ => None,
+        }
+    }
+}
+
+/// A named token letting scripts and integrations act as its user, within its scopes, until it expires.
+///
+/// Only its hash is stored: the token itself is shown once, when created.
+#[derive(FromRow)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// The start of the token, telling tokens apart without revealing them.
+    pub prefix: String,
+    pub token_hash: String,
+    pub scopes: Vec<ApiTokenScope>,
+    pub expires_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(ToSchema, FromRow, Serialize, Deserialize)]
+pub struct ApiTokenPublic {
+    pub id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<ApiTokenScope>,
+    pub expires_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+/// A newly created API token, along with the token itself.
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct CreatedApiToken {
+    /// The token to send in the `X-Api-Key` header, or as a bearer token. It cannot be retrieved again.
+    pub token: String,
+    #[serde(flatten)]
+    pub api_token: ApiTokenPublic,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate)]
+pub struct CreateApiTokenPayload {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 chars."))]
+    pub name: String,
+    #[validate(length(min = 1, message = "At least one scope is required."))]
+    pub scopes: Vec<ApiTokenScope>,
+    /// How many days the token is valid.
+    #[validate(range(
+        min = 1,
+        max = 365,
+        message = "Expiration must be between 1 and 365 days."
+    ))]
+    pub expires_in_days: i64,
+}
+
+impl From<&ApiToken> for ApiTokenPublic {
+    fn from(api_token: &ApiToken) -> Self {
+        Self {
+            id: api_token.id,
+            name: api_token.name.clone(),
+            prefix: api_token.prefix.clone(),
+            scopes: api_token.scopes.clone(),
+            expires_at: api_token.expires_at,
+            last_used_at: api_token.last_used_at,
+            created_at: api_token.created_at,
+        }
+    }
+}
+
+impl ApiToken {
+    /// Builds a token of the user, returned along with its plaintext value.
+    pub fn new(user_id: Uuid, payload: &CreateApiTokenPayload) -> (Self, String) {
+        let token = format!("{API_TOKEN_PREFIX}{}", generate_token());
+        let now = Utc::now().naive_utc();
+
+        let mut scopes = Vec::new();
+        for scope in &payload.scopes {
+            if !scopes.contains(scope) {
+                scopes.push(*scope);
+            }
+        }
+
+        let api_token = Self {
+            id: Uuid::now_v7(),
+            user_id,
+            name: payload.name.trim().to_string(),
+            prefix: token[..API_TOKEN_PREFIX.len() + 6].to_string(),
+            token_hash: hash_token(&token),
+            scopes,
+            expires_at: now + Duration::days(payload.expires_in_days),
+            last_used_at: None,
+            created_at: now,
+        };
+
+        (api_token, token)
+    }
+
+    /// Returns whether the token grants the scope, directly or through the matching write scope.
+    pub fn allows(&self, scope: ApiTokenScope) -> bool {
+        self.scopes
+            .iter()
+            .any(|granted| *granted == scope || Some(*granted) == scope.write_scope())
+    }
+
+    pub async fn find_by_user(
+        state: &AppState,
+        user_id: Uuid,
+    ) -> Result<Vec<ApiTokenPublic>, ApiError> {
+        ApiTokenRepositoryImpl::find_by_user(state, user_id).await
+    }
+
+    pub async fn create(state: &AppState, api_token: &ApiToken) -> Result<(), ApiError> {
+        ApiTokenRepositoryImpl::create(state, api_token).await
+    }
+
+    pub async fn delete(
+        state: &AppState,
+        user_id: Uuid,
+        payload: &DeletePayload,
+    ) -> Result<(), ApiError> {
+        ApiTokenRepositoryImpl::delete(state, user_id, payload).await
+    }
+
+    /// Returns the unexpired token with the given plaintext value, recording its use.
+    pub async fn authenticate(state: &AppState, token: &str) -> Result<Option<Self>, ApiError> {
+        ApiTokenRepositoryImpl::touch(state, &hash_token(token), Utc::now().naive_utc()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_token(scopes: &[ApiTokenScope]) -> ApiToken {
+        let payload = CreateApiTokenPayload {
+            name: String::from("CI"),
+            scopes: scopes.to_vec(),
+            expires_in_days: 30,
+        };
+
+        ApiToken::new(Uuid::now_v7(), &payload).0
+    }
+
+    #[test]
+    fn reads_and_writes_require_their_scope() {
+        use ApiTokenScope::*;
+
+        let required = |method: Method, path: &str| ApiTokenScope::required_for(&method, path);
+
+        assert_eq!(required(Method::GET, "/api/v1/tickets"), Some(TicketsRead));
+        assert_eq!(
+            required(Method::HEAD, "/api/v1/tickets/TCK-000001"),
+            Some(TicketsRead)
+        );
+        assert_eq!(
+            required(Method::POST, "/api/v1/tickets/abc/comments"),
+            Some(TicketsWrite)
+        );
+        assert_eq!(required(Method::PUT, "/api/v1/users"), Some(UsersWrite));
+        assert_eq!(
+            required(Method::GET, "/api/v1/categories"),
+            Some(CategoriesRead)
+        );
+        assert_eq!(required(Method::DELETE, "/api/v1/tags"), Some(TagsWrite));
+        assert_eq!(
+            required(Method::GET, "/api/v1/reports/ratings"),
+            Some(ReportsRead)
+        );
+    }
+
+    #[test]
+    fn exports_require_reading_tickets() {
+        assert_eq!(
+            ApiTokenScope::required_for(&Method::GET, "/api/v1/export/csv/tickets"),
+            Some(ApiTokenScope::TicketsRead)
+        );
+        assert_eq!(
+            ApiTokenScope::required_for(&Method::POST, "/api/v1/export/csv/tickets"),
+            None
+        );
+    }
+
+    #[test]
+    fn managing_api_tokens_is_refused() {
+        assert_eq!(
+            ApiTokenScope::required_for(&Method::GET, "/api/v1/users/me/tokens"),
+            None
+        );
+        assert_eq!(
+            ApiTokenScope::required_for(&Method::POST, "/api/v1/users/me/tokens"),
+            None
+        );
+        assert_eq!(
+            ApiTokenScope::required_for(&Method::DELETE, "/api/v1/users/me/tokens/abc"),
+            None
+        );
+    }
+
+    #[test]
+    fn unknown_resources_are_refused() {
+        let required = |method: Method, path: &str| ApiTokenScope::required_for(&method, path);
+
+        assert_eq!(required(Method::GET, "/api/v1/auth/logout"), None);
+        assert_eq!(required(Method::POST, "/api/v1/reports/ratings"), None);
+        assert_eq!(required(Method::GET, "/api/v1/"), None);
+        assert_eq!(required(Method::GET, "/api/v2/tickets"), None);
+        assert_eq!(required(Method::GET, "/tickets"), None);
+    }
+
+    #[test]
+    fn write_scopes_include_reading() {
+        let api_token = api_token(&[ApiTokenScope::TicketsWrite, ApiTokenScope::UsersRead]);
+
+        assert!(api_token.allows(ApiTokenScope::TicketsWrite));
+        assert!(api_token.allows(ApiTokenScope::TicketsRead));
+        assert!(api_token.allows(ApiTokenScope::UsersRead));
+        assert!(!api_token.allows(ApiTokenScope::UsersWrite));
+        assert!(!api_token.allows(ApiTokenScope::CategoriesRead));
+        assert!(!api_token.allows(ApiTokenScope::ReportsRead));
+    }
+}
@@ -3,6 +3,10 @@ use utoipa::ToSchema;
 use validator::Validate;
 
 pub mod access;
+pub mod api_token;
+pub mod oidc;
+pub mod refresh_token;
+pub mod revocation;
 pub mod token;
 
 #[derive(Deserialize, Serialize, ToSchema, Validate)]
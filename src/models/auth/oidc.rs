@@ -0,0 +1,210 @@
+use crate::{
+    database::{
+        repositories::oidc_repository::{OidcRepository, OidcRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+    models::user::{Role, User},
+    utils::hashing::generate_token,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sqlx::prelude::FromRow;
+use std::collections::HashMap;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+/// How long a user has to sign in at the identity provider, in minutes.
+const LOGIN_STATE_LIFETIME: i64 = 10;
+
+/// A pending single sign-on, from the redirection to the identity provider until its callback.
+///
+/// The state is sent back by the provider, the PKCE code verifier proves the callback exchanges
+/// the code it was issued for, and the nonce ties the ID token to this login.
+#[derive(FromRow)]
+pub struct OidcLoginState {
+    pub state: String,
+    pub code_verifier: String,
+    pub nonce: String,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+/// Links an account of the identity provider, identified by its issuer and subject, to a user.
+#[derive(FromRow)]
+pub struct UserIdentity {
+    pub issuer: String,
+    pub subject: String,
+    pub user_id: Uuid,
+    pub created_at: NaiveDateTime,
+    pub last_login_at: NaiveDateTime,
+}
+
+/// The claims of an ID token used to sign in and provision users.
+#[derive(Deserialize, Serialize)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub nonce: Option<String>,
+    pub email: Option<String>,
+    /// Some providers send it as a string.
+    pub email_verified: Option<Value>,
+    pub preferred_username: Option<String>,
+    pub given_name: Option<String>,
+    pub family_name: Option<String>,
+    /// The other claims, among which the one mapped to roles.
+    #[serde(flatten)]
+    pub other: HashMap<String, Value>,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct OidcLoginQuery {
+    /// Forwarded to the identity provider, such as the email of the user signing in.
+    pub login_hint: Option<String>,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct OidcCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    /// Sent instead of the code when the login failed or was denied.
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+impl OidcLoginState {
+    /// Generates the random state, code verifier and nonce of a new login.
+    pub fn generate() -> Self {
+        let now = Utc::now().naive_utc();
+
+        Self {
+            state: generate_token(),
+            code_verifier: generate_token(),
+            nonce: generate_token(),
+            expires_at: now + Duration::minutes(LOGIN_STATE_LIFETIME),
+            created_at: now,
+        }
+    }
+
+    /// Returns the S256 PKCE challenge of the code verifier.
+    pub fn code_challenge(&self) -> String {
+        URL_SAFE_NO_PAD.encode(Sha256::digest(self.code_verifier.as_bytes()))
+    }
+
+    pub async fn create(state: &AppState, login_state: &OidcLoginState) -> Result<(), ApiError> {
+        OidcRepositoryImpl::create_login_state(state, login_state).await
+    }
+
+    /// Returns the unexpired login of the given state, which can only be completed once.
+    pub async fn take(state: &AppState, login_state: &str) -> Result<Option<Self>, ApiError> {
+        OidcRepositoryImpl::take_login_state(state, login_state, Utc::now().naive_utc()).await
+    }
+}
+
+impl IdTokenClaims {
+    /// Returns the email of the user, when the provider verified it.
+    pub fn verified_email(&self) -> Option<&str> {
+        match &self.email_verified {
+            Some(Value::Bool(true)) => self.email.as_deref(),
+            Some(Value::String(verified)) if verified == "true" => self.email.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the string values of a claim, given its path with nested claims separated by dots.
+    pub fn values(&self, path: &str) -> Vec<&str> {
+        let mut segments = path.split('.');
+        let mut claim = segments.next().and_then(|name| self.other.get(name));
+
+        for segment in segments {
+            claim = claim.and_then(|value| value.get(segment));
+        }
+
+        match claim {
+            Some(Value::String(value)) => vec![value.as_str()],
+            Some(Value::Array(values)) => values.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl UserIdentity {
+    /// Returns the user signing in with the identity, linking it to the user with the same verified email,
+    /// or provisioning a new user, on first login.
+    ///
+    /// The role mapped from the claims is given to new users, and updates the role of existing ones.
+    pub async fn sign_in(
+        state: &AppState,
+        claims: &IdTokenClaims,
+        role: Option<Role>,
+    ) -> Result<User, ApiError> {
+        OidcRepositoryImpl::sign_in(state, claims, role).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn claims(claims: Value) -> IdTokenClaims {
+        let mut token = json!({ "iss": "https://idp.example.com", "sub": "42" });
+        token
+            .as_object_mut()
+            .unwrap()
+            .extend(claims.as_object().unwrap().clone());
+
+        serde_json::from_value(token).unwrap()
+    }
+
+    #[test]
+    fn only_verified_emails_are_returned() {
+        let email = |verified: Value| {
+            claims(json!({ "email": "ada@example.com", "email_verified": verified }))
+                .verified_email()
+                .map(str::to_string)
+        };
+
+        assert_eq!(email(json!(true)).as_deref(), Some("ada@example.com"));
+        assert_eq!(email(json!("true")).as_deref(), Some("ada@example.com"));
+        assert_eq!(email(json!(false)), None);
+        assert_eq!(email(json!("false")), None);
+        assert_eq!(email(json!(1)), None);
+        assert_eq!(
+            claims(json!({ "email": "ada@example.com" })).verified_email(),
+            None
+        );
+    }
+
+    #[test]
+    fn claim_values_are_strings_or_arrays_of_strings() {
+        let claims = claims(json!({
+            "groups": ["tickify-admins", 7, "staff"],
+            "department": "support",
+            "level": 3,
+        }));
+
+        assert_eq!(claims.values("groups"), vec!["tickify-admins", "staff"]);
+        assert_eq!(claims.values("department"), vec!["support"]);
+        assert!(claims.values("level").is_empty());
+        assert!(claims.values("missing").is_empty());
+    }
+
+    #[test]
+    fn nested_claim_values_are_read_by_path() {
+        let claims = claims(json!({
+            "realm_access": { "roles": ["tickify-moderators"] },
+        }));
+
+        assert_eq!(
+            claims.values("realm_access.roles"),
+            vec!["tickify-moderators"]
+        );
+        assert!(claims.values("realm_access.groups").is_empty());
+        assert!(claims.values("realm_access.roles.name").is_empty());
+        assert!(claims.values("email").is_empty());
+    }
+}
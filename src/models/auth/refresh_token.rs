@@ -0,0 +1,72 @@
+use crate::{
+    config::Config,
+    database::{
+        repositories::refresh_token_repository::{
+            RefreshTokenRepository, RefreshTokenRepositoryImpl,
+        },
+        AppState,
+    },
+    errors::api_error::ApiError,
+    models::user::User,
+    utils::hashing::{generate_token, hash_token},
+};
+use chrono::{NaiveDateTime, Utc};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// An opaque token exchanged for a new access token, only stored hashed.
+///
+/// Every exchange rotates it: the used token is kept to detect its reuse, which revokes its whole family,
+/// the tokens descending from the same login.
+#[derive(FromRow)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl RefreshToken {
+    /// Builds a token of the given family, returned along with its plaintext value.
+    pub fn new(user_id: Uuid, family_id: Uuid) -> (Self, String) {
+        let token = generate_token();
+        let now = Utc::now().naive_utc();
+
+        let refresh_token = Self {
+            id: Uuid::now_v7(),
+            user_id,
+            family_id,
+            token_hash: hash_token(&token),
+            expires_at: now + Config::refresh_token_lifetime(),
+            used_at: None,
+            revoked_at: None,
+            created_at: now,
+        };
+
+        (refresh_token, token)
+    }
+
+    /// Issues the first token of a new family, on login.
+    pub async fn issue(
+        state: &AppState,
+        user_id: Uuid,
+        family_id: Uuid,
+    ) -> Result<String, ApiError> {
+        let (refresh_token, token) = Self::new(user_id, family_id);
+        RefreshTokenRepositoryImpl::create(state, &refresh_token).await?;
+
+        Ok(token)
+    }
+
+    /// Exchanges a token for the next one of its family, returned along with its plaintext value and its user.
+    pub async fn rotate(
+        state: &AppState,
+        token: &str,
+    ) -> Result<(User, RefreshToken, String), ApiError> {
+        RefreshTokenRepositoryImpl::rotate(state, &hash_token(token)).await
+    }
+}
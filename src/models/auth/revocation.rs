@@ -0,0 +1,183 @@
+use crate::{
+    database::{
+        repositories::revocation_repository::{RevocationRepository, RevocationRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+};
+use chrono::{NaiveDateTime, Utc};
+use sqlx::prelude::FromRow;
+use std::{collections::HashMap, sync::RwLock};
+use uuid::Uuid;
+
+/// An access token revoked before its expiration, on logout.
+#[derive(FromRow)]
+pub struct RevokedToken {
+    pub jti: Uuid,
+    pub user_id: Uuid,
+    /// Once the token has expired, its revocation can be forgotten.
+    pub expires_at: NaiveDateTime,
+    pub revoked_at: NaiveDateTime,
+}
+
+/// The revocation of every session of a user: the access tokens issued until then are no longer valid.
+#[derive(FromRow)]
+pub struct SessionRevocation {
+    pub user_id: Uuid,
+    pub revoked_at: NaiveDateTime,
+}
+
+/// In-memory copy of the revocations, checked on every request without querying the database.
+///
+/// Revocations are written to the database first, then to the cache of this instance;
+/// the cache is reloaded periodically to pick up those made by other instances.
+#[derive(Default)]
+pub struct RevocationCache {
+    /// The expiration of the revoked tokens, by ID.
+    tokens: RwLock<HashMap<Uuid, NaiveDateTime>>,
+    /// The last revocation of the sessions of the users, by user ID.
+    sessions: RwLock<HashMap<Uuid, NaiveDateTime>>,
+}
+
+impl RevocationCache {
+    /// Returns whether the token of the given user, issued at the given timestamp, was revoked.
+    pub fn is_revoked(&self, jti: Uuid, user_id: Uuid, issued_at: usize) -> bool {
+        if self
+            .tokens
+            .read()
+            .expect("Revocation cache poisoned")
+            .contains_key(&jti)
+        {
+            return true;
+        }
+
+        // `iat` has second precision, so the revocation only covers the tokens issued in the
+        // seconds before it: a login right after revoking the sessions must stay valid.
+        self.sessions
+            .read()
+            .expect("Revocation cache poisoned")
+            .get(&user_id)
+            .is_some_and(|revoked_at| (issued_at as i64) < revoked_at.and_utc().timestamp())
+    }
+
+    pub fn revoke_token(&self, token: &RevokedToken) {
+        self.tokens
+            .write()
+            .expect("Revocation cache poisoned")
+            .insert(token.jti, token.expires_at);
+    }
+
+    pub fn revoke_sessions(&self, revocation: &SessionRevocation) {
+        self.sessions
+            .write()
+            .expect("Revocation cache poisoned")
+            .insert(revocation.user_id, revocation.revoked_at);
+    }
+
+    /// Replaces the content of the cache with the revocations stored in the database.
+    pub async fn reload(&self, state: &AppState) -> Result<(), ApiError> {
+        let (tokens, sessions) = RevocationRepositoryImpl::find_active(state).await?;
+
+        *self.tokens.write().expect("Revocation cache poisoned") = tokens
+            .into_iter()
+            .map(|token| (token.jti, token.expires_at))
+            .collect();
+        *self.sessions.write().expect("Revocation cache poisoned") = sessions
+            .into_iter()
+            .map(|revocation| (revocation.user_id, revocation.revoked_at))
+            .collect();
+
+        Ok(())
+    }
+}
+
+impl RevokedToken {
+    pub fn new(jti: Uuid, user_id: Uuid, expires_at: NaiveDateTime) -> Self {
+        Self {
+            jti,
+            user_id,
+            expires_at,
+            revoked_at: Utc::now().naive_utc(),
+        }
+    }
+
+    /// Revokes the token along with the refresh tokens of its session.
+    pub async fn revoke(
+        state: &AppState,
+        token: &RevokedToken,
+        session_id: Uuid,
+    ) -> Result<(), ApiError> {
+        RevocationRepositoryImpl::revoke_token(state, token, session_id).await?;
+        state.revocations.revoke_token(token);
+        Ok(())
+    }
+
+    /// Forgets the revocations of the tokens expired before the given date, returning how many were.
+    pub async fn purge_expired(
+        state: &AppState,
+        expired_before: NaiveDateTime,
+    ) -> Result<u64, ApiError> {
+        RevocationRepositoryImpl::purge_expired(state, expired_before).await
+    }
+}
+
+impl SessionRevocation {
+    pub fn new(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            revoked_at: Utc::now().naive_utc(),
+        }
+    }
+
+    /// Revokes every access, refresh and API token of the user.
+    pub async fn revoke(state: &AppState, revocation: &SessionRevocation) -> Result<(), ApiError> {
+        RevocationRepositoryImpl::revoke_sessions(state, revocation).await?;
+        state.revocations.revoke_sessions(revocation);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    fn revoked_at(timestamp: i64) -> SessionRevocation {
+        SessionRevocation {
+            user_id: Uuid::now_v7(),
+            revoked_at: DateTime::from_timestamp(timestamp, 500_000_000)
+                .unwrap()
+                .naive_utc(),
+        }
+    }
+
+    #[test]
+    fn sessions_revoke_the_tokens_issued_before_them() {
+        let cache = RevocationCache::default();
+        let revocation = revoked_at(1_700_000_000);
+        cache.revoke_sessions(&revocation);
+
+        assert!(cache.is_revoked(Uuid::now_v7(), revocation.user_id, 1_699_999_999));
+        assert!(!cache.is_revoked(Uuid::now_v7(), revocation.user_id, 1_700_000_001));
+        assert!(!cache.is_revoked(Uuid::now_v7(), Uuid::now_v7(), 1_699_999_999));
+    }
+
+    #[test]
+    fn logins_in_the_second_of_the_revocation_stay_valid() {
+        let cache = RevocationCache::default();
+        let revocation = revoked_at(1_700_000_000);
+        cache.revoke_sessions(&revocation);
+
+        assert!(!cache.is_revoked(Uuid::now_v7(), revocation.user_id, 1_700_000_000));
+    }
+
+    #[test]
+    fn revoked_tokens_are_revoked_whenever_issued() {
+        let cache = RevocationCache::default();
+        let token = RevokedToken::new(Uuid::now_v7(), Uuid::now_v7(), Utc::now().naive_utc());
+        cache.revoke_token(&token);
+
+        assert!(cache.is_revoked(token.jti, token.user_id, usize::MAX));
+        assert!(!cache.is_revoked(Uuid::now_v7(), token.user_id, usize::MAX));
+    }
+}
@@ -1,15 +1,33 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub iat: usize,
     pub sub: String,
     pub exp: usize,
     pub role: String,
+    /// The unique identifier of the token, to revoke it.
+    pub jti: String,
+    /// The session of the token: the family of the refresh tokens renewing it.
+    pub sid: String,
 }
 
 #[derive(Deserialize, Serialize, ToSchema)]
 pub struct VerifyTokenPayload {
     pub token: String,
 }
+
+/// The tokens returned on login and on refresh.
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct TokenPair {
+    /// A short-lived JWT authenticating the requests.
+    pub access_token: String,
+    /// An opaque token exchanged once for a new pair on `/auth/refresh`.
+    pub refresh_token: String,
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct RefreshTokenPayload {
+    pub refresh_token: String,
+}
@@ -0,0 +1,84 @@
+use super::DeletePayload;
+use crate::{
+    database::{
+        repositories::category_repository::{CategoryRepository, CategoryRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(ToSchema, FromRow, Serialize, Deserialize)]
+pub struct Category {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(ToSchema, Clone, FromRow, Serialize, Deserialize)]
+pub struct CategoryInfo {
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate)]
+pub struct CreateCategoryPayload {
+    #[validate(length(min = 2, max = 50, message = "Name must be between 2 and 50 chars."))]
+    pub name: String,
+    #[validate(length(max = 500, message = "Description must be up to 500 chars."))]
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate)]
+pub struct UpdateCategoryPayload {
+    pub id: Uuid,
+    #[validate(length(min = 2, max = 50, message = "Name must be between 2 and 50 chars."))]
+    pub name: Option<String>,
+    #[validate(length(max = 500, message = "Description must be up to 500 chars."))]
+    pub description: Option<String>,
+}
+
+impl Category {
+    pub fn new(name: &str, description: Option<String>) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            name: name.to_string(),
+            description,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub async fn find_all(state: &AppState) -> Result<Vec<Self>, ApiError> {
+        CategoryRepositoryImpl::find_all(state).await
+    }
+
+    pub async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Self>, ApiError> {
+        CategoryRepositoryImpl::find_by_id(state, id).await
+    }
+
+    pub async fn create(
+        state: &AppState,
+        payload: &CreateCategoryPayload,
+    ) -> Result<Self, ApiError> {
+        CategoryRepositoryImpl::create(state, payload).await
+    }
+
+    pub async fn update(
+        state: &AppState,
+        payload: &UpdateCategoryPayload,
+    ) -> Result<Uuid, ApiError> {
+        CategoryRepositoryImpl::update(state, payload).await
+    }
+
+    pub async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError> {
+        CategoryRepositoryImpl::delete(state, payload).await
+    }
+}
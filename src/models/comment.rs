@@ -0,0 +1,117 @@
+use super::{ticket::RequesterInfo, DeletePayload};
+use crate::{
+    database::{
+        repositories::comment_repository::{CommentRepository, CommentRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(ToSchema, FromRow, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: Uuid,
+    pub ticket_id: Uuid,
+    pub author: Uuid,
+    pub content: String,
+    pub internal: bool,
+    pub parent_id: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(ToSchema, Clone, Serialize, Deserialize)]
+pub struct CommentPublic {
+    pub id: Uuid,
+    pub ticket_id: Uuid,
+    pub author: RequesterInfo,
+    pub content: String,
+    pub internal: bool,
+    /// The comment this one replies to, if any.
+    pub parent_id: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate)]
+pub struct CreateCommentPayload {
+    #[validate(length(
+        min = 1,
+        max = 3000,
+        message = "Content must be between 1 and 3000 chars."
+    ))]
+    pub content: String,
+    /// Internal notes are only visible to moderators and admins.
+    pub internal: Option<bool>,
+    /// Replies to another comment of the same ticket.
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate)]
+pub struct UpdateCommentPayload {
+    pub id: Uuid,
+    #[validate(length(
+        min = 1,
+        max = 3000,
+        message = "Content must be between 1 and 3000 chars."
+    ))]
+    pub content: String,
+}
+
+impl Comment {
+    pub fn new(
+        ticket_id: Uuid,
+        author: Uuid,
+        content: &str,
+        internal: bool,
+        parent_id: Option<Uuid>,
+    ) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            ticket_id,
+            author,
+            content: content.to_string(),
+            internal,
+            parent_id,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+        include_internal: bool,
+    ) -> Result<Vec<CommentPublic>, ApiError> {
+        CommentRepositoryImpl::find_by_ticket(state, ticket_id, include_internal).await
+    }
+
+    pub async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Self>, ApiError> {
+        CommentRepositoryImpl::find_by_id(state, id).await
+    }
+
+    pub async fn create(
+        state: &AppState,
+        ticket_id: Uuid,
+        author: Uuid,
+        payload: &CreateCommentPayload,
+    ) -> Result<Self, ApiError> {
+        CommentRepositoryImpl::create(state, ticket_id, author, payload).await
+    }
+
+    pub async fn update(
+        state: &AppState,
+        payload: &UpdateCommentPayload,
+    ) -> Result<Uuid, ApiError> {
+        CommentRepositoryImpl::update(state, payload).await
+    }
+
+    pub async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError> {
+        CommentRepositoryImpl::delete(state, payload).await
+    }
+}
@@ -0,0 +1,131 @@
+use super::{ticket::TicketStatus, user::User};
+use crate::{
+    database::{
+        repositories::link_repository::{LinkRepository, LinkRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::{FromRow, Type};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// The kind of a link, read from its source ticket to its target ticket.
+#[derive(ToSchema, PartialEq, Clone, Copy, Serialize, Deserialize, Type, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "snake_case"))]
+#[sqlx(type_name = "ticket_link_kind", rename_all = "snake_case")]
+pub enum TicketLinkKind {
+    ParentOf,
+    Blocks,
+    RelatesTo,
+    DuplicateOf,
+}
+
+impl std::fmt::Display for TicketLinkKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            TicketLinkKind::ParentOf => "parent_of",
+            TicketLinkKind::Blocks => "blocks",
+            TicketLinkKind::RelatesTo => "relates_to",
+            TicketLinkKind::DuplicateOf => "duplicate_of",
+        };
+
+        write!(f, "{kind}")
+    }
+}
+
+impl TicketLinkKind {
+    /// Returns the relation of a ticket to the other end of a link of this kind.
+    pub fn relation(&self, incoming: bool) -> TicketRelation {
+        match (self, incoming) {
+            (TicketLinkKind::ParentOf, false) => TicketRelation::ParentOf,
+            (TicketLinkKind::ParentOf, true) => TicketRelation::ChildOf,
+            (TicketLinkKind::Blocks, false) => TicketRelation::Blocks,
+            (TicketLinkKind::Blocks, true) => TicketRelation::BlockedBy,
+            (TicketLinkKind::RelatesTo, _) => TicketRelation::RelatesTo,
+            (TicketLinkKind::DuplicateOf, false) => TicketRelation::DuplicateOf,
+            (TicketLinkKind::DuplicateOf, true) => TicketRelation::DuplicatedBy,
+        }
+    }
+}
+
+/// The relation of a ticket to a linked ticket, from the point of view of the ticket.
+#[derive(ToSchema, PartialEq, Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "snake_case"))]
+pub enum TicketRelation {
+    ParentOf,
+    ChildOf,
+    Blocks,
+    BlockedBy,
+    RelatesTo,
+    DuplicateOf,
+    DuplicatedBy,
+}
+
+#[derive(ToSchema, FromRow, Serialize, Deserialize)]
+pub struct TicketLink {
+    pub id: Uuid,
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub kind: TicketLinkKind,
+    pub created_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(ToSchema, Clone, Serialize, Deserialize)]
+pub struct LinkedTicket {
+    pub id: Uuid,
+    pub title: String,
+    pub status: TicketStatus,
+}
+
+#[derive(ToSchema, Clone, Serialize, Deserialize)]
+pub struct TicketLinkPublic {
+    pub id: Uuid,
+    pub relation: TicketRelation,
+    pub ticket: LinkedTicket,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct CreateTicketLinkPayload {
+    /// The ticket to link to.
+    pub target: Uuid,
+    /// The kind of the link, read from this ticket to the target.
+    pub kind: TicketLinkKind,
+}
+
+impl TicketLink {
+    pub fn new(source_id: Uuid, target_id: Uuid, kind: TicketLinkKind, created_by: Uuid) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            source_id,
+            target_id,
+            kind,
+            created_by: Some(created_by),
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+        viewer: Option<Uuid>,
+    ) -> Result<Vec<TicketLinkPublic>, ApiError> {
+        LinkRepositoryImpl::find_by_ticket(state, ticket_id, viewer).await
+    }
+
+    pub async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Self>, ApiError> {
+        LinkRepositoryImpl::find_by_id(state, id).await
+    }
+
+    pub async fn create(state: &AppState, link: &TicketLink, actor: &User) -> Result<(), ApiError> {
+        LinkRepositoryImpl::create(state, link, actor).await
+    }
+
+    pub async fn delete(state: &AppState, link: &TicketLink, actor: &User) -> Result<(), ApiError> {
+        LinkRepositoryImpl::delete(state, link, actor).await
+    }
+}
@@ -1,9 +1,33 @@
+pub mod attachment;
 pub mod auth;
+pub mod category;
+pub mod comment;
+pub mod link;
+pub mod pagination;
+pub mod precondition;
+pub mod rating;
+pub mod report;
 pub mod status;
+pub mod tag;
 pub mod ticket;
+pub mod ticket_event;
 pub mod user;
+pub mod watcher;
+pub mod worklog;
 
 #[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
 pub struct DeletePayload {
     pub id: uuid::Uuid,
 }
+
+/// Deserializes a field that can be omitted or set to `null`, telling both apart:
+/// a missing field is `None`, while an explicit `null` is `Some(None)`.
+///
+/// Use it with `#[serde(default)]`, so missing fields still deserialize.
+pub fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: serde::Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    serde::Deserialize::deserialize(deserializer).map(Some)
+}
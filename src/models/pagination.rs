@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Number of items of a page when no limit is requested.
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+
+/// Maximum number of items of a page.
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+#[derive(ToSchema, PartialEq, Clone, Copy, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "lowercase", deserialize = "lowercase"))]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+
+    /// Comparison operator selecting the rows after the cursor in this direction.
+    pub fn after_cursor(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => ">",
+            SortDirection::Desc => "<",
+        }
+    }
+}
+
+/// A page of a listing, with the cursor of the next page and the total count of the listing.
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Pass it as `cursor` to retrieve the next page. Missing on the last page.
+    pub next_cursor: Option<Uuid>,
+    pub total: i64,
+}
+
+impl<T> Page<T> {
+    /// Builds a page from rows fetched with one extra row beyond the limit,
+    /// which tells whether there is a next page.
+    pub fn from_rows(mut items: Vec<T>, limit: i64, total: i64, id: impl Fn(&T) -> Uuid) -> Self {
+        let next_cursor = if items.len() as i64 > limit {
+            items.truncate(limit as usize);
+            items.last().map(id)
+        } else {
+            None
+        };
+
+        Self {
+            items,
+            next_cursor,
+            total,
+        }
+    }
+}
+
+/// Returns the requested page size, within the allowed range.
+pub fn page_size(limit: Option<i64>) -> i64 {
+    limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+}
@@ -0,0 +1,221 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tracing::error;
+
+use crate::errors::api_error::ApiError;
+
+/// The conditional request headers, checked against the version of a resource.
+///
+/// The version of a resource is its `updated_at`. Its `ETag` also holds a digest of the
+/// representation, which changes with the children of the resource and with the viewer.
+#[derive(Debug, Clone, Default)]
+pub struct Preconditions {
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+}
+
+impl Preconditions {
+    /// Returns the version of a resource updated at the given date.
+    fn version(updated_at: NaiveDateTime) -> String {
+        format!("{:x}", updated_at.and_utc().timestamp_micros())
+    }
+
+    /// Returns the `ETag` of a representation of the version of a resource updated at the given date.
+    pub fn etag(updated_at: NaiveDateTime, representation: &[u8]) -> String {
+        let digest = format!("{:x}", Sha256::digest(representation));
+
+        format!("\"{}-{}\"", Self::version(updated_at), &digest[..16])
+    }
+
+    /// Ensures the `If-Match` header, when sent, matches the current version of the resource.
+    ///
+    /// Only the version of the `ETag` is compared, as the children of the resource are not
+    /// changed by the request.
+    pub fn check(&self, updated_at: NaiveDateTime) -> Result<(), ApiError> {
+        match &self.if_match {
+            Some(tags) if !matches_version(tags, &Self::version(updated_at)) => {
+                Err(ApiError::PreconditionFailed)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether the `If-None-Match` header matches the current `ETag` of the resource,
+    /// i.e. the copy of the client is still fresh.
+    pub fn is_fresh(&self, etag: &str) -> bool {
+        self.if_none_match
+            .as_deref()
+            .is_some_and(|tags| matches(tags, etag, true))
+    }
+
+    /// Responds with the resource and its `ETag`, or with `304 Not Modified`
+    /// when the copy of the client is still fresh.
+    pub fn respond<T: Serialize>(&self, updated_at: NaiveDateTime, resource: T) -> Response {
+        let body = match serde_json::to_vec(&resource) {
+            Ok(body) => body,
+            Err(e) => {
+                error!("Error serializing the resource: {e}");
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+        let etag = Self::etag(updated_at, &body);
+
+        if self.is_fresh(&etag) {
+            (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response()
+        } else {
+            (
+                [
+                    (header::ETAG, etag),
+                    (header::CONTENT_TYPE, "application/json".to_string()),
+                ],
+                body,
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Whether a list of entity tags matches the `ETag`, comparing weak tags only when allowed.
+fn matches(tags: &str, etag: &str, weak: bool) -> bool {
+    tags.split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag == etag || (weak && tag.strip_prefix("W/") == Some(etag)))
+}
+
+/// Whether a list of entity tags has a strong tag of the version.
+fn matches_version(tags: &str, version: &str) -> bool {
+    tags.split(',').map(str::trim).any(|tag| {
+        tag == "*"
+            || tag
+                .strip_prefix('"')
+                .and_then(|tag| tag.strip_suffix('"'))
+                .and_then(|tag| tag.split_once('-'))
+                .is_some_and(|(tag_version, _)| tag_version == version)
+    })
+}
+
+fn header_value(headers: &HeaderMap, name: header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+impl<S> FromRequestParts<S> for Preconditions
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    /// Extracts the `If-Match` and `If-None-Match` headers from the request parts.
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self {
+            if_match: header_value(&parts.headers, header::IF_MATCH),
+            if_none_match: header_value(&parts.headers, header::IF_NONE_MATCH),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    const ETAG: &str = "\"5f3a\"";
+
+    fn updated_at() -> NaiveDateTime {
+        DateTime::from_timestamp(1_700_000_000, 123_000)
+            .unwrap()
+            .naive_utc()
+    }
+
+    #[test]
+    fn matches_the_etag_or_any() {
+        assert!(matches(ETAG, ETAG, false));
+        assert!(matches("*", ETAG, false));
+        assert!(!matches("\"other\"", ETAG, false));
+        assert!(!matches("5f3a", ETAG, false));
+    }
+
+    #[test]
+    fn matches_weak_tags_only_when_allowed() {
+        assert!(matches("W/\"5f3a\"", ETAG, true));
+        assert!(!matches("W/\"5f3a\"", ETAG, false));
+    }
+
+    #[test]
+    fn matches_any_tag_of_a_list() {
+        assert!(matches("\"a\", \"5f3a\"", ETAG, false));
+        assert!(matches("\"a\",W/\"5f3a\"", ETAG, true));
+        assert!(matches("\"a\", *", ETAG, false));
+        assert!(!matches("\"a\", \"b\"", ETAG, true));
+    }
+
+    #[test]
+    fn etags_change_with_the_version_and_the_representation() {
+        let etag = Preconditions::etag(updated_at(), b"{}");
+
+        assert_eq!(etag, Preconditions::etag(updated_at(), b"{}"));
+        assert_ne!(
+            etag,
+            Preconditions::etag(updated_at(), b"{\"comments\":[]}")
+        );
+        assert_ne!(
+            etag,
+            Preconditions::etag(updated_at() + chrono::Duration::microseconds(1), b"{}")
+        );
+    }
+
+    #[test]
+    fn if_match_rejects_stale_versions() {
+        let etag = Preconditions::etag(updated_at(), b"{}");
+        let preconditions = |if_match: &str| Preconditions {
+            if_match: Some(if_match.to_string()),
+            if_none_match: None,
+        };
+
+        assert!(Preconditions::default().check(updated_at()).is_ok());
+        assert!(preconditions(&etag).check(updated_at()).is_ok());
+        assert!(preconditions("*").check(updated_at()).is_ok());
+        assert!(matches!(
+            preconditions("\"0-0\"").check(updated_at()),
+            Err(ApiError::PreconditionFailed)
+        ));
+        assert!(preconditions(&format!("W/{etag}"))
+            .check(updated_at())
+            .is_err());
+    }
+
+    #[test]
+    fn if_match_ignores_the_representation() {
+        let etag = Preconditions::etag(updated_at(), b"{\"comments\":[]}");
+        let preconditions = Preconditions {
+            if_match: Some(format!("\"a\", {etag}")),
+            if_none_match: None,
+        };
+
+        assert!(preconditions.check(updated_at()).is_ok());
+    }
+
+    #[test]
+    fn if_none_match_is_fresh_for_the_current_etag() {
+        let etag = Preconditions::etag(updated_at(), b"{}");
+        let preconditions = |if_none_match: &str| Preconditions {
+            if_match: None,
+            if_none_match: Some(if_none_match.to_string()),
+        };
+
+        assert!(!Preconditions::default().is_fresh(&etag));
+        assert!(preconditions(&etag).is_fresh(&etag));
+        assert!(preconditions(&format!("W/{etag}")).is_fresh(&etag));
+        assert!(preconditions("*").is_fresh(&etag));
+        assert!(!preconditions("\"0\"").is_fresh(&etag));
+        assert!(!preconditions(&etag).is_fresh(&Preconditions::etag(updated_at(), b"[]")));
+    }
+}
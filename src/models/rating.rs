@@ -0,0 +1,78 @@
+use super::{report::ReportQuery, ticket::RequesterInfo};
+use crate::{
+    database::{
+        repositories::rating_repository::{RatingRepository, RatingRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// The satisfaction rating of the requester with the resolution of a ticket.
+#[derive(ToSchema, FromRow, Serialize, Deserialize)]
+pub struct Rating {
+    pub ticket_id: Uuid,
+    pub rater: Uuid,
+    /// The agent credited with the resolution: the assignee, or whoever closed the ticket.
+    pub agent: Option<Uuid>,
+    pub score: i16,
+    pub comment: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(ToSchema, Clone, Serialize, Deserialize)]
+pub struct RatingPublic {
+    pub score: i16,
+    pub comment: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate)]
+pub struct CreateRatingPayload {
+    #[validate(range(min = 1, max = 5, message = "Score must be between 1 and 5."))]
+    pub score: i16,
+    #[validate(length(max = 3000, message = "Comment must be up to 3000 chars."))]
+    pub comment: Option<String>,
+}
+
+/// The ratings of the tickets an agent resolved in a period.
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct RatingReportEntry {
+    pub agent: RequesterInfo,
+    /// The first day of the period.
+    pub period: NaiveDate,
+    pub ratings: i64,
+    /// The average score, from 1 to 5.
+    pub average: f64,
+    /// Share of ratings of 4 or 5, from 0 to 1.
+    pub satisfaction: f64,
+}
+
+impl Rating {
+    pub fn new(ticket_id: Uuid, rater: Uuid, payload: &CreateRatingPayload) -> Self {
+        Self {
+            ticket_id,
+            rater,
+            agent: None,
+            score: payload.score,
+            comment: payload.comment.clone(),
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub async fn create(state: &AppState, rating: &Rating) -> Result<(), ApiError> {
+        RatingRepositoryImpl::create(state, rating).await
+    }
+
+    pub async fn report(
+        state: &AppState,
+        query: &ReportQuery,
+    ) -> Result<Vec<RatingReportEntry>, ApiError> {
+        RatingRepositoryImpl::report(state, query).await
+    }
+}
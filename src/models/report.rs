@@ -0,0 +1,37 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// The length of the periods a report is grouped by.
+#[derive(ToSchema, PartialEq, Clone, Copy, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "lowercase", deserialize = "lowercase"))]
+pub enum ReportPeriod {
+    Day,
+    Week,
+    #[default]
+    Month,
+}
+
+impl ReportPeriod {
+    /// The field of `date_trunc` starting each period.
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            ReportPeriod::Day => "day",
+            ReportPeriod::Week => "week",
+            ReportPeriod::Month => "month",
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams, Default)]
+pub struct ReportQuery {
+    /// Only report from this date, inclusive.
+    pub from: Option<NaiveDate>,
+    /// Only report up to this date, inclusive.
+    pub to: Option<NaiveDate>,
+    /// Only report the agent.
+    pub agent: Option<Uuid>,
+    /// Defaults to `month`. Weeks start on monday.
+    pub period: Option<ReportPeriod>,
+}
@@ -0,0 +1,52 @@
+use super::user::User;
+use crate::{
+    database::{
+        repositories::tag_repository::{TagRepository, TagRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Maximum number of tags of a single ticket.
+pub const MAX_TAGS_PER_TICKET: usize = 10;
+
+/// Maximum length of a single tag.
+pub const MAX_TAG_LENGTH: usize = 30;
+
+#[derive(ToSchema, FromRow, Serialize, Deserialize)]
+pub struct Tag {
+    pub name: String,
+    /// Number of tickets with the tag.
+    pub tickets: i64,
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct TagsPayload {
+    pub tags: Vec<String>,
+}
+
+impl Tag {
+    pub async fn find_all(state: &AppState) -> Result<Vec<Self>, ApiError> {
+        TagRepositoryImpl::find_all(state).await
+    }
+
+    pub async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<String>, ApiError> {
+        TagRepositoryImpl::find_by_ticket(state, ticket_id).await
+    }
+
+    pub async fn replace(
+        state: &AppState,
+        ticket_id: Uuid,
+        tags: &[String],
+        actor: &User,
+    ) -> Result<(), ApiError> {
+        TagRepositoryImpl::replace(state, ticket_id, tags, actor).await
+    }
+}
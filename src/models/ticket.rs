@@ -1,5 +1,16 @@
-use super::DeletePayload;
+use super::{
+    attachment::AttachmentPublic,
+    category::CategoryInfo,
+    comment::CommentPublic,
+    link::TicketLinkPublic,
+    pagination::{Page, SortDirection},
+    precondition::Preconditions,
+    rating::RatingPublic,
+    user::{Role, User},
+    DeletePayload,
+};
 use crate::{
+    config::Config,
     database::{
         repositories::ticket_repository::{TicketRepository, TicketRepositoryImpl},
         AppState,
@@ -9,7 +20,7 @@ use crate::{
 use chrono::{NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::prelude::{FromRow, Type};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
 
@@ -23,6 +34,7 @@ pub enum TicketStatus {
     Reopened,
     Paused,
     Cancelled,
+    Merged,
 }
 
 impl ToString for TicketStatus {
@@ -34,36 +46,173 @@ impl ToString for TicketStatus {
             TicketStatus::Reopened => "Reopened".to_string(),
             TicketStatus::Paused => "Paused".to_string(),
             TicketStatus::Cancelled => "Cancelled".to_string(),
+            TicketStatus::Merged => "Merged".to_string(),
         }
     }
 }
 
-#[derive(ToSchema, FromRow, Serialize, Deserialize)]
+impl TicketStatus {
+    /// Returns whether the status ends the lifecycle of the ticket.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            TicketStatus::Closed | TicketStatus::Cancelled | TicketStatus::Merged
+        )
+    }
+
+    /// Returns the statuses a ticket can move to from the current one.
+    ///
+    /// Regular users can only cancel or reopen their tickets; every other
+    /// move is reserved for moderators and admins. Tickets only get merged
+    /// through a merge, which cannot be undone.
+    pub fn allowed_transitions(&self, role: &Role) -> Vec<TicketStatus> {
+        use TicketStatus::*;
+
+        let transitions = match self {
+            Open => vec![InProgress, Paused, Closed, Cancelled],
+            InProgress => vec![Paused, Closed, Cancelled],
+            Paused => vec![InProgress, Closed, Cancelled],
+            Reopened => vec![InProgress, Paused, Closed, Cancelled],
+            Closed => vec![Reopened],
+            Cancelled => vec![Reopened],
+            Merged => vec![],
+        };
+
+        match role {
+            Role::User => transitions
+                .into_iter()
+                .filter(|status| matches!(status, Cancelled | Reopened))
+                .collect(),
+            Role::Moderator | Role::Admin => transitions,
+        }
+    }
+
+    /// Returns whether a user with the given role can move a ticket to the `next` status.
+    pub fn can_transition_to(&self, next: &TicketStatus, role: &Role) -> bool {
+        self.allowed_transitions(role).contains(next)
+    }
+}
+
+#[derive(ToSchema, PartialEq, Clone, Serialize, Deserialize, Type, Debug, Default)]
+#[serde(rename_all(serialize = "lowercase", deserialize = "lowercase"))]
+#[sqlx(type_name = "ticket_priority", rename_all = "lowercase")]
+pub enum TicketPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Urgent,
+}
+
+impl std::fmt::Display for TicketPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TicketPriority::Low => write!(f, "Low"),
+            TicketPriority::Normal => write!(f, "Normal"),
+            TicketPriority::High => write!(f, "High"),
+            TicketPriority::Urgent => write!(f, "Urgent"),
+        }
+    }
+}
+
+#[derive(ToSchema, PartialEq, Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "snake_case"))]
+pub enum SlaStatus {
+    OnTrack,
+    AtRisk,
+    Breached,
+}
+
+impl SlaStatus {
+    /// Evaluates the SLA of a ticket at the given time.
+    ///
+    /// A target is breached when it was met late, or is still pending after its due date.
+    /// Closing a ticket also counts as its first response. A pending target is at risk
+    /// once 80% of its window has elapsed.
+    pub fn evaluate(
+        created_at: NaiveDateTime,
+        response_due_at: Option<NaiveDateTime>,
+        due_at: Option<NaiveDateTime>,
+        responded_at: Option<NaiveDateTime>,
+        closed_at: Option<NaiveDateTime>,
+        now: NaiveDateTime,
+    ) -> Self {
+        let responded_at = responded_at.or(closed_at);
+        let late = |done: Option<NaiveDateTime>, due: Option<NaiveDateTime>| {
+            due.is_some_and(|due| done.unwrap_or(now) > due)
+        };
+        let near = |done: Option<NaiveDateTime>, due: Option<NaiveDateTime>| {
+            done.is_none() && due.is_some_and(|due| now >= created_at + (due - created_at) * 4 / 5)
+        };
+
+        if late(responded_at, response_due_at) || late(closed_at, due_at) {
+            SlaStatus::Breached
+        } else if near(responded_at, response_due_at) || near(closed_at, due_at) {
+            SlaStatus::AtRisk
+        } else {
+            SlaStatus::OnTrack
+        }
+    }
+}
+
+#[derive(ToSchema, FromRow, Serialize, Deserialize, Clone)]
 pub struct Ticket {
     pub id: Uuid,
     pub title: String,
     pub description: String,
     pub requester: Uuid,
     pub status: TicketStatus,
+    pub priority: TicketPriority,
+    pub category: Option<Uuid>,
+    pub assignee: Option<Uuid>,
     pub closed_by: Option<Uuid>,
     pub solution: Option<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub closed_at: Option<NaiveDateTime>,
+    pub response_due_at: Option<NaiveDateTime>,
+    pub due_at: Option<NaiveDateTime>,
+    pub responded_at: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub deleted_by: Option<Uuid>,
+    pub merged_into: Option<Uuid>,
 }
 
 #[derive(ToSchema, FromRow, Serialize, Deserialize)]
 pub struct TicketPublic {
     pub id: Uuid,
+    /// The human-readable number of the ticket, like `TCK-000123`.
+    pub number: String,
     pub title: String,
     pub description: String,
     pub status: TicketStatus,
+    pub priority: TicketPriority,
+    pub category: Option<CategoryInfo>,
+    pub tags: Vec<String>,
     pub requester: RequesterInfo,
+    pub assignee: Option<RequesterInfo>,
     pub closed_by: Option<RequesterInfo>,
     pub solution: Option<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub closed_at: Option<NaiveDateTime>,
+    pub response_due_at: Option<NaiveDateTime>,
+    pub due_at: Option<NaiveDateTime>,
+    pub responded_at: Option<NaiveDateTime>,
+    pub sla_status: SlaStatus,
+    /// The ticket this one was merged into, where its conversation continues.
+    pub merged_into: Option<Uuid>,
+    /// Only set for the tickets in the trash.
+    pub deleted_at: Option<NaiveDateTime>,
+    pub deleted_by: Option<RequesterInfo>,
+    /// Total minutes logged on the ticket.
+    pub worked_minutes: i64,
+    /// The satisfaction rating of the requester, once the ticket is closed.
+    pub rating: Option<RatingPublic>,
+    pub watchers: Vec<RequesterInfo>,
+    pub links: Vec<TicketLinkPublic>,
+    pub comments: Vec<CommentPublic>,
+    pub attachments: Vec<AttachmentPublic>,
 }
 
 #[derive(ToSchema, Clone, FromRow, Serialize, Deserialize)]
@@ -77,15 +226,64 @@ pub struct RequesterInfo {
 
 pub struct TicketView {
     pub id: String,
+    pub number: String,
     pub title: String,
     pub description: String,
     pub requester: String,
+    pub assignee: String,
     pub status: String,
     pub closed_by: String,
     pub solution: String,
     pub created_at: String,
     pub updated_at: String,
     pub closed_at: String,
+    pub worked_minutes: String,
+    pub comments: Vec<String>,
+}
+
+impl From<TicketPublic> for TicketView {
+    /// Formats all fields for export.
+    /// For each field not found, returns `null`.
+    fn from(ticket: TicketPublic) -> Self {
+        let time_fmt = "%Y-%m-%d %H:%M:%S";
+
+        let comments = ticket
+            .comments
+            .iter()
+            .map(|comment| {
+                format!(
+                    "{}{} ({}): {}",
+                    if comment.internal { "[internal] " } else { "" },
+                    comment.author.username,
+                    comment.created_at.format(time_fmt),
+                    comment.content
+                )
+            })
+            .collect();
+
+        Self {
+            id: ticket.id.to_string(),
+            number: ticket.number,
+            title: ticket.title,
+            description: ticket.description,
+            requester: ticket.requester.username,
+            assignee: ticket
+                .assignee
+                .map_or("null".to_string(), |assignee| assignee.username),
+            status: ticket.status.to_string(),
+            closed_by: ticket
+                .closed_by
+                .map_or("null".to_string(), |closed_by| closed_by.username),
+            solution: ticket.solution.unwrap_or_else(|| "null".to_string()),
+            created_at: ticket.created_at.format(time_fmt).to_string(),
+            updated_at: ticket.updated_at.format(time_fmt).to_string(),
+            closed_at: ticket.closed_at.map_or("null".to_string(), |closed_at| {
+                closed_at.format(time_fmt).to_string()
+            }),
+            worked_minutes: ticket.worked_minutes.to_string(),
+            comments,
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, ToSchema, Validate)]
@@ -99,6 +297,10 @@ pub struct CreateTicketPayload {
     ))]
     pub description: String,
     pub requester: Option<String>,
+    /// Defaults to `normal`.
+    pub priority: Option<TicketPriority>,
+    pub category: Option<Uuid>,
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Deserialize, Serialize, ToSchema, Validate)]
@@ -114,6 +316,15 @@ pub struct UpdateTicketPayload {
     pub description: Option<String>,
     pub requester: Option<Uuid>,
     pub status: Option<TicketStatus>,
+    /// Changing the priority recomputes the SLA due dates from the creation date.
+    pub priority: Option<TicketPriority>,
+    /// Set to `null` to remove the category of the ticket.
+    #[serde(default, deserialize_with = "super::double_option")]
+    #[schema(value_type = Option<Uuid>, nullable)]
+    pub category: Option<Option<Uuid>>,
+    /// Replaces all the tags of the ticket.
+    pub tags: Option<Vec<String>>,
+    /// Who closed the ticket. Only used when closing, defaults to the current user.
     pub closed_by: Option<Uuid>,
     #[validate(length(
         min = 10,
@@ -123,19 +334,206 @@ pub struct UpdateTicketPayload {
     pub solution: Option<String>,
 }
 
+#[derive(ToSchema, PartialEq, Clone, Copy, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "snake_case"))]
+pub enum TicketSortField {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Priority,
+    Title,
+}
+
+impl TicketSortField {
+    pub fn column(&self) -> &'static str {
+        match self {
+            TicketSortField::CreatedAt => "created_at",
+            TicketSortField::UpdatedAt => "updated_at",
+            TicketSortField::Priority => "priority",
+            TicketSortField::Title => "title",
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams, Default)]
+pub struct TicketFilters {
+    /// Only list tickets whose SLA is (`true`) or is not (`false`) breached.
+    pub breached: Option<bool>,
+    /// Only list tickets of the category.
+    pub category: Option<Uuid>,
+    /// Only list tickets with the tag.
+    pub tag: Option<String>,
+    /// Only list tickets with the status.
+    pub status: Option<TicketStatus>,
+    /// Only list tickets of the requester.
+    pub requester: Option<Uuid>,
+    /// Only list tickets created at or after this date (UTC).
+    pub created_after: Option<NaiveDateTime>,
+    /// Only list tickets created before this date (UTC).
+    pub created_before: Option<NaiveDateTime>,
+    /// Only list tickets whose title or description contains the text.
+    pub q: Option<String>,
+    /// Defaults to `created_at`.
+    pub sort: Option<TicketSortField>,
+    /// Defaults to `desc`.
+    pub direction: Option<SortDirection>,
+    /// The `next_cursor` of the previous page.
+    pub cursor: Option<Uuid>,
+    /// Defaults to 20, up to 100.
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, IntoParams, Validate)]
+pub struct TicketSearchQuery {
+    /// Words or quoted phrases to search for, in web search syntax.
+    #[validate(length(
+        min = 2,
+        max = 200,
+        message = "Search query must be between 2 and 200 chars."
+    ))]
+    pub q: String,
+    /// Defaults to 20, up to 100.
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TicketSearchResult {
+    pub ticket: TicketPublic,
+    pub rank: f32,
+    /// Excerpts of the description and solution as escaped HTML, with the matches wrapped in `<mark>` tags.
+    pub snippet: String,
+}
+
+impl TicketSearchResult {
+    /// Delimiters of the matches in the snippets highlighted by the database.
+    const MATCH_START: char = '\u{2}';
+    const MATCH_STOP: char = '\u{3}';
+
+    /// Escapes the HTML of a highlighted snippet, then wraps its matches in `<mark>` tags.
+    pub fn highlight(snippet: &str) -> String {
+        let mut html = String::with_capacity(snippet.len());
+
+        for c in snippet.chars() {
+            match c {
+                Self::MATCH_START => html.push_str("<mark>"),
+                Self::MATCH_STOP => html.push_str("</mark>"),
+                '&' => html.push_str("&amp;"),
+                '<' => html.push_str("&lt;"),
+                '>' => html.push_str("&gt;"),
+                '"' => html.push_str("&quot;"),
+                '\'' => html.push_str("&#39;"),
+                c => html.push(c),
+            }
+        }
+
+        html
+    }
+}
+
+/// The human-readable number of a ticket, like `TCK-000123`.
+///
+/// The prefix is read from the configuration, and is optional when parsing a number.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TicketNumber(pub i64);
+
+impl std::fmt::Display for TicketNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{:06}", Config::ticket_number_prefix(), self.0)
+    }
+}
+
+impl std::str::FromStr for TicketNumber {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let prefix = Config::ticket_number_prefix();
+        let digits = match value.get(..prefix.len()) {
+            Some(start) if start.eq_ignore_ascii_case(&prefix) => &value[prefix.len()..],
+            _ => value,
+        };
+
+        digits.parse().map(TicketNumber)
+    }
+}
+
+/// The tickets a user can see.
+///
+/// Moderators and admins see every ticket, internal notes included,
+/// while regular users only see the tickets they requested or watch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TicketVisibility {
+    All,
+    Participant(Uuid),
+}
+
+impl TicketVisibility {
+    pub fn of(user: &User) -> Self {
+        match user.role {
+            Role::Admin | Role::Moderator => TicketVisibility::All,
+            Role::User => TicketVisibility::Participant(user.id),
+        }
+    }
+
+    /// The user whose tickets are visible, or `None` when every ticket is.
+    pub fn viewer(&self) -> Option<Uuid> {
+        match self {
+            TicketVisibility::All => None,
+            TicketVisibility::Participant(id) => Some(*id),
+        }
+    }
+
+    pub fn includes_internal(&self) -> bool {
+        *self == TicketVisibility::All
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct TicketTransitions {
+    pub current: TicketStatus,
+    pub allowed: Vec<TicketStatus>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate)]
+pub struct MergeTicketsPayload {
+    /// The duplicate tickets to fold into the target ticket.
+    #[validate(length(
+        min = 1,
+        max = 50,
+        message = "Between 1 and 50 source tickets must be provided."
+    ))]
+    pub sources: Vec<Uuid>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct AssignTicketPayload {
+    pub assignee: Uuid,
+}
+
 impl Ticket {
-    pub fn new(title: &str, description: &str, requester: Uuid) -> Self {
+    pub fn new(title: &str, description: &str, requester: Uuid, priority: TicketPriority) -> Self {
+        let now = Utc::now().naive_utc();
+        let sla = Config::sla_targets(&priority);
+
         Self {
             id: Uuid::now_v7(),
             title: title.to_string(),
             description: description.to_string(),
             requester,
             status: TicketStatus::Open,
+            priority,
+            category: None,
+            assignee: None,
             closed_by: None,
             solution: None,
-            created_at: Utc::now().naive_utc(),
-            updated_at: Utc::now().naive_utc(),
+            created_at: now,
+            updated_at: now,
             closed_at: None,
+            response_due_at: Some(now + sla.response),
+            due_at: Some(now + sla.resolution),
+            responded_at: None,
+            deleted_at: None,
+            deleted_by: None,
+            merged_into: None,
         }
     }
 
@@ -143,23 +541,262 @@ impl Ticket {
         Ok(TicketRepositoryImpl::count(state).await?)
     }
 
-    pub async fn find_all(state: &AppState) -> Result<Vec<TicketPublic>, ApiError> {
-        Ok(TicketRepositoryImpl::find_all(state).await?)
+    pub async fn find_all(
+        state: &AppState,
+        visibility: &TicketVisibility,
+    ) -> Result<Vec<TicketPublic>, ApiError> {
+        Ok(TicketRepositoryImpl::find_all(state, visibility).await?)
+    }
+
+    pub async fn find_page(
+        state: &AppState,
+        filters: &TicketFilters,
+        visibility: &TicketVisibility,
+    ) -> Result<Page<TicketPublic>, ApiError> {
+        TicketRepositoryImpl::find_page(state, filters, visibility).await
+    }
+
+    pub async fn find_by_id(
+        state: &AppState,
+        id: Uuid,
+        visibility: &TicketVisibility,
+    ) -> Result<Option<TicketPublic>, ApiError> {
+        Ok(TicketRepositoryImpl::find_by_id(state, id, visibility).await?)
+    }
+
+    pub async fn find_id_by_number(
+        state: &AppState,
+        number: TicketNumber,
+    ) -> Result<Option<Uuid>, ApiError> {
+        TicketRepositoryImpl::find_id_by_number(state, number).await
+    }
+
+    pub async fn search(
+        state: &AppState,
+        query: &TicketSearchQuery,
+        visibility: &TicketVisibility,
+    ) -> Result<Vec<TicketSearchResult>, ApiError> {
+        TicketRepositoryImpl::search(state, query, visibility).await
+    }
+
+    pub async fn find_queue(
+        state: &AppState,
+        assignee: Option<Uuid>,
+    ) -> Result<Vec<TicketPublic>, ApiError> {
+        TicketRepositoryImpl::find_queue(state, assignee).await
+    }
+
+    pub async fn create(
+        state: &AppState,
+        payload: &CreateTicketPayload,
+        actor: &User,
+    ) -> Result<Self, ApiError> {
+        Ok(TicketRepositoryImpl::create(state, payload, actor).await?)
+    }
+
+    pub async fn update(
+        state: &AppState,
+        payload: &UpdateTicketPayload,
+        actor: &User,
+        preconditions: &Preconditions,
+    ) -> Result<Uuid, ApiError> {
+        Ok(TicketRepositoryImpl::update(state, payload, actor, preconditions).await?)
+    }
+
+    pub async fn assign(
+        state: &AppState,
+        id: Uuid,
+        assignee: Option<Uuid>,
+        actor: &User,
+    ) -> Result<Uuid, ApiError> {
+        TicketRepositoryImpl::assign(state, id, assignee, actor).await
+    }
+
+    pub async fn merge(
+        state: &AppState,
+        target: Uuid,
+        sources: &[Uuid],
+        actor: &User,
+    ) -> Result<Uuid, ApiError> {
+        TicketRepositoryImpl::merge(state, target, sources, actor).await
+    }
+
+    pub async fn mark_responded(state: &AppState, id: Uuid) -> Result<(), ApiError> {
+        TicketRepositoryImpl::mark_responded(state, id).await
+    }
+
+    pub async fn delete(
+        state: &AppState,
+        payload: &DeletePayload,
+        actor: &User,
+        preconditions: &Preconditions,
+    ) -> Result<(), ApiError> {
+        Ok(TicketRepositoryImpl::delete(state, payload, actor, preconditions).await?)
     }
 
-    pub async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<TicketPublic>, ApiError> {
-        Ok(TicketRepositoryImpl::find_by_id(state, id).await?)
+    pub async fn find_trash(state: &AppState) -> Result<Vec<TicketPublic>, ApiError> {
+        TicketRepositoryImpl::find_trash(state).await
     }
 
-    pub async fn create(state: &AppState, payload: &CreateTicketPayload) -> Result<Self, ApiError> {
-        Ok(TicketRepositoryImpl::create(state, payload).await?)
+    pub async fn restore(state: &AppState, id: Uuid, actor: &User) -> Result<Uuid, ApiError> {
+        TicketRepositoryImpl::restore(state, id, actor).await
     }
 
-    pub async fn update(state: &AppState, payload: &UpdateTicketPayload) -> Result<Uuid, ApiError> {
-        Ok(TicketRepositoryImpl::update(state, payload).await?)
+    pub async fn purge(state: &AppState, id: Uuid, actor: Option<&User>) -> Result<(), ApiError> {
+        TicketRepositoryImpl::purge(state, id, actor).await
     }
 
-    pub async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError> {
-        Ok(TicketRepositoryImpl::delete(state, payload).await?)
+    pub async fn purge_expired(
+        state: &AppState,
+        deleted_before: NaiveDateTime,
+    ) -> Result<Vec<Uuid>, ApiError> {
+        TicketRepositoryImpl::purge_expired(state, deleted_before).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    fn at(hours: i64) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            + Duration::hours(hours)
+    }
+
+    #[test]
+    fn moderators_follow_the_transition_table() {
+        use TicketStatus::*;
+
+        assert_eq!(
+            Open.allowed_transitions(&Role::Moderator),
+            vec![InProgress, Paused, Closed, Cancelled]
+        );
+        assert_eq!(
+            InProgress.allowed_transitions(&Role::Admin),
+            vec![Paused, Closed, Cancelled]
+        );
+        assert_eq!(
+            Paused.allowed_transitions(&Role::Admin),
+            vec![InProgress, Closed, Cancelled]
+        );
+        assert_eq!(Closed.allowed_transitions(&Role::Moderator), vec![Reopened]);
+        assert_eq!(Cancelled.allowed_transitions(&Role::Admin), vec![Reopened]);
+        assert!(Merged.allowed_transitions(&Role::Admin).is_empty());
+    }
+
+    #[test]
+    fn users_can_only_cancel_or_reopen() {
+        use TicketStatus::*;
+
+        assert_eq!(Open.allowed_transitions(&Role::User), vec![Cancelled]);
+        assert_eq!(InProgress.allowed_transitions(&Role::User), vec![Cancelled]);
+        assert_eq!(Closed.allowed_transitions(&Role::User), vec![Reopened]);
+        assert!(Merged.allowed_transitions(&Role::User).is_empty());
+        assert!(!Open.can_transition_to(&Closed, &Role::User));
+        assert!(Open.can_transition_to(&Closed, &Role::Moderator));
+        assert!(!Closed.can_transition_to(&Merged, &Role::Admin));
+    }
+
+    #[test]
+    fn sla_without_targets_is_on_track() {
+        let status = SlaStatus::evaluate(at(0), None, None, None, None, at(1000));
+
+        assert_eq!(status, SlaStatus::OnTrack);
+    }
+
+    #[test]
+    fn sla_is_at_risk_from_80_percent_of_the_window() {
+        let evaluate = |now| SlaStatus::evaluate(at(0), Some(at(10)), None, None, None, now);
+
+        assert_eq!(evaluate(at(7)), SlaStatus::OnTrack);
+        assert_eq!(evaluate(at(8)), SlaStatus::AtRisk);
+        assert_eq!(evaluate(at(10)), SlaStatus::AtRisk);
+        assert_eq!(evaluate(at(10) + Duration::seconds(1)), SlaStatus::Breached);
+    }
+
+    #[test]
+    fn sla_is_breached_when_met_late() {
+        let late = SlaStatus::evaluate(at(0), Some(at(10)), None, Some(at(11)), None, at(12));
+        let on_time = SlaStatus::evaluate(at(0), Some(at(10)), None, Some(at(10)), None, at(12));
+
+        assert_eq!(late, SlaStatus::Breached);
+        assert_eq!(on_time, SlaStatus::OnTrack);
+    }
+
+    #[test]
+    fn sla_counts_closing_as_the_first_response() {
+        let status =
+            SlaStatus::evaluate(at(0), Some(at(10)), Some(at(20)), None, Some(at(9)), at(30));
+
+        assert_eq!(status, SlaStatus::OnTrack);
+    }
+
+    #[test]
+    fn sla_checks_the_resolution_target() {
+        let evaluate =
+            |closed_at, now| SlaStatus::evaluate(at(0), None, Some(at(20)), None, closed_at, now);
+
+        assert_eq!(evaluate(None, at(15)), SlaStatus::OnTrack);
+        assert_eq!(evaluate(None, at(16)), SlaStatus::AtRisk);
+        assert_eq!(evaluate(None, at(21)), SlaStatus::Breached);
+        assert_eq!(evaluate(Some(at(21)), at(22)), SlaStatus::Breached);
+        assert_eq!(evaluate(Some(at(19)), at(22)), SlaStatus::OnTrack);
+    }
+
+    #[test]
+    fn snippets_are_escaped_before_highlighting() {
+        let snippet = "<img src=x onerror=\"alert('x')\"> the \u{2}printer\u{3} & scanner";
+
+        assert_eq!(
+            TicketSearchResult::highlight(snippet),
+            "&lt;img src=x onerror=&quot;alert(&#39;x&#39;)&quot;&gt; the <mark>printer</mark> &amp; scanner"
+        );
+    }
+
+    #[test]
+    fn ticket_numbers_round_trip() {
+        let prefix = Config::ticket_number_prefix();
+        let number = TicketNumber(123);
+
+        assert_eq!(number.to_string(), format!("{prefix}000123"));
+        assert_eq!(number.to_string().parse(), Ok(number));
+        assert_eq!(
+            TicketNumber(1234567).to_string().parse(),
+            Ok(TicketNumber(1234567))
+        );
+    }
+
+    #[test]
+    fn ticket_numbers_parse_without_prefix_or_case() {
+        let prefix = Config::ticket_number_prefix();
+
+        assert_eq!("42".parse(), Ok(TicketNumber(42)));
+        assert_eq!(
+            format!("{}000042", prefix.to_lowercase()).parse(),
+            Ok(TicketNumber(42))
+        );
+        assert!(format!("{prefix}abc").parse::<TicketNumber>().is_err());
+        assert!("".parse::<TicketNumber>().is_err());
+    }
+
+    #[test]
+    fn update_payloads_tell_a_missing_category_from_null() {
+        let id = Uuid::now_v7();
+        let category = Uuid::now_v7();
+        let parse = |json: String| serde_json::from_str::<UpdateTicketPayload>(&json).unwrap();
+
+        assert_eq!(parse(format!(r#"{{"id":"{id}"}}"#)).category, None);
+        assert_eq!(
+            parse(format!(r#"{{"id":"{id}","category":null}}"#)).category,
+            Some(None)
+        );
+        assert_eq!(
+            parse(format!(r#"{{"id":"{id}","category":"{category}"}}"#)).category,
+            Some(Some(category))
+        );
     }
 }
@@ -0,0 +1,284 @@
+use super::ticket::{RequesterInfo, Ticket};
+use crate::{
+    database::{
+        repositories::ticket_event_repository::{TicketEventRepository, TicketEventRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::{FromRow, Type};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(ToSchema, PartialEq, Clone, Serialize, Deserialize, Type, Debug)]
+#[serde(rename_all(serialize = "lowercase", deserialize = "lowercase"))]
+#[sqlx(type_name = "ticket_event_kind", rename_all = "lowercase")]
+pub enum TicketEventKind {
+    Created,
+    Updated,
+    Deleted,
+    Restored,
+    Purged,
+}
+
+/// A single entry of the audit history of a ticket.
+///
+/// Updates record one event per changed field, with the old and new values
+/// as text. Creation, deletion, restoration and purge events have no field.
+/// Purges done by the retention task have no actor.
+#[derive(ToSchema, FromRow, Serialize, Deserialize)]
+pub struct TicketEvent {
+    pub id: Uuid,
+    pub ticket_id: Uuid,
+    pub actor: Option<Uuid>,
+    pub kind: TicketEventKind,
+    pub field: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct TicketEventPublic {
+    pub id: Uuid,
+    pub ticket_id: Uuid,
+    pub actor: Option<RequesterInfo>,
+    pub kind: TicketEventKind,
+    pub field: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl TicketEvent {
+    fn new(ticket_id: Uuid, actor: Option<Uuid>, kind: TicketEventKind) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            ticket_id,
+            actor,
+            kind,
+            field: None,
+            old_value: None,
+            new_value: None,
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub fn created(ticket_id: Uuid, actor: Uuid) -> Self {
+        Self::new(ticket_id, Some(actor), TicketEventKind::Created)
+    }
+
+    pub fn deleted(ticket_id: Uuid, actor: Uuid) -> Self {
+        Self::new(ticket_id, Some(actor), TicketEventKind::Deleted)
+    }
+
+    pub fn restored(ticket_id: Uuid, actor: Uuid) -> Self {
+        Self::new(ticket_id, Some(actor), TicketEventKind::Restored)
+    }
+
+    pub fn purged(ticket_id: Uuid, actor: Option<Uuid>) -> Self {
+        Self::new(ticket_id, actor, TicketEventKind::Purged)
+    }
+
+    pub fn updated(
+        ticket_id: Uuid,
+        actor: Uuid,
+        field: &str,
+        old_value: Option<String>,
+        new_value: Option<String>,
+    ) -> Self {
+        Self {
+            field: Some(field.to_string()),
+            old_value,
+            new_value,
+            ..Self::new(ticket_id, Some(actor), TicketEventKind::Updated)
+        }
+    }
+
+    /// Returns one update event per recorded field that differs between the ticket
+    /// before and after an update. Tags are recorded when they are replaced.
+    pub fn changes(before: &Ticket, after: &Ticket, actor: Uuid) -> Vec<Self> {
+        let id = |id: Uuid| id.to_string();
+
+        let fields = [
+            (
+                "title",
+                Some(before.title.clone()),
+                Some(after.title.clone()),
+            ),
+            (
+                "description",
+                Some(before.description.clone()),
+                Some(after.description.clone()),
+            ),
+            (
+                "requester",
+                Some(id(before.requester)),
+                Some(id(after.requester)),
+            ),
+            (
+                "status",
+                Some(before.status.to_string()),
+                Some(after.status.to_string()),
+            ),
+            (
+                "closed_by",
+                before.closed_by.map(id),
+                after.closed_by.map(id),
+            ),
+            (
+                "priority",
+                Some(before.priority.to_string()),
+                Some(after.priority.to_string()),
+            ),
+            ("category", before.category.map(id), after.category.map(id)),
+            ("solution", before.solution.clone(), after.solution.clone()),
+        ];
+
+        fields
+            .into_iter()
+            .filter(|(_, old_value, new_value)| old_value != new_value)
+            .map(|(field, old_value, new_value)| {
+                Self::updated(before.id, actor, field, old_value, new_value)
+            })
+            .collect()
+    }
+
+    pub async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<TicketEventPublic>, ApiError> {
+        TicketEventRepositoryImpl::find_by_ticket(state, ticket_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ticket::{TicketPriority, TicketStatus};
+
+    fn ticket() -> Ticket {
+        let now = Utc::now().naive_utc();
+
+        Ticket {
+            id: Uuid::now_v7(),
+            title: "Printer is offline".to_string(),
+            description: "The printer on the second floor is offline.".to_string(),
+            requester: Uuid::now_v7(),
+            status: TicketStatus::Open,
+            priority: TicketPriority::Normal,
+            category: None,
+            assignee: None,
+            closed_by: None,
+            solution: None,
+            created_at: now,
+            updated_at: now,
+            closed_at: None,
+            response_due_at: None,
+            due_at: None,
+            responded_at: None,
+            deleted_at: None,
+            deleted_by: None,
+            merged_into: None,
+        }
+    }
+
+    #[test]
+    fn unchanged_tickets_record_no_events() {
+        let before = ticket();
+        let after = Ticket {
+            updated_at: Utc::now().naive_utc(),
+            ..before.clone()
+        };
+
+        assert!(TicketEvent::changes(&before, &after, Uuid::now_v7()).is_empty());
+    }
+
+    #[test]
+    fn each_changed_field_records_its_old_and_new_values() {
+        let actor = Uuid::now_v7();
+        let category = Uuid::now_v7();
+        let before = ticket();
+        let after = Ticket {
+            title: "Printer is back".to_string(),
+            priority: TicketPriority::High,
+            category: Some(category),
+            ..before.clone()
+        };
+
+        let events = TicketEvent::changes(&before, &after, actor);
+        let changes: Vec<_> = events
+            .iter()
+            .map(|event| {
+                (
+                    event.field.as_deref(),
+                    event.old_value.as_deref(),
+                    event.new_value.as_deref(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    Some("title"),
+                    Some("Printer is offline"),
+                    Some("Printer is back")
+                ),
+                (Some("priority"), Some("Normal"), Some("High")),
+                (Some("category"), None, Some(category.to_string().as_str())),
+            ]
+        );
+        assert!(events.iter().all(|event| event.ticket_id == before.id
+            && event.actor == Some(actor)
+            && event.kind == TicketEventKind::Updated));
+    }
+
+    #[test]
+    fn closing_records_the_status_closer_and_solution() {
+        let actor = Uuid::now_v7();
+        let before = ticket();
+        let after = Ticket {
+            status: TicketStatus::Closed,
+            closed_by: Some(actor),
+            solution: Some("Restarted the printer.".to_string()),
+            ..before.clone()
+        };
+
+        let events = TicketEvent::changes(&before, &after, actor);
+        let fields: Vec<_> = events
+            .iter()
+            .filter_map(|event| event.field.as_deref())
+            .collect();
+
+        assert_eq!(fields, vec!["status", "closed_by", "solution"]);
+        assert_eq!(events[0].old_value.as_deref(), Some("Open"));
+        assert_eq!(events[0].new_value.as_deref(), Some("Closed"));
+        assert_eq!(events[1].old_value, None);
+        assert_eq!(events[1].new_value, Some(actor.to_string()));
+        assert_eq!(events[2].old_value, None);
+    }
+
+    #[test]
+    fn clearing_the_category_records_its_old_value() {
+        let category = Uuid::now_v7();
+        let before = Ticket {
+            category: Some(category),
+            ..ticket()
+        };
+        let after = Ticket {
+            category: None,
+            ..before.clone()
+        };
+
+        let events = TicketEvent::changes(&before, &after, Uuid::now_v7());
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].field.as_deref(), Some("category"));
+        assert_eq!(events[0].old_value, Some(category.to_string()));
+        assert_eq!(events[0].new_value, None);
+    }
+}
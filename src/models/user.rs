@@ -1,4 +1,8 @@
-use super::DeletePayload;
+use super::{
+    pagination::{Page, SortDirection},
+    precondition::Preconditions,
+    DeletePayload,
+};
 use crate::{
     database::{
         repositories::user_repository::{UserRepository, UserRepositoryImpl},
@@ -9,7 +13,7 @@ use crate::{
 use chrono::{NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::prelude::{FromRow, Type};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
 
@@ -79,6 +83,45 @@ pub struct UserPublic {
     pub updated_at: NaiveDateTime,
 }
 
+#[derive(ToSchema, PartialEq, Clone, Copy, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all(serialize = "snake_case", deserialize = "snake_case"))]
+pub enum UserSortField {
+    #[default]
+    CreatedAt,
+    Username,
+}
+
+impl UserSortField {
+    pub fn column(&self) -> &'static str {
+        match self {
+            UserSortField::CreatedAt => "created_at",
+            UserSortField::Username => "username",
+        }
+    }
+}
+
+#[derive(Deserialize, IntoParams, Default)]
+pub struct UserFilters {
+    /// Only list users with the role.
+    pub role: Option<Role>,
+    /// Only list users with the status.
+    pub status: Option<Status>,
+    /// Only list users created at or after this date (UTC).
+    pub created_after: Option<NaiveDateTime>,
+    /// Only list users created before this date (UTC).
+    pub created_before: Option<NaiveDateTime>,
+    /// Only list users whose username, email or name contains the text.
+    pub q: Option<String>,
+    /// Defaults to `created_at`.
+    pub sort: Option<UserSortField>,
+    /// Defaults to `desc`.
+    pub direction: Option<SortDirection>,
+    /// The `next_cursor` of the previous page.
+    pub cursor: Option<Uuid>,
+    /// Defaults to 20, up to 100.
+    pub limit: Option<i64>,
+}
+
 #[derive(Deserialize, Serialize, ToSchema, Validate)]
 pub struct RegisterPayload {
     #[validate(length(
@@ -216,8 +259,11 @@ impl User {
         Ok(UserRepositoryImpl::count(state).await?)
     }
 
-    pub async fn find_all(state: &AppState) -> Result<Vec<UserPublic>, ApiError> {
-        Ok(UserRepositoryImpl::find_all(state).await?)
+    pub async fn find_all(
+        state: &AppState,
+        filters: &UserFilters,
+    ) -> Result<Page<UserPublic>, ApiError> {
+        UserRepositoryImpl::find_all(state, filters).await
     }
 
     pub async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<UserPublic>, ApiError> {
@@ -228,11 +274,19 @@ impl User {
         Ok(UserRepositoryImpl::create(state, payload).await?)
     }
 
-    pub async fn update(state: &AppState, payload: &UpdateUserPayload) -> Result<Uuid, ApiError> {
-        Ok(UserRepositoryImpl::update(state, payload).await?)
+    pub async fn update(
+        state: &AppState,
+        payload: &UpdateUserPayload,
+        preconditions: &Preconditions,
+    ) -> Result<Uuid, ApiError> {
+        Ok(UserRepositoryImpl::update(state, payload, preconditions).await?)
     }
 
-    pub async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError> {
-        Ok(UserRepositoryImpl::delete(state, payload).await?)
+    pub async fn delete(
+        state: &AppState,
+        payload: &DeletePayload,
+        preconditions: &Preconditions,
+    ) -> Result<(), ApiError> {
+        Ok(UserRepositoryImpl::delete(state, payload, preconditions).await?)
     }
 }
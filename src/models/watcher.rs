@@ -0,0 +1,58 @@
+use super::ticket::RequesterInfo;
+use crate::{
+    database::{
+        repositories::watcher_repository::{WatcherRepository, WatcherRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A subscription of a user to the updates of a ticket.
+///
+/// Watchers can read the ticket even when they did not request it.
+#[derive(ToSchema, FromRow, Serialize, Deserialize)]
+pub struct Watcher {
+    pub ticket_id: Uuid,
+    pub user_id: Uuid,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Default)]
+pub struct WatchPayload {
+    /// The user to subscribe, defaults to the current user.
+    pub user: Option<Uuid>,
+}
+
+impl Watcher {
+    pub fn new(ticket_id: Uuid, user_id: Uuid) -> Self {
+        Self {
+            ticket_id,
+            user_id,
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<RequesterInfo>, ApiError> {
+        WatcherRepositoryImpl::find_by_ticket(state, ticket_id).await
+    }
+
+    pub async fn subscribe(state: &AppState, watcher: &Watcher) -> Result<(), ApiError> {
+        WatcherRepositoryImpl::create(state, watcher).await
+    }
+
+    pub async fn unsubscribe(
+        state: &AppState,
+        ticket_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), ApiError> {
+        WatcherRepositoryImpl::delete(state, ticket_id, user_id).await
+    }
+}
@@ -0,0 +1,121 @@
+use super::{report::ReportQuery, ticket::RequesterInfo, DeletePayload};
+use crate::{
+    database::{
+        repositories::worklog_repository::{WorklogRepository, WorklogRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(ToSchema, FromRow, Serialize, Deserialize)]
+pub struct Worklog {
+    pub id: Uuid,
+    pub ticket_id: Uuid,
+    pub agent: Uuid,
+    pub minutes: i32,
+    pub note: Option<String>,
+    pub work_date: NaiveDate,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(ToSchema, Clone, Serialize, Deserialize)]
+pub struct WorklogPublic {
+    pub id: Uuid,
+    pub ticket_id: Uuid,
+    pub agent: RequesterInfo,
+    pub minutes: i32,
+    pub note: Option<String>,
+    pub work_date: NaiveDate,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate)]
+pub struct CreateWorklogPayload {
+    #[validate(range(min = 1, max = 1440, message = "Minutes must be between 1 and 1440."))]
+    pub minutes: i32,
+    #[validate(length(max = 1000, message = "Note must be up to 1000 chars."))]
+    pub note: Option<String>,
+    /// The day the work was done. Defaults to today (UTC).
+    pub work_date: Option<NaiveDate>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Validate)]
+pub struct UpdateWorklogPayload {
+    pub id: Uuid,
+    #[validate(range(min = 1, max = 1440, message = "Minutes must be between 1 and 1440."))]
+    pub minutes: Option<i32>,
+    #[validate(length(max = 1000, message = "Note must be up to 1000 chars."))]
+    pub note: Option<String>,
+    pub work_date: Option<NaiveDate>,
+}
+
+/// The time an agent logged in a period.
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct WorklogReportEntry {
+    pub agent: RequesterInfo,
+    /// The first day of the period.
+    pub period: NaiveDate,
+    pub minutes: i64,
+    /// Number of distinct tickets worked on.
+    pub tickets: i64,
+    /// Number of worklog entries.
+    pub entries: i64,
+}
+
+impl Worklog {
+    pub fn new(ticket_id: Uuid, agent: Uuid, payload: &CreateWorklogPayload) -> Self {
+        let now = Utc::now().naive_utc();
+
+        Self {
+            id: Uuid::now_v7(),
+            ticket_id,
+            agent,
+            minutes: payload.minutes,
+            note: payload.note.clone(),
+            work_date: payload.work_date.unwrap_or(now.date()),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub async fn find_by_ticket(
+        state: &AppState,
+        ticket_id: Uuid,
+    ) -> Result<Vec<WorklogPublic>, ApiError> {
+        WorklogRepositoryImpl::find_by_ticket(state, ticket_id).await
+    }
+
+    pub async fn find_by_id(state: &AppState, id: Uuid) -> Result<Option<Self>, ApiError> {
+        WorklogRepositoryImpl::find_by_id(state, id).await
+    }
+
+    pub async fn create(state: &AppState, worklog: &Worklog) -> Result<(), ApiError> {
+        WorklogRepositoryImpl::create(state, worklog).await
+    }
+
+    pub async fn update(
+        state: &AppState,
+        payload: &UpdateWorklogPayload,
+    ) -> Result<Uuid, ApiError> {
+        WorklogRepositoryImpl::update(state, payload).await
+    }
+
+    pub async fn delete(state: &AppState, payload: &DeletePayload) -> Result<(), ApiError> {
+        WorklogRepositoryImpl::delete(state, payload).await
+    }
+
+    pub async fn report(
+        state: &AppState,
+        query: &ReportQuery,
+    ) -> Result<Vec<WorklogReportEntry>, ApiError> {
+        WorklogRepositoryImpl::report(state, query).await
+    }
+}
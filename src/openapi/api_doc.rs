@@ -1,5 +1,8 @@
 use crate::{
-    controllers::{auth, export, migrations, status, ticket, user},
+    controllers::{
+        api_token, attachment, auth, category, comment, export, link, migrations, oidc, rating,
+        report, status, tag, ticket, user, watcher, worklog,
+    },
     models::{status::Status, ticket::Ticket, user::User},
 };
 use serde::Serialize;
@@ -32,6 +35,11 @@ use utoipa::{
 
         // Auth
         auth::login,
+        auth::refresh,
+        auth::logout,
+        oidc::login,
+        oidc::callback,
+        auth::jwks,
         auth::register,
         auth::verify,
 
@@ -42,14 +50,80 @@ use utoipa::{
         user::create_user,
         user::update_user,
         user::delete_user,
+        user::revoke_user_sessions,
+
+        // API tokens
+        api_token::find_api_tokens,
+        api_token::create_api_token,
+        api_token::delete_api_token,
 
         // Tickets
         ticket::count_tickets,
         ticket::find_ticket_by_id,
         ticket::find_all_tickets,
+        ticket::search_tickets,
         ticket::create_ticket,
         ticket::update_ticket,
         ticket::delete_ticket,
+        ticket::find_trash,
+        ticket::restore_ticket,
+        ticket::purge_ticket,
+        ticket::find_ticket_transitions,
+        ticket::find_ticket_history,
+        ticket::assign_ticket,
+        ticket::unassign_ticket,
+        ticket::claim_ticket,
+        ticket::merge_tickets,
+        ticket::find_my_queue,
+        ticket::find_unassigned_queue,
+
+        // Comments
+        comment::find_comments,
+        comment::create_comment,
+        comment::update_comment,
+        comment::delete_comment,
+
+        // Attachments
+        attachment::find_attachments,
+        attachment::upload_attachment,
+        attachment::download_attachment,
+        attachment::delete_attachment,
+
+        // Categories
+        category::find_all_categories,
+        category::find_category_by_id,
+        category::create_category,
+        category::update_category,
+        category::delete_category,
+
+        // Tags
+        tag::find_all_tags,
+        tag::find_ticket_tags,
+        tag::add_ticket_tags,
+        tag::remove_ticket_tags,
+
+        // Watchers
+        watcher::find_watchers,
+        watcher::subscribe,
+        watcher::unsubscribe,
+
+        // Links
+        link::find_links,
+        link::create_link,
+        link::delete_link,
+
+        // Ratings
+        rating::rate_ticket,
+
+        // Worklogs
+        worklog::find_worklogs,
+        worklog::create_worklog,
+        worklog::update_worklog,
+        worklog::delete_worklog,
+
+        // Reports
+        report::worklog_report,
+        report::rating_report,
 
         // Export
         export::ticket_to_pdf,
@@ -64,7 +138,16 @@ use utoipa::{
         (name = "Migrations", description = "Migrations endpoints"),
         (name = "Auth", description = "Auth endpoints"),
         (name = "Users", description = "Users endpoints"),
+        (name = "API tokens", description = "API tokens endpoints"),
         (name = "Tickets", description = "Tickets endpoints"),
+        (name = "Comments", description = "Ticket comments endpoints"),
+        (name = "Attachments", description = "Ticket attachments endpoints"),
+        (name = "Categories", description = "Ticket categories endpoints"),
+        (name = "Tags", description = "Ticket tags endpoints"),
+        (name = "Watchers", description = "Ticket watchers endpoints"),
+        (name = "Links", description = "Ticket links endpoints"),
+        (name = "Worklogs", description = "Ticket worklogs endpoints"),
+        (name = "Reports", description = "Reports endpoints"),
     )
 )]
 pub struct ApiDoc;
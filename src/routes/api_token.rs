@@ -0,0 +1,14 @@
+use crate::{controllers::api_token, database::AppState};
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+pub fn create_routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/",
+            get(api_token::find_api_tokens)
+                .post(api_token::create_api_token)
+                .delete(api_token::delete_api_token),
+        )
+        .with_state(state)
+}
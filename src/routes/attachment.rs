@@ -0,0 +1,20 @@
+use crate::{config::Config, controllers::attachment, database::AppState};
+use axum::{extract::DefaultBodyLimit, routing::get, Router};
+use std::sync::Arc;
+
+pub fn create_routes(state: Arc<AppState>) -> Router {
+    // The multipart envelope adds some bytes on top of the file itself.
+    let body_limit = Config::attachment_limits().max_size + 64 * 1024;
+
+    Router::new()
+        .route(
+            "/",
+            get(attachment::find_attachments).post(attachment::upload_attachment),
+        )
+        .route(
+            "/{attachment_id}",
+            get(attachment::download_attachment).delete(attachment::delete_attachment),
+        )
+        .layer(DefaultBodyLimit::max(body_limit))
+        .with_state(state)
+}
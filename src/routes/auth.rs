@@ -1,11 +1,24 @@
-use crate::{controllers::auth, database::AppState};
-use axum::{routing::post, Router};
+use crate::{
+    controllers::{auth, oidc},
+    database::AppState,
+    middlewares::authentication::authenticate,
+};
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
 use std::sync::Arc;
 
 pub fn create_routes(state: Arc<AppState>) -> Router {
     Router::new()
+        .route("/logout", post(auth::logout))
+        .route_layer(middleware::from_fn_with_state(state.clone(), authenticate))
         .route("/login", post(auth::login))
+        .route("/refresh", post(auth::refresh))
         .route("/register", post(auth::register))
         .route("/verify", post(auth::verify))
+        .route("/oidc/login", get(oidc::login))
+        .route("/oidc/callback", get(oidc::callback))
         .with_state(state)
 }
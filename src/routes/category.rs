@@ -0,0 +1,16 @@
+use crate::{controllers::category, database::AppState};
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+pub fn create_routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/{id}", get(category::find_category_by_id))
+        .route(
+            "/",
+            get(category::find_all_categories)
+                .post(category::create_category)
+                .put(category::update_category)
+                .delete(category::delete_category),
+        )
+        .with_state(state)
+}
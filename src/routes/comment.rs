@@ -0,0 +1,15 @@
+use crate::{controllers::comment, database::AppState};
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+pub fn create_routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/",
+            get(comment::find_comments)
+                .post(comment::create_comment)
+                .put(comment::update_comment)
+                .delete(comment::delete_comment),
+        )
+        .with_state(state)
+}
@@ -0,0 +1,13 @@
+use crate::{controllers::link, database::AppState};
+use axum::{
+    routing::{delete, get},
+    Router,
+};
+use std::sync::Arc;
+
+pub fn create_routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(link::find_links).post(link::create_link))
+        .route("/{link_id}", delete(link::delete_link))
+        .with_state(state)
+}
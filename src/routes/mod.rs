@@ -1,10 +1,21 @@
+pub mod api_token;
+pub mod attachment;
 pub mod auth;
+pub mod category;
+pub mod comment;
 pub mod export;
+pub mod link;
 pub mod migrations;
+pub mod rating;
+pub mod report;
 pub mod status;
 pub mod swagger;
+pub mod tag;
 pub mod ticket;
 pub mod user;
+pub mod watcher;
+pub mod well_known;
+pub mod worklog;
 
 use crate::{config::Config, database::AppState, middlewares::authentication::authenticate};
 use axum::{middleware, Router};
@@ -17,12 +28,16 @@ pub fn create_routes(state: Arc<AppState>) -> Router {
             Router::new()
                 .nest("/users", user::create_routes(state.clone()))
                 .nest("/tickets", ticket::create_routes(state.clone()))
+                .nest("/categories", category::create_routes(state.clone()))
+                .nest("/tags", tag::create_routes(state.clone()))
                 .nest("/export", export::create_routes(state.clone()))
+                .nest("/reports", report::create_routes(state.clone()))
                 .layer(middleware::from_fn_with_state(state.clone(), authenticate))
                 .nest("/auth", auth::create_routes(state.clone()))
                 .nest("/status", status::create_routes(state.clone()))
                 .nest("/migrations", migrations::create_routes(state.clone())),
         )
+        .nest("/.well-known", well_known::create_routes(state.clone()))
         .merge(swagger::swagger_routes())
         .layer(Config::cors())
 }
@@ -0,0 +1,9 @@
+use crate::{controllers::rating, database::AppState};
+use axum::{routing::post, Router};
+use std::sync::Arc;
+
+pub fn create_routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", post(rating::rate_ticket))
+        .with_state(state)
+}
@@ -0,0 +1,10 @@
+use crate::{controllers::report, database::AppState};
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+pub fn create_routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/worklogs", get(report::worklog_report))
+        .route("/ratings", get(report::rating_report))
+        .with_state(state)
+}
@@ -0,0 +1,20 @@
+use crate::{controllers::tag, database::AppState};
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+pub fn create_routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(tag::find_all_tags))
+        .with_state(state)
+}
+
+pub fn create_ticket_routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/",
+            get(tag::find_ticket_tags)
+                .post(tag::add_ticket_tags)
+                .delete(tag::remove_ticket_tags),
+        )
+        .with_state(state)
+}
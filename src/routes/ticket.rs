@@ -1,11 +1,27 @@
+use super::{attachment, comment, link, rating, tag, watcher, worklog};
 use crate::{controllers::ticket, database::AppState};
-use axum::{routing::get, Router};
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
 use std::sync::Arc;
 
 pub fn create_routes(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/count", get(ticket::count_tickets))
+        .route("/search", get(ticket::search_tickets))
+        .route("/trash", get(ticket::find_trash))
+        .route("/trash/{id}", delete(ticket::purge_ticket))
+        .route("/trash/{id}/restore", post(ticket::restore_ticket))
+        .route("/queue/mine", get(ticket::find_my_queue))
+        .route("/queue/unassigned", get(ticket::find_unassigned_queue))
         .route("/{id}", get(ticket::find_ticket_by_id))
+        .route("/{id}/transitions", get(ticket::find_ticket_transitions))
+        .route("/{id}/history", get(ticket::find_ticket_history))
+        .route("/{id}/assign", post(ticket::assign_ticket))
+        .route("/{id}/unassign", post(ticket::unassign_ticket))
+        .route("/{id}/claim", post(ticket::claim_ticket))
+        .route("/{id}/merge", post(ticket::merge_tickets))
         .route(
             "/",
             get(ticket::find_all_tickets)
@@ -13,5 +29,15 @@ pub fn create_routes(state: Arc<AppState>) -> Router {
                 .put(ticket::update_ticket)
                 .delete(ticket::delete_ticket),
         )
-        .with_state(state)
+        .with_state(state.clone())
+        .nest("/{id}/comments", comment::create_routes(state.clone()))
+        .nest(
+            "/{id}/attachments",
+            attachment::create_routes(state.clone()),
+        )
+        .nest("/{id}/tags", tag::create_ticket_routes(state.clone()))
+        .nest("/{id}/watchers", watcher::create_routes(state.clone()))
+        .nest("/{id}/links", link::create_routes(state.clone()))
+        .nest("/{id}/worklogs", worklog::create_routes(state.clone()))
+        .nest("/{id}/rating", rating::create_routes(state))
 }
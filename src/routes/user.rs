@@ -1,11 +1,16 @@
+use super::api_token;
 use crate::{controllers::user, database::AppState};
-use axum::{routing::get, Router};
+use axum::{
+    routing::{delete, get},
+    Router,
+};
 use std::sync::Arc;
 
 pub fn create_routes(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/count", get(user::count_users))
         .route("/{id}", get(user::find_user_by_id))
+        .route("/{id}/sessions", delete(user::revoke_user_sessions))
         .route(
             "/",
             get(user::find_all_users)
@@ -13,5 +18,6 @@ pub fn create_routes(state: Arc<AppState>) -> Router {
                 .put(user::update_user)
                 .delete(user::delete_user),
         )
-        .with_state(state)
+        .with_state(state.clone())
+        .nest("/me/tokens", api_token::create_routes(state))
 }
@@ -0,0 +1,13 @@
+use crate::{controllers::watcher, database::AppState};
+use axum::{
+    routing::{delete, get},
+    Router,
+};
+use std::sync::Arc;
+
+pub fn create_routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(watcher::find_watchers).post(watcher::subscribe))
+        .route("/{user_id}", delete(watcher::unsubscribe))
+        .with_state(state)
+}
@@ -0,0 +1,9 @@
+use crate::{controllers::auth, database::AppState};
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+pub fn create_routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/jwks.json", get(auth::jwks))
+        .with_state(state)
+}
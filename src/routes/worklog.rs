@@ -0,0 +1,15 @@
+use crate::{controllers::worklog, database::AppState};
+use axum::{routing::get, Router};
+use std::sync::Arc;
+
+pub fn create_routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route(
+            "/",
+            get(worklog::find_worklogs)
+                .post(worklog::create_worklog)
+                .put(worklog::update_worklog)
+                .delete(worklog::delete_worklog),
+        )
+        .with_state(state)
+}
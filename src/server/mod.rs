@@ -1,12 +1,39 @@
 use crate::{
+    config::Config,
     database::{connection::create_pool, AppState},
     errors::api_error::ApiError,
-    routes,
+    models::auth::revocation::RevocationCache,
+    routes, tasks,
+    utils::oidc::OidcClient,
 };
 use std::sync::Arc;
 use tracing::{error, info};
 
 pub async fn run() -> Result<(), ApiError> {
+    let jwt_keys = match Config::jwt_keys() {
+        Ok(keys) => {
+            info!(
+                "🔑 Signing JWTs with {:?} key {}, {} verification keys published",
+                keys.algorithm,
+                keys.kid,
+                keys.jwks.keys.len()
+            );
+            keys
+        }
+        Err(e) => {
+            error!("❌ Error loading the JWT keys: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    let oidc = Config::oidc().map(|settings| {
+        info!(
+            "🔐 Single sign-on with OpenID Connect provider {}",
+            settings.issuer
+        );
+        OidcClient::new(settings)
+    });
+
     let pool = match create_pool().await {
         Ok(pool) => {
             info!("✅ Connected to the database");
@@ -18,7 +45,27 @@ pub async fn run() -> Result<(), ApiError> {
         }
     };
 
-    let app = routes::create_routes(Arc::new(AppState { db: pool.clone() }));
+    let state = Arc::new(AppState {
+        db: pool.clone(),
+        storage: Config::storage(),
+        revocations: RevocationCache::default(),
+        jwt_keys,
+        oidc,
+    });
+
+    // The revocations are loaded before serving, so revoked tokens are never accepted.
+    match state.revocations.reload(&state).await {
+        Ok(()) => info!("✅ Loaded the token revocations"),
+        Err(e) => {
+            error!("❌ Error loading the token revocations: {e}");
+            std::process::exit(1);
+        }
+    }
+
+    tasks::trash::spawn_purge(state.clone());
+    tasks::revocation::spawn_sync(state.clone());
+
+    let app = routes::create_routes(state);
 
     let addr = std::env::var("HOST")?;
     let listener = match tokio::net::TcpListener::bind(&addr).await {
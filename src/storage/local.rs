@@ -0,0 +1,41 @@
+use super::Storage;
+use crate::errors::storage_error::StorageError;
+use std::{io::ErrorKind, path::PathBuf};
+use tokio::fs;
+
+/// Stores the files in a directory of the local filesystem.
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for LocalStorage {
+    async fn save(&self, key: &str, data: &[u8]) -> Result<(), StorageError> {
+        let path = self.root.join(key);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        fs::write(path, data).await?;
+        Ok(())
+    }
+
+    async fn load(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        Ok(fs::read(self.root.join(key)).await?)
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), StorageError> {
+        // Removing a file that is already gone is not an error.
+        match fs::remove_file(self.root.join(key)).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
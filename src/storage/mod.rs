@@ -0,0 +1,14 @@
+pub mod local;
+
+use crate::errors::storage_error::StorageError;
+
+/// Backend where the contents of the uploaded files are kept.
+///
+/// Files are addressed by a key generated by the API, while their metadata
+/// lives in the database.
+#[async_trait::async_trait]
+pub trait Storage: Send + Sync {
+    async fn save(&self, key: &str, data: &[u8]) -> Result<(), StorageError>;
+    async fn load(&self, key: &str) -> Result<Vec<u8>, StorageError>;
+    async fn remove(&self, key: &str) -> Result<(), StorageError>;
+}
@@ -0,0 +1,2 @@
+pub mod revocation;
+pub mod trash;
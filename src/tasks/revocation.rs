@@ -0,0 +1,27 @@
+use crate::{config::Config, database::AppState, models::auth::revocation::RevokedToken};
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{debug, error, info};
+
+/// Spawns the background task reloading the revocation cache from the database,
+/// after forgetting the revocations of expired tokens.
+pub fn spawn_sync(state: Arc<AppState>) {
+    let mut interval = tokio::time::interval(Config::revocation_sync_interval());
+
+    tokio::spawn(async move {
+        loop {
+            interval.tick().await;
+
+            match RevokedToken::purge_expired(&state, Utc::now().naive_utc()).await {
+                Ok(0) => debug!("No expired token revocations."),
+                Ok(count) => info!("🔑 Purged {count} expired token revocations"),
+                Err(e) => error!("❌ Error purging the token revocations: {e}"),
+            }
+
+            match state.revocations.reload(&state).await {
+                Ok(()) => debug!("Revocation cache reloaded."),
+                Err(e) => error!("❌ Error reloading the revocation cache: {e}"),
+            }
+        }
+    });
+}
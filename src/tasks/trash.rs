@@ -0,0 +1,26 @@
+use crate::{config::Config, database::AppState, models::ticket::Ticket};
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{debug, error, info};
+
+/// Spawns the background task purging the tickets kept in the trash past the retention period.
+pub fn spawn_purge(state: Arc<AppState>) {
+    let Some(retention) = Config::trash_retention() else {
+        info!("🗑️ Trash retention disabled, deleted tickets are kept until purged");
+        return;
+    };
+    let mut interval = tokio::time::interval(Config::trash_purge_interval());
+
+    tokio::spawn(async move {
+        loop {
+            interval.tick().await;
+
+            let deleted_before = Utc::now().naive_utc() - retention;
+            match Ticket::purge_expired(&state, deleted_before).await {
+                Ok(ids) if ids.is_empty() => debug!("No expired tickets in the trash."),
+                Ok(ids) => info!("🗑️ Purged {} expired tickets from the trash", ids.len()),
+                Err(e) => error!("❌ Error purging the trash: {e}"),
+            }
+        }
+    });
+}
@@ -1,8 +1,12 @@
 use crate::errors::api_error::ApiError;
 use argon2::{
-    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    password_hash::{
+        rand_core::{OsRng, RngCore},
+        PasswordHash, PasswordHasher, PasswordVerifier, SaltString,
+    },
     Argon2,
 };
+use sha2::{Digest, Sha256};
 use tracing::error;
 
 /// Encrypt a password.
@@ -33,3 +37,18 @@ pub fn verify_password(plain_password: &str, hash: &str) -> Result<bool, ApiErro
         })
         .map(|_| true)
 }
+
+/// Generates an opaque random token, such as a refresh token.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Hashes an opaque token for storage.
+///
+/// Unlike passwords, tokens are random and long enough to be hashed without salt, keeping them searchable.
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
@@ -1,9 +1,19 @@
-use crate::{errors::api_error::ApiError, models::auth::token::Claims};
+use super::keys::JwtKeys;
+use crate::{
+    errors::{api_error::ApiError, auth_error::AuthError},
+    models::auth::token::Claims,
+};
 use chrono::{Duration, TimeDelta, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, TokenData, Validation};
+use jsonwebtoken::{decode, decode_header, encode, Header, TokenData, Validation};
 use std::env;
+use uuid::Uuid;
 
-pub fn generate_jwt(username: &str, role: &str) -> Result<String, ApiError> {
+pub fn generate_jwt(
+    keys: &JwtKeys,
+    username: &str,
+    role: &str,
+    session_id: Uuid,
+) -> Result<String, ApiError> {
     let now = Utc::now();
     let expire: TimeDelta = Duration::seconds(
         env::var("JWT_EXPIRATION_TIME")?
@@ -19,33 +29,25 @@ pub fn generate_jwt(username: &str, role: &str) -> Result<String, ApiError> {
         sub: username.to_string(),
         exp,
         role,
+        jti: Uuid::now_v7().to_string(),
+        sid: session_id.to_string(),
     };
 
-    let token = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(env::var("JWT_SECRET")?.as_bytes()),
-    )?;
+    let mut header = Header::new(keys.algorithm);
+    header.kid = Some(keys.kid.clone());
+
+    let token = encode(&header, &claims, &keys.encoding_key)?;
 
     Ok(token)
 }
 
-pub fn validate_jwt(token: &str) -> Result<(), ApiError> {
-    let validation = Validation::default();
-    let _: TokenData<Claims> = decode(
-        token,
-        &DecodingKey::from_secret(env::var("JWT_SECRET")?.as_bytes()),
-        &validation,
-    )?;
-    Ok(())
-}
+pub fn decode_jwt(keys: &JwtKeys, token: String) -> Result<TokenData<Claims>, ApiError> {
+    let header = decode_header(&token)?;
 
-pub fn decode_jwt(token: String) -> Result<TokenData<Claims>, ApiError> {
-    let secret = env::var("JWT_SECRET")?;
+    // The key, and so its algorithm, is chosen from the `kid` of the token, never from its `alg`.
+    let (algorithm, key) = keys
+        .decoding_key(header.kid.as_deref())
+        .ok_or(ApiError::from(AuthError::InvalidToken))?;
 
-    Ok(decode::<Claims>(
-        &token,
-        &DecodingKey::from_secret(secret.as_ref()),
-        &Validation::default(),
-    )?)
+    Ok(decode::<Claims>(&token, key, &Validation::new(*algorithm))?)
 }
@@ -1,2 +1,4 @@
 pub mod hashing;
 pub mod jwt;
+pub mod keys;
+pub mod oidc;
@@ -0,0 +1,323 @@
+use crate::{
+    config::OidcSettings,
+    errors::oidc_error::OidcError,
+    models::{
+        auth::oidc::{IdTokenClaims, OidcLoginState},
+        user::Role,
+    },
+};
+use jsonwebtoken::{
+    decode, decode_header,
+    jwk::{Jwk, JwkSet},
+    Algorithm, DecodingKey, Validation,
+};
+use reqwest::{Client, Url};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+/// The endpoints of the identity provider, from its discovery document.
+#[derive(Deserialize, Clone)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+#[derive(Deserialize)]
+struct TokenErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+/// Signs users in with an OpenID Connect provider, using the authorization code flow with PKCE.
+///
+/// The discovery document and the keys of the provider are fetched on first use, so the API starts
+/// while the provider is unreachable, and the keys are fetched again when an ID token is signed with a new one.
+pub struct OidcClient {
+    pub settings: OidcSettings,
+    http: Client,
+    metadata: RwLock<Option<ProviderMetadata>>,
+    jwks: RwLock<JwkSet>,
+}
+
+impl OidcClient {
+    pub fn new(settings: OidcSettings) -> Self {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("Error building the OpenID Connect HTTP client");
+
+        Self {
+            settings,
+            http,
+            metadata: RwLock::new(None),
+            jwks: RwLock::new(JwkSet { keys: Vec::new() }),
+        }
+    }
+
+    /// Returns the URL redirecting the user to the provider to sign in.
+    pub async fn authorization_url(
+        &self,
+        login_state: &OidcLoginState,
+        login_hint: Option<&str>,
+    ) -> Result<Url, OidcError> {
+        let metadata = self.metadata().await?;
+
+        let mut params = vec![
+            ("response_type", "code"),
+            ("client_id", self.settings.client_id.as_str()),
+            ("redirect_uri", self.settings.redirect_uri.as_str()),
+            ("scope", self.settings.scopes.as_str()),
+            ("state", login_state.state.as_str()),
+            ("nonce", login_state.nonce.as_str()),
+            ("code_challenge_method", "S256"),
+        ];
+        let code_challenge = login_state.code_challenge();
+        params.push(("code_challenge", &code_challenge));
+        if let Some(login_hint) = login_hint {
+            params.push(("login_hint", login_hint));
+        }
+
+        Url::parse_with_params(&metadata.authorization_endpoint, &params).map_err(|e| {
+            OidcError::ProviderUnavailable(format!("invalid authorization endpoint: {e}"))
+        })
+    }
+
+    /// Exchanges an authorization code for the ID token of the user.
+    pub async fn exchange_code(
+        &self,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<String, OidcError> {
+        let metadata = self.metadata().await?;
+
+        let mut request = self.http.post(&metadata.token_endpoint).form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.settings.redirect_uri.as_str()),
+            ("client_id", self.settings.client_id.as_str()),
+            ("code_verifier", code_verifier),
+        ]);
+        if let Some(client_secret) = &self.settings.client_secret {
+            request = request.basic_auth(&self.settings.client_id, Some(client_secret));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| OidcError::ProviderUnavailable(e.to_string()))?;
+
+        if response.status().is_client_error() {
+            let reason = match response.json::<TokenErrorResponse>().await {
+                Ok(error) => error.error_description.unwrap_or(error.error),
+                Err(_) => String::from("the authorization code was rejected"),
+            };
+            return Err(OidcError::Denied(reason));
+        }
+
+        let response: TokenResponse = read_json(response).await?;
+
+        Ok(response.id_token)
+    }
+
+    /// Verifies the signature, issuer, audience, expiration and nonce of an ID token, returning its claims.
+    pub async fn verify_id_token(
+        &self,
+        id_token: &str,
+        nonce: &str,
+    ) -> Result<IdTokenClaims, OidcError> {
+        let invalid = |reason: &str| OidcError::InvalidIdToken(reason.to_string());
+
+        let header = decode_header(id_token).map_err(|e| invalid(&e.to_string()))?;
+
+        // Symmetric algorithms would accept tokens signed with the client secret, known to this API.
+        if matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            return Err(invalid("only asymmetric signing algorithms are supported"));
+        }
+
+        let jwk = self.jwk(header.kid.as_deref()).await?;
+        let decoding_key = DecodingKey::from_jwk(&jwk).map_err(|e| invalid(&e.to_string()))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.settings.issuer]);
+        validation.set_audience(&[&self.settings.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        let claims = decode::<IdTokenClaims>(id_token, &decoding_key, &validation)
+            .map_err(|e| invalid(&e.to_string()))?
+            .claims;
+
+        if claims.nonce.as_deref() != Some(nonce) {
+            return Err(invalid("the nonce does not match the login"));
+        }
+
+        Ok(claims)
+    }
+
+    /// Returns the highest role granted by the values of the role claim.
+    ///
+    /// Users without any mapped value are regular users, so roles removed at the provider are
+    /// removed on the next login. Without a role mapping, roles are managed in the API and `None` is returned.
+    pub fn role(&self, claims: &IdTokenClaims) -> Option<Role> {
+        if self.settings.role_mapping.is_empty() {
+            return None;
+        }
+
+        let values = claims.values(&self.settings.role_claim);
+
+        [Role::Admin, Role::Moderator]
+            .into_iter()
+            .find(|role| {
+                self.settings
+                    .role_mapping
+                    .iter()
+                    .any(|(value, mapped)| mapped == role && values.contains(&value.as_str()))
+            })
+            .or(Some(Role::User))
+    }
+
+    async fn metadata(&self) -> Result<ProviderMetadata, OidcError> {
+        if let Some(metadata) = self.metadata.read().await.as_ref() {
+            return Ok(metadata.clone());
+        }
+
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            self.settings.issuer.trim_end_matches('/')
+        );
+        debug!("Fetching the OpenID Connect discovery document from {url}");
+
+        let metadata: ProviderMetadata = self.get(&url).await?;
+
+        if metadata.issuer != self.settings.issuer {
+            return Err(OidcError::ProviderUnavailable(format!(
+                "the discovery document is for issuer {}",
+                metadata.issuer
+            )));
+        }
+
+        *self.metadata.write().await = Some(metadata.clone());
+
+        Ok(metadata)
+    }
+
+    /// Returns the key of the provider with the given ID, or its only key when tokens have no `kid`.
+    async fn jwk(&self, kid: Option<&str>) -> Result<Jwk, OidcError> {
+        let find = |jwks: &JwkSet| match kid {
+            Some(kid) => jwks.find(kid).cloned(),
+            None if jwks.keys.len() == 1 => jwks.keys.first().cloned(),
+            None => None,
+        };
+
+        if let Some(jwk) = find(&*self.jwks.read().await) {
+            return Ok(jwk);
+        }
+
+        // The provider may have rotated its keys since they were fetched.
+        let metadata = self.metadata().await?;
+        debug!(
+            "Fetching the OpenID Connect keys from {}",
+            metadata.jwks_uri
+        );
+
+        let jwks: JwkSet = self.get(&metadata.jwks_uri).await?;
+        let jwk = find(&jwks);
+        *self.jwks.write().await = jwks;
+
+        jwk.ok_or_else(|| {
+            warn!("No OpenID Connect key matches the kid {kid:?}");
+            OidcError::InvalidIdToken(String::from("unknown signing key"))
+        })
+    }
+
+    async fn get<T: DeserializeOwned>(&self, url: &str) -> Result<T, OidcError> {
+        let response = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| OidcError::ProviderUnavailable(e.to_string()))?;
+
+        read_json(response).await
+    }
+}
+
+async fn read_json<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, OidcError> {
+    response
+        .error_for_status()
+        .map_err(|e| OidcError::ProviderUnavailable(e.to_string()))?
+        .json()
+        .await
+        .map_err(|e| OidcError::ProviderUnavailable(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn client(role_mapping: &[(&str, Role)]) -> OidcClient {
+        OidcClient::new(OidcSettings {
+            issuer: String::from("https://idp.example.com"),
+            client_id: String::from("tickify"),
+            client_secret: None,
+            redirect_uri: String::from("http://127.0.0.1:8000/api/v1/auth/oidc/callback"),
+            scopes: String::from("openid email profile"),
+            role_claim: String::from("groups"),
+            role_mapping: role_mapping
+                .iter()
+                .map(|(value, role)| (value.to_string(), role.clone()))
+                .collect(),
+        })
+    }
+
+    fn claims(groups: &[&str]) -> IdTokenClaims {
+        serde_json::from_value(json!({
+            "iss": "https://idp.example.com",
+            "sub": "42",
+            "groups": groups,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn the_highest_mapped_role_is_granted() {
+        let client = client(&[
+            ("tickify-moderators", Role::Moderator),
+            ("tickify-admins", Role::Admin),
+        ]);
+
+        assert_eq!(
+            client.role(&claims(&["tickify-moderators", "tickify-admins"])),
+            Some(Role::Admin)
+        );
+        assert_eq!(
+            client.role(&claims(&["staff", "tickify-moderators"])),
+            Some(Role::Moderator)
+        );
+    }
+
+    #[test]
+    fn users_without_a_mapped_value_are_regular_users() {
+        let client = client(&[("tickify-admins", Role::Admin)]);
+
+        assert_eq!(client.role(&claims(&["staff"])), Some(Role::User));
+        assert_eq!(client.role(&claims(&[])), Some(Role::User));
+    }
+
+    #[test]
+    fn roles_are_kept_without_a_mapping() {
+        assert_eq!(client(&[]).role(&claims(&["tickify-admins"])), None);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::{
+        auth::access::AccessControl,
+        ticket::{Ticket, TicketPublic, TicketVisibility},
+        user::Role,
+    },
+};
+use tracing::error;
+use uuid::Uuid;
+
+/// Ensures the current user can read the ticket and everything attached to it.
+///
+/// Admins and moderators can access every ticket, while regular users can only
+/// access the tickets they requested or watch. Tickets the user cannot see are not found.
+pub async fn require_ticket_access(
+    state: &AppState,
+    access: &AccessControl,
+    ticket_id: Uuid,
+) -> Result<TicketPublic, ApiError> {
+    let visibility = TicketVisibility::of(access.user());
+
+    match Ticket::find_by_id(state, ticket_id, &visibility).await? {
+        Some(ticket) => Ok(ticket),
+        None => {
+            error!("No ticket found with id: {ticket_id} visible to: {visibility:?}");
+            Err(ApiError::NotFound)
+        }
+    }
+}
+
+/// Ensures the current user can change the ticket and everything attached to it.
+///
+/// Watchers can only read the tickets they watch, so only the requester,
+/// moderators and admins can change a ticket.
+pub async fn require_ticket_write_access(
+    state: &AppState,
+    access: &AccessControl,
+    ticket_id: Uuid,
+) -> Result<TicketPublic, ApiError> {
+    let ticket = require_ticket_access(state, access, ticket_id).await?;
+
+    access.require_owner_or_any_role(ticket.requester.id, &[Role::Admin, Role::Moderator])?;
+
+    Ok(ticket)
+}
@@ -0,0 +1,27 @@
+use crate::{
+    database::AppState,
+    errors::api_error::ApiError,
+    models::user::{Role, Status, User},
+};
+use tracing::error;
+use uuid::Uuid;
+
+/// Checks if the user can be assigned to tickets.
+///
+/// Only active moderators and admins can work on tickets.
+pub async fn is_user_assignable(state: &AppState, user_id: Uuid) -> Result<(), ApiError> {
+    let user = match User::find_by_id(state, user_id).await? {
+        Some(user) => user,
+        None => {
+            error!("User ID not found.");
+            return Err(ApiError::NotFound);
+        }
+    };
+
+    if user.status != Status::Active || user.role == Role::User {
+        error!("User '{}' cannot be assigned to tickets.", user.username);
+        Err(ApiError::InvalidAssignee)
+    } else {
+        Ok(())
+    }
+}
@@ -18,9 +18,9 @@ pub async fn user_exists(state: &AppState, user_id: Uuid) -> Result<(), ApiError
     }
 }
 
-/// Checks if the ticket is already registered according to his ID.
+/// Checks if the ticket is already registered according to his ID, and not in the trash.
 pub async fn ticket_exists(state: &AppState, ticket_id: Uuid) -> Result<(), ApiError> {
-    let exists = sqlx::query(r#"SELECT id FROM tickets WHERE id = $1;"#)
+    let exists = sqlx::query(r#"SELECT id FROM tickets WHERE id = $1 AND deleted_at IS NULL;"#)
         .bind(ticket_id)
         .fetch_optional(&state.db)
         .await?
@@ -33,3 +33,19 @@ pub async fn ticket_exists(state: &AppState, ticket_id: Uuid) -> Result<(), ApiE
         Ok(())
     }
 }
+
+/// Checks if the category is already registered according to its ID.
+pub async fn category_exists(state: &AppState, category_id: Uuid) -> Result<(), ApiError> {
+    let exists = sqlx::query(r#"SELECT id FROM categories WHERE id = $1;"#)
+        .bind(category_id)
+        .fetch_optional(&state.db)
+        .await?
+        .is_some();
+
+    if !exists {
+        error!("Category ID not found.");
+        Err(ApiError::NotFound)
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    database::{
+        repositories::link_repository::{LinkRepository, LinkRepositoryImpl},
+        AppState,
+    },
+    errors::api_error::ApiError,
+    models::link::{TicketLink, TicketLinkKind},
+};
+use tracing::error;
+
+/// Checks that the link can be added between its tickets.
+///
+/// A ticket cannot be linked to itself or twice in the same way, a child has a single parent,
+/// and parent and blocking links cannot form cycles.
+pub async fn validate_link(state: &AppState, link: &TicketLink) -> Result<(), ApiError> {
+    if link.source_id == link.target_id {
+        error!("Ticket {} cannot be linked to itself.", link.source_id);
+        return Err(ApiError::InvalidLink(String::from(
+            "A ticket cannot be linked to itself.",
+        )));
+    }
+
+    if LinkRepositoryImpl::exists(state, link.source_id, link.target_id, link.kind).await? {
+        error!(
+            "Tickets {} and {} are already linked.",
+            link.source_id, link.target_id
+        );
+        return Err(ApiError::InvalidLink(format!(
+            "The tickets are already linked as {}.",
+            link.kind
+        )));
+    }
+
+    if link.kind == TicketLinkKind::ParentOf
+        && LinkRepositoryImpl::find_by_ticket(state, link.target_id, None)
+            .await?
+            .iter()
+            .any(|other| other.relation == TicketLinkKind::ParentOf.relation(true))
+    {
+        error!("Ticket {} already has a parent.", link.target_id);
+        return Err(ApiError::InvalidLink(String::from(
+            "The target ticket already has a parent.",
+        )));
+    }
+
+    if matches!(link.kind, TicketLinkKind::ParentOf | TicketLinkKind::Blocks)
+        && LinkRepositoryImpl::is_reachable(state, link.target_id, link.source_id, link.kind)
+            .await?
+    {
+        error!(
+            "Linking {} to {} would form a cycle.",
+            link.source_id, link.target_id
+        );
+        return Err(ApiError::InvalidLink(format!(
+            "The link would form a cycle of {} links.",
+            link.kind
+        )));
+    }
+
+    Ok(())
+}
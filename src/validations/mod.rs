@@ -1,2 +1,6 @@
+pub mod access;
+pub mod assignment;
 pub mod existence;
+pub mod links;
+pub mod tags;
 pub mod uniqueness;
@@ -0,0 +1,36 @@
+use crate::{
+    errors::api_error::ApiError,
+    models::tag::{MAX_TAGS_PER_TICKET, MAX_TAG_LENGTH},
+};
+use tracing::error;
+
+/// Normalizes free-form tags to trimmed lowercase, without duplicates,
+/// checking their length and count.
+pub fn normalize_tags(tags: &[String]) -> Result<Vec<String>, ApiError> {
+    let mut normalized: Vec<String> = Vec::new();
+
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+
+        if tag.is_empty() || tag.chars().count() > MAX_TAG_LENGTH {
+            error!("Invalid tag '{tag}'.");
+            return Err(ApiError::InvalidTags(format!(
+                "Tags must be between 1 and {MAX_TAG_LENGTH} chars."
+            )));
+        }
+
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+
+    if normalized.len() > MAX_TAGS_PER_TICKET {
+        error!("Too many tags: {}", normalized.len());
+        return Err(ApiError::InvalidTags(format!(
+            "A ticket can have up to {MAX_TAGS_PER_TICKET} tags."
+        )));
+    }
+
+    normalized.sort();
+    Ok(normalized)
+}
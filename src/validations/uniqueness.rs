@@ -1,6 +1,7 @@
 use crate::database::AppState;
 use crate::errors::api_error::ApiError;
 use tracing::error;
+use uuid::Uuid;
 
 /// Check if there is already another user with the same username.
 pub async fn is_user_unique(state: &AppState, username: &str) -> Result<(), ApiError> {
@@ -17,3 +18,28 @@ pub async fn is_user_unique(state: &AppState, username: &str) -> Result<(), ApiE
         Ok(())
     }
 }
+
+/// Check if there is already another category with the same name.
+///
+/// On updates, `id` is the category being updated, so keeping or recasing its own name is allowed.
+pub async fn is_category_unique(
+    state: &AppState,
+    name: &str,
+    id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let exists = sqlx::query(
+        r#"SELECT id FROM categories WHERE LOWER(name) = LOWER($1) AND ($2::uuid IS NULL OR id <> $2);"#,
+    )
+    .bind(name)
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .is_some();
+
+    if exists {
+        error!("Category '{name}' already exists.");
+        Err(ApiError::AlreadyExists)
+    } else {
+        Ok(())
+    }
+}